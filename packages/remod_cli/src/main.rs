@@ -1,8 +1,30 @@
 use clap::{Parser as ClapParser, Subcommand};
-use glob::glob;
+use glob::{glob, Pattern};
 use remod_config::Config;
+use remod_core::a11y::A11y;
+use remod_core::barrels::Barrels;
+use remod_core::class_components::ClassComponents;
+use remod_core::create_root::CreateRoot;
+use remod_core::declaration_style::{ComponentStyle, DeclarationStyle};
+use remod_core::default_props::DefaultProps;
 use remod_core::display_name::DisplayName;
+use remod_core::export_style::{ExportStyle, ExportStyleConversion};
+use remod_core::forward_ref::ForwardRef;
+use remod_core::i18n::I18n;
+use remod_core::memo::Memo;
+use remod_core::move_component::MoveComponent;
+use remod_core::prop_types::PropTypes;
+use remod_core::props_docs::PropsDocs;
+use remod_core::props_interface::PropsInterface;
+use remod_core::react_import::ReactImport;
+use remod_core::rename_component::RenameComponent;
+use remod_core::scaffold::NewComponent;
+use remod_core::split::Split;
 use remod_core::storybook::Storybook;
+use remod_core::test_ids::TestIds;
+use remod_core::testing_library::TestingLibrary;
+use remod_core::unused::Unused;
+use remod_core::use_client::UseClient;
 use std::fs::read_to_string;
 use std::path::{Path, PathBuf};
 
@@ -26,8 +48,127 @@ enum Commands {
         #[command(subcommand)]
         command: Option<DisplayNamesSubCommands>,
     },
+    /// Create new files following the config
+    New {
+        #[command(subcommand)]
+        command: NewSubCommands,
+    },
     /// Create Stories for components. Creates a story file if doesn't exist
-    Stories,
+    Stories {
+        /// Emit a MDX docs page alongside every created story. This option overrides the config
+        #[arg(long)]
+        docs: bool,
+    },
+    /// Create React Testing Library tests for components. Creates a test file if doesn't exist
+    Tests,
+    /// Convert `propTypes` of components into TypeScript props interfaces. JavaScript files are renamed to `.tsx`
+    PropTypes,
+    /// Move `defaultProps` of function components into default values of their props destructuring
+    DefaultProps,
+    /// Unwrap components from `forwardRef`, passing the ref as a prop instead
+    ForwardRef,
+    /// Convert class components into function components using hooks. Classes which can not be converted safely are reported
+    ClassComponents,
+    /// Remove `import React` from modules which only need it for JSX
+    ReactImport {
+        /// Rewrite `React.useState` and the like to named imports so the import can be removed
+        #[arg(long)]
+        named_imports: bool,
+    },
+    /// Declare all components either as arrow functions or as function declarations
+    DeclarationStyle {
+        /// Set the declaration style, `arrow` or `function`. This option overrides the config
+        #[arg(short, long, value_name = "style")]
+        style: Option<String>,
+    },
+    /// Export components either as named exports or as default exports and update the imports of them
+    ExportStyle {
+        /// Set the export style, `named` or `default`. This option overrides the config
+        #[arg(short, long, value_name = "style")]
+        style: Option<String>,
+    },
+    /// Report exported components which are never imported or rendered. The exports of `entry_points` count as used
+    Unused,
+    /// Add the `"use client"` directive to components using hooks, event handlers or browser globals
+    UseClient {
+        /// Remove the directive from components which do not need it instead
+        #[arg(long)]
+        remove: bool,
+    },
+    /// Wrap exported components in `memo`. Select them by name or with `--pure-only`
+    Memo {
+        /// Name or glob pattern (`*Item`) of the components to wrap. Can be repeated
+        #[arg(short, long, value_name = "name")]
+        name: Vec<String>,
+        /// Only wrap components which call no hooks and read no contexts
+        #[arg(long)]
+        pure_only: bool,
+    },
+    /// Wrap the JSX text of components in `t("key")` calls and extract the messages
+    I18n,
+    /// Report accessibility issues of the JSX rendered by components
+    A11y {
+        /// Write the issues as a SARIF log to the file
+        #[arg(long, value_name = "file")]
+        sarif: Option<PathBuf>,
+    },
+    /// Write `index` files re-exporting the components and their props types of every directory
+    Barrels {
+        /// Re-export the component modules with `export *` instead of naming their exports
+        #[arg(long)]
+        export_all: bool,
+        /// Report missing and out of date barrels without writing them
+        #[arg(long)]
+        check: bool,
+    },
+    /// Convert `ReactDOM.render` and `ReactDOM.hydrate` entry points to `createRoot` and `hydrateRoot`
+    CreateRoot,
+    /// Move inline props types of components into exported `{Component}Props` interfaces
+    PropsInterface,
+    /// Write the props of the exported components with their types, default values and descriptions as JSON
+    PropsDocs {
+        /// JSON file the documentation is written to
+        #[arg(short, long, value_name = "file", default_value = "props.json")]
+        output: PathBuf,
+    },
+    /// Move every exported component but the primary one of a file into a file of its own and update the importing files
+    Split {
+        /// Re-export the moved components from the file they were split from
+        #[arg(long)]
+        re_export: bool,
+    },
+    /// Move a component with its story, test and style files and update the imports pointing to them, `tsconfig.json` path aliases included
+    Move {
+        /// The file to move
+        from: PathBuf,
+        /// The path to move it to, or the directory to move it into
+        to: PathBuf,
+    },
+    /// Rename a component along with its props interface, display name, story file and every import and JSX usage of it
+    RenameComponent {
+        /// Current name of the component
+        old: String,
+        /// New name of the component
+        new: String,
+        /// Show the changes without writing them
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Add `data-testid` to the root element of components
+    TestIds {
+        /// Strip the added `data-testid` attributes for production builds instead
+        #[arg(long)]
+        remove: bool,
+    },
+}
+
+#[derive(Subcommand, Debug, Clone)]
+enum NewSubCommands {
+    /// Create a component folder with the component, its story, its test and a barrel
+    Component {
+        /// Path of the component under `root_dir`, `Forms/Input`
+        path: String,
+    },
 }
 
 #[derive(Subcommand, Debug, Clone)]
@@ -123,11 +264,204 @@ fn main() {
                 }
                 display_names.display_stats();
             }
-            Commands::Stories => {
-                let mut storybook = Storybook::default();
+            Commands::Stories { docs } => {
+                let mut storybook = Storybook {
+                    docs: docs || config.story_docs.unwrap_or(false),
+                    ..Default::default()
+                };
                 storybook.emit_story_files(files, &config);
                 storybook.display_stats();
             }
+            Commands::Tests => {
+                let mut testing_library = TestingLibrary::default();
+                testing_library.emit_test_files(files, &config);
+                testing_library.display_stats();
+            }
+            Commands::PropTypes => {
+                let mut prop_types = PropTypes::default();
+                prop_types.migrate_prop_types(files, &config);
+                prop_types.display_stats();
+            }
+            Commands::DefaultProps => {
+                let mut default_props = DefaultProps::default();
+                default_props.migrate_default_props(files, &config);
+                default_props.display_stats();
+            }
+            Commands::ForwardRef => {
+                let mut forward_ref = ForwardRef::default();
+                forward_ref.remove_forward_ref(files, &config);
+                forward_ref.display_stats();
+            }
+            Commands::ClassComponents => {
+                let mut class_components = ClassComponents::default();
+                class_components.convert_class_components(files, &config);
+                class_components.display_stats();
+            }
+            Commands::ReactImport { named_imports } => {
+                let mut react_import = ReactImport {
+                    named_imports,
+                    ..Default::default()
+                };
+                react_import.remove_react_import(files, &config);
+                react_import.display_stats();
+            }
+            Commands::DeclarationStyle { style } => {
+                match style
+                    .as_deref()
+                    .or(config.component_style.as_deref())
+                    .and_then(ComponentStyle::from_name)
+                {
+                    Some(style) => {
+                        let mut declaration_style = DeclarationStyle::new(style);
+                        declaration_style.convert_declaration_style(files, &config);
+                        declaration_style.display_stats();
+                    }
+                    None => {
+                        println!("Cannot proceed without a declaration style. Run `remod declaration-style -s arrow` or `remod declaration-style -s function`");
+                    }
+                }
+            }
+            Commands::ExportStyle { style } => {
+                match style
+                    .as_deref()
+                    .or(config.export_style.as_deref())
+                    .and_then(ExportStyle::from_name)
+                {
+                    Some(style) => {
+                        let mut export_style = ExportStyleConversion::new(style);
+                        export_style.convert_export_style(files, &config);
+                        export_style.display_stats();
+                    }
+                    None => {
+                        println!("Cannot proceed without an export style. Run `remod export-style -s named` or `remod export-style -s default`");
+                    }
+                }
+            }
+            Commands::Unused => {
+                let mut unused = Unused::default();
+                unused.find_unused_components(files, &config);
+                unused.display_stats();
+            }
+            Commands::UseClient { remove } => {
+                let mut use_client = UseClient {
+                    remove,
+                    ..Default::default()
+                };
+                use_client.use_client(files, &config);
+                use_client.display_stats();
+            }
+            Commands::Memo { name, pure_only } => {
+                if name.is_empty() && !pure_only {
+                    println!("Cannot proceed without selecting components. Run `remod memo -n NAME` or `remod memo --pure-only`");
+                    return;
+                }
+                let names = name
+                    .iter()
+                    .filter_map(|n| match Pattern::new(n) {
+                        Ok(pattern) => Some(pattern),
+                        Err(..) => {
+                            println!("Name Pattern match failed for {}", n);
+                            None
+                        }
+                    })
+                    .collect();
+                let mut memo = Memo {
+                    names,
+                    pure_only,
+                    ..Default::default()
+                };
+                memo.wrap_in_memo(files, &config);
+                memo.display_stats();
+            }
+            Commands::I18n => {
+                let mut i18n = I18n::default();
+                i18n.translate(files, &config);
+                i18n.display_stats();
+            }
+            Commands::A11y { sarif } => {
+                let mut a11y = A11y {
+                    sarif,
+                    ..Default::default()
+                };
+                a11y.check_a11y(files, &config);
+                a11y.display_stats();
+            }
+            Commands::New { command } => match command {
+                NewSubCommands::Component { path } => {
+                    let mut new_component = NewComponent {
+                        path,
+                        ..Default::default()
+                    };
+                    new_component.create_component(&config);
+                    new_component.display_stats();
+                }
+            },
+            Commands::Barrels { export_all, check } => {
+                let mut barrels = Barrels {
+                    export_all,
+                    check,
+                    ..Default::default()
+                };
+                barrels.update_barrels(files, &config);
+                let stale = barrels.stale;
+                barrels.display_stats();
+                if stale > 0 {
+                    std::process::exit(1);
+                }
+            }
+            Commands::CreateRoot => {
+                let mut create_root = CreateRoot::default();
+                create_root.create_root(files, &config);
+                create_root.display_stats();
+            }
+            Commands::PropsDocs { output } => {
+                let mut props_docs = PropsDocs {
+                    output,
+                    ..Default::default()
+                };
+                props_docs.extract_props_docs(files, &config);
+                props_docs.display_stats();
+            }
+            Commands::PropsInterface => {
+                let mut props_interface = PropsInterface::default();
+                props_interface.extract_props_interfaces(files, &config);
+                props_interface.display_stats();
+            }
+            Commands::Split { re_export } => {
+                let mut split = Split {
+                    re_export,
+                    ..Default::default()
+                };
+                split.split_files(files, &config);
+                split.display_stats();
+            }
+            Commands::Move { from, to } => {
+                let mut move_component = MoveComponent {
+                    from,
+                    to,
+                    ..Default::default()
+                };
+                move_component.move_component(files, &config);
+                move_component.display_stats();
+            }
+            Commands::RenameComponent { old, new, dry_run } => {
+                let mut rename_component = RenameComponent {
+                    old,
+                    new,
+                    dry_run,
+                    ..Default::default()
+                };
+                rename_component.rename_component(files, &config);
+                rename_component.display_stats();
+            }
+            Commands::TestIds { remove } => {
+                let mut test_ids = TestIds {
+                    remove,
+                    ..Default::default()
+                };
+                test_ids.update_test_ids(files, &config);
+                test_ids.display_stats();
+            }
         },
         None => {
             println!("Cannot run remod without any commands run `remod -h` to see the list of available commands");
@@ -1,6 +1,6 @@
 use remod_config::Config;
 use remod_core::arrow_components::ArrowExpressionComponents;
-use remod_core::storybook::Storybook;
+use remod_core::storybook::{StoryFiles, Storybook};
 use serde_json::{Map, Value};
 use std::path::{Path, PathBuf};
 use tokio::try_join;
@@ -15,6 +15,55 @@ struct Backend {
     commands: Vec<String>,
 }
 
+impl Backend {
+    /// Creates the file with the contents, for the docs pages of the stories
+    async fn create_file(&self, path: &str, contents: String) {
+        let uri = match Url::from_file_path(path) {
+            Ok(uri) => uri,
+            Err(..) => {
+                self.client
+                    .log_message(MessageType::ERROR, "Error parsing URI")
+                    .await;
+                return;
+            }
+        };
+        let create = self.client.apply_edit(WorkspaceEdit {
+            changes: None,
+            document_changes: Some(DocumentChanges::Operations(vec![
+                DocumentChangeOperation::Op(ResourceOp::Create(CreateFile {
+                    uri: uri.clone(),
+                    options: Some(CreateFileOptions {
+                        ignore_if_exists: Some(false),
+                        overwrite: Some(false),
+                    }),
+                    annotation_id: None,
+                })),
+                DocumentChangeOperation::Edit(TextDocumentEdit {
+                    text_document: OptionalVersionedTextDocumentIdentifier { uri, version: None },
+                    edits: vec![OneOf::Left(TextEdit {
+                        range: Range::default(),
+                        new_text: contents,
+                    })],
+                }),
+            ])),
+            change_annotations: None,
+        });
+        match create.await {
+            Ok(response) if response.applied => {
+                self.client
+                    .log_message(MessageType::INFO, format!("Created {}", path))
+                    .await
+            }
+            Ok(..) => {
+                self.client
+                    .log_message(MessageType::ERROR, format!("Could not create {}", path))
+                    .await
+            }
+            Err(e) => self.client.log_message(MessageType::ERROR, e).await,
+        }
+    }
+}
+
 #[tower_lsp::async_trait]
 impl LanguageServer for Backend {
     async fn initialize(&self, _: InitializeParams) -> Result<InitializeResult> {
@@ -137,12 +186,19 @@ impl LanguageServer for Backend {
                     .log_message(MessageType::ERROR, "Error parsing command arguments")
                     .await;
             } else {
-                let mut story_book = Storybook::default();
+                let story_book = Storybook::default();
 
-                let story_file =
+                let story_files =
                     story_book.pre_process_story_module(symbol, &path.unwrap(), &self.config);
-                match story_file {
-                    Ok((stf, file)) => {
+                match story_files {
+                    Ok(StoryFiles { story, docs }) => {
+                        if let Some((docs_path, contents)) = docs {
+                            self.create_file(&docs_path, contents).await;
+                        }
+                        let (stf, file) = match story {
+                            Some(story) => story,
+                            None => return Ok(Some(Value::Null)),
+                        };
                         let path = Path::new(&file);
                         self.client
                             .log_message(MessageType::LOG, format!("Trying to parse {}", &file))
@@ -221,20 +277,21 @@ impl LanguageServer for Backend {
                                                                 "Story successfully populated",
                                                             )
                                                             .await;
-                                                        let (start_loc, end_loc) =
-                                                            stf.find_story_ident_loc(&self.config);
-                                                        let selection_range = Some(Range {
-                                                            start: Position {
-                                                                line: (start_loc.line as u32) - 1,
-                                                                character: start_loc.col_display
-                                                                    as u32,
-                                                            },
-                                                            end: Position {
-                                                                line: (end_loc.line as u32) - 1,
-                                                                character: end_loc.col_display
-                                                                    as u32,
-                                                            },
-                                                        });
+                                                        let selection_range = stf
+                                                            .find_story_ident_loc(&self.config)
+                                                            .map(|(start_loc, end_loc)| Range {
+                                                                start: Position {
+                                                                    line: (start_loc.line as u32)
+                                                                        - 1,
+                                                                    character: start_loc.col_display
+                                                                        as u32,
+                                                                },
+                                                                end: Position {
+                                                                    line: (end_loc.line as u32) - 1,
+                                                                    character: end_loc.col_display
+                                                                        as u32,
+                                                                },
+                                                            });
                                                         let _ = self
                                                             .client
                                                             .show_document(ShowDocumentParams {
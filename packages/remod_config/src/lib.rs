@@ -1,5 +1,17 @@
+use std::collections::HashMap;
+
 use serde::Deserialize;
 use serde_json::Result;
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct StoryDecorator {
+    /// The decorator snippet to add to the `decorators` of the story meta
+    pub decorator: String,
+    /// Import statements required by the decorator snippet
+    #[serde(default)]
+    pub imports: Vec<String>,
+}
+
 #[derive(Debug, Deserialize)]
 pub struct Config {
     /// the root directory to use for scanning
@@ -20,6 +32,40 @@ pub struct Config {
     /// Ignore the pattern matched in glob
     #[serde(default)]
     pub ignore: Vec<String>,
+    /// Map of hooks or contexts (`useProps`, `ThemeContext`) to the decorators that provide them in stories
+    #[serde(default)]
+    pub story_decorators: HashMap<String, StoryDecorator>,
+    /// The tool to create stories for. One of `storybook`, `ladle` or `cosmos`. Defaults to `storybook`
+    #[serde(default)]
+    pub story_target: Option<String>,
+    /// Emit a MDX docs page for every story file created
+    #[serde(default)]
+    pub story_docs: Option<bool>,
+    /// The test runner to write test files for. One of `jest` or `vitest`. Defaults to `jest`
+    #[serde(default)]
+    pub test_runner: Option<String>,
+    /// The file extension pattern to use when creating test files
+    #[serde(default)]
+    pub test_file_ext: Option<String>,
+    /// The declaration style of components. One of `arrow` or `function`
+    #[serde(default)]
+    pub component_style: Option<String>,
+    /// How components are exported. One of `named` or `default`
+    #[serde(default)]
+    pub export_style: Option<String>,
+    /// The `data-testid` value added to the root elements of components. `{component}` and
+    /// `{component-kebab}` are replaced by the component name
+    #[serde(default)]
+    pub test_id_template: Option<String>,
+    /// The module providing the `useTranslation` hook
+    #[serde(default)]
+    pub i18n_import: Option<String>,
+    /// The JSON file the extracted translation messages are written to
+    #[serde(default)]
+    pub i18n_messages: Option<String>,
+    /// Patterns of the entry point files, every export of them counts as used
+    #[serde(default)]
+    pub entry_points: Vec<String>,
 }
 
 impl Default for Config {
@@ -31,6 +77,17 @@ impl Default for Config {
             display_name_prefix: Default::default(),
             ignore: Default::default(),
             story_file_ext: Some(String::from(".stories.tsx")),
+            story_decorators: Default::default(),
+            story_target: Default::default(),
+            story_docs: Default::default(),
+            test_runner: Default::default(),
+            test_file_ext: Some(String::from(".test.tsx")),
+            component_style: Default::default(),
+            export_style: Default::default(),
+            test_id_template: Some(String::from("{component-kebab}")),
+            i18n_import: Some(String::from("react-i18next")),
+            i18n_messages: Some(String::from("messages.json")),
+            entry_points: Default::default(),
         }
     }
 }
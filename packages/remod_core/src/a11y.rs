@@ -0,0 +1,447 @@
+use std::{fs, path::PathBuf};
+
+use glob::Paths;
+use remod_config::Config;
+use serde_json::{json, Value};
+use swc_common::{SourceMap, Span};
+use swc_ecma_ast::{
+    Expr, JSXAttr, JSXAttrName, JSXAttrOrSpread, JSXAttrValue, JSXElement, JSXElementChild,
+    JSXElementName, JSXExpr, JSXOpeningElement, Lit, Module, UnaryOp,
+};
+use swc_ecma_visit::{Visit, VisitWith};
+
+use crate::{
+    display_name::module_components,
+    test_ids::{component_body, ComponentBody},
+    utils::{parse_module, should_ignore_entry},
+};
+
+/// Elements keyboard users can reach without a `tabIndex`
+const INTERACTIVE_ELEMENTS: [&str; 6] = ["a", "button", "input", "select", "textarea", "summary"];
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum A11yRule {
+    ImgAlt,
+    ClickEventsHaveRole,
+    AriaHiddenFocusable,
+    MissingLabel,
+}
+
+impl A11yRule {
+    pub const ALL: [A11yRule; 4] = [
+        A11yRule::ImgAlt,
+        A11yRule::ClickEventsHaveRole,
+        A11yRule::AriaHiddenFocusable,
+        A11yRule::MissingLabel,
+    ];
+
+    pub fn id(&self) -> &'static str {
+        match self {
+            A11yRule::ImgAlt => "img-alt",
+            A11yRule::ClickEventsHaveRole => "click-events-have-role",
+            A11yRule::AriaHiddenFocusable => "aria-hidden-focusable",
+            A11yRule::MissingLabel => "missing-label",
+        }
+    }
+
+    pub fn description(&self) -> &'static str {
+        match self {
+            A11yRule::ImgAlt => "Images need an `alt` text, empty for decorative images",
+            A11yRule::ClickEventsHaveRole => {
+                "Non-interactive elements with click handlers need a `role` and a `tabIndex`"
+            }
+            A11yRule::AriaHiddenFocusable => {
+                "Focusable elements can not be hidden from assistive technologies"
+            }
+            A11yRule::MissingLabel => "Form controls and buttons need an accessible label",
+        }
+    }
+}
+
+/// Accessibility issue found in a module. Lines and columns start at 1
+#[derive(Debug, Clone, PartialEq)]
+pub struct A11yIssue {
+    pub rule: A11yRule,
+    pub message: String,
+    pub line: usize,
+    pub column: usize,
+}
+
+fn find_attr<'a>(element: &'a JSXOpeningElement, name: &str) -> Option<&'a JSXAttr> {
+    element.attrs.iter().find_map(|attr| match attr {
+        JSXAttrOrSpread::JSXAttr(attr) => match attr.name {
+            JSXAttrName::Ident(ref id) if &*id.sym == name => Some(attr),
+            _ => None,
+        },
+        JSXAttrOrSpread::SpreadElement(..) => None,
+    })
+}
+
+fn has_spread(element: &JSXOpeningElement) -> bool {
+    element
+        .attrs
+        .iter()
+        .any(|attr| matches!(attr, JSXAttrOrSpread::SpreadElement(..)))
+}
+
+fn str_value(attr: &JSXAttr) -> Option<String> {
+    match attr.value {
+        Some(JSXAttrValue::Lit(Lit::Str(ref value))) => Some(value.value.to_string()),
+        _ => None,
+    }
+}
+
+/// `aria-hidden`, `aria-hidden="true"` or `aria-hidden={true}`
+fn is_true(attr: &JSXAttr) -> bool {
+    match attr.value {
+        None => true,
+        Some(JSXAttrValue::Lit(Lit::Str(ref value))) => &*value.value == "true",
+        Some(JSXAttrValue::JSXExprContainer(ref container)) => matches!(
+            container.expr,
+            JSXExpr::Expr(ref expr) if matches!(**expr, Expr::Lit(Lit::Bool(ref b)) if b.value)
+        ),
+        _ => false,
+    }
+}
+
+/// Value of `tabIndex="0"` or `tabIndex={-1}`. `None` when it is computed
+fn tab_index(attr: &JSXAttr) -> Option<i64> {
+    fn number(expr: &Expr) -> Option<i64> {
+        match expr {
+            Expr::Lit(Lit::Num(num)) => Some(num.value as i64),
+            Expr::Unary(unary) if unary.op == UnaryOp::Minus => number(&unary.arg).map(|n| -n),
+            _ => None,
+        }
+    }
+    match attr.value {
+        Some(JSXAttrValue::Lit(Lit::Str(ref value))) => value.value.parse().ok(),
+        Some(JSXAttrValue::JSXExprContainer(ref container)) => match container.expr {
+            JSXExpr::Expr(ref expr) => number(expr),
+            JSXExpr::JSXEmptyExpr(..) => None,
+        },
+        _ => None,
+    }
+}
+
+/// `htmlFor` values of the labels of the module
+#[derive(Default)]
+struct LabelTargets {
+    pub ids: Vec<String>,
+}
+
+impl Visit for LabelTargets {
+    fn visit_jsx_attr(&mut self, n: &JSXAttr) {
+        if matches!(n.name, JSXAttrName::Ident(ref id) if &*id.sym == "htmlFor") {
+            if let Some(value) = str_value(n) {
+                self.ids.push(value);
+            }
+        }
+        n.visit_children_with(self);
+    }
+}
+
+struct A11yVisitor<'a> {
+    cm: &'a SourceMap,
+    label_targets: &'a [String],
+    /// Depth of the enclosing `<label>` elements
+    in_label: usize,
+    pub issues: Vec<A11yIssue>,
+}
+
+impl A11yVisitor<'_> {
+    fn report(&mut self, rule: A11yRule, span: Span, message: String) {
+        let loc = self.cm.lookup_char_pos(span.lo);
+        self.issues.push(A11yIssue {
+            rule,
+            message,
+            line: loc.line,
+            column: loc.col.0 + 1,
+        });
+    }
+
+    fn check_element(&mut self, element: &JSXElement) {
+        let opening = &element.opening;
+        let name = match opening.name {
+            JSXElementName::Ident(ref id) => id.sym.to_string(),
+            _ => return,
+        };
+        let intrinsic = name.starts_with(char::is_lowercase);
+        // Buttons and links of component libraries, `UnstyledButton`
+        let interactive = if intrinsic {
+            INTERACTIVE_ELEMENTS.contains(&name.as_str())
+                && (name != "input"
+                    || find_attr(opening, "type").and_then(str_value).as_deref() != Some("hidden"))
+        } else {
+            name.ends_with("Button") || name.ends_with("Link")
+        };
+        let tab_index_attr = find_attr(opening, "tabIndex");
+        let focusable = match tab_index_attr {
+            Some(attr) => tab_index(attr).is_some_and(|index| index >= 0),
+            None => interactive,
+        };
+        if let Some(attr) = find_attr(opening, "aria-hidden") {
+            if is_true(attr) && focusable {
+                self.report(
+                    A11yRule::AriaHiddenFocusable,
+                    opening.span,
+                    format!(
+                        "`<{}>` is hidden with `aria-hidden` but can still be focused. Set `tabIndex={{-1}}` or remove `aria-hidden`",
+                        name
+                    ),
+                );
+            }
+        }
+        // A spread may hold any of the missing attributes
+        if !intrinsic || has_spread(opening) {
+            return;
+        }
+        let labelled = ["aria-label", "aria-labelledby", "title"]
+            .iter()
+            .any(|attr| find_attr(opening, attr).is_some());
+        match name.as_str() {
+            "img" if find_attr(opening, "alt").is_none() => self.report(
+                A11yRule::ImgAlt,
+                opening.span,
+                String::from("`<img>` has no `alt` text. Use `alt=\"\"` for decorative images"),
+            ),
+            "input" | "select" | "textarea" if !labelled && self.in_label == 0 => {
+                let input_type = find_attr(opening, "type").and_then(str_value);
+                if matches!(
+                    input_type.as_deref(),
+                    Some("hidden" | "submit" | "reset" | "button" | "image")
+                ) {
+                    return;
+                }
+                let labelled_by_id = match find_attr(opening, "id") {
+                    Some(attr) => match str_value(attr) {
+                        Some(id) => self.label_targets.contains(&id),
+                        // Computed ids can not be matched with their labels
+                        None => true,
+                    },
+                    None => false,
+                };
+                if !labelled_by_id {
+                    self.report(
+                        A11yRule::MissingLabel,
+                        opening.span,
+                        format!(
+                            "`<{}>` has no label. Wrap it in a `<label>` or set `aria-label`",
+                            name
+                        ),
+                    );
+                }
+            }
+            "button" if !labelled => {
+                let empty = element.children.iter().all(|child| match child {
+                    JSXElementChild::JSXText(text) => text.value.trim().is_empty(),
+                    _ => false,
+                });
+                if empty {
+                    self.report(
+                        A11yRule::MissingLabel,
+                        opening.span,
+                        String::from("`<button>` has no content. Set `aria-label` to describe it"),
+                    );
+                }
+            }
+            _ => {}
+        }
+        if !interactive
+            && find_attr(opening, "onClick").is_some()
+            && find_attr(opening, "role").is_none()
+            && tab_index_attr.is_none()
+        {
+            self.report(
+                A11yRule::ClickEventsHaveRole,
+                opening.span,
+                format!(
+                    "`<{}>` has a click handler but no `role` and `tabIndex`, keyboard users can not reach it",
+                    name
+                ),
+            );
+        }
+    }
+}
+
+impl Visit for A11yVisitor<'_> {
+    fn visit_jsx_element(&mut self, n: &JSXElement) {
+        self.check_element(n);
+        let is_label =
+            matches!(n.opening.name, JSXElementName::Ident(ref id) if &*id.sym == "label");
+        if is_label {
+            self.in_label += 1;
+        }
+        n.visit_children_with(self);
+        if is_label {
+            self.in_label -= 1;
+        }
+    }
+}
+
+/// Finds the accessibility issues of the JSX rendered by the components of the module
+pub fn a11y_module(module: &Module, cm: &SourceMap) -> Vec<A11yIssue> {
+    let mut label_targets = LabelTargets::default();
+    module.visit_with(&mut label_targets);
+    let mut visitor = A11yVisitor {
+        cm,
+        label_targets: &label_targets.ids,
+        in_label: 0,
+        issues: vec![],
+    };
+    for component in module_components(module) {
+        match component_body(module, &component) {
+            Some(ComponentBody::Block(block)) => block.visit_with(&mut visitor),
+            Some(ComponentBody::Expr(expr)) => expr.visit_with(&mut visitor),
+            None => {}
+        }
+    }
+    let mut issues = visitor.issues;
+    issues.sort_by_key(|issue| (issue.line, issue.column));
+    issues.dedup();
+    issues
+}
+
+/// SARIF 2.1.0 log of the issues, for code scanning tools
+pub fn sarif_log(issues: &[(PathBuf, A11yIssue)]) -> Value {
+    let rules = A11yRule::ALL
+        .iter()
+        .map(|rule| {
+            json!({
+                "id": rule.id(),
+                "shortDescription": { "text": rule.description() },
+            })
+        })
+        .collect::<Vec<Value>>();
+    let results = issues
+        .iter()
+        .map(|(path, issue)| {
+            json!({
+                "ruleId": issue.rule.id(),
+                "level": "warning",
+                "message": { "text": issue.message },
+                "locations": [{
+                    "physicalLocation": {
+                        "artifactLocation": { "uri": path.display().to_string() },
+                        "region": { "startLine": issue.line, "startColumn": issue.column },
+                    },
+                }],
+            })
+        })
+        .collect::<Vec<Value>>();
+    json!({
+        "version": "2.1.0",
+        "$schema": "https://json.schemastore.org/sarif-2.1.0.json",
+        "runs": [{
+            "tool": {
+                "driver": {
+                    "name": "remod",
+                    "rules": rules,
+                },
+            },
+            "results": results,
+        }],
+    })
+}
+
+#[derive(Debug, Default)]
+pub struct A11y {
+    /// Write a SARIF log of the issues to the file
+    pub sarif: Option<PathBuf>,
+    /// Issues found by file
+    pub issues: Vec<(PathBuf, A11yIssue)>,
+    /// Count of ignored files
+    pub ignored: i64,
+    /// Count of total files
+    pub total: i64,
+}
+
+impl A11y {
+    pub fn check_a11y(&mut self, files: Paths, config: &Config) {
+        for entry in files {
+            match entry {
+                Ok(path) => {
+                    self.total += 1;
+                    if should_ignore_entry(&config.ignore, &path) {
+                        println!("Skipping {}", path.display());
+                        self.ignored += 1;
+                        continue;
+                    }
+                    let (module, cm, _comments) = parse_module(&path, config);
+                    for issue in a11y_module(&module, &cm) {
+                        println!(
+                            "{}:{}:{} {} {}",
+                            path.display(),
+                            issue.line,
+                            issue.column,
+                            issue.rule.id(),
+                            issue.message
+                        );
+                        self.issues.push((path.to_owned(), issue));
+                    }
+                }
+                Err(e) => println!("{:?}", e),
+            }
+        }
+        if let Some(ref sarif) = self.sarif {
+            match serde_json::to_string_pretty(&sarif_log(&self.issues)) {
+                Ok(json) => match fs::write(sarif, format!("{}\n", json)) {
+                    Ok(_) => println!("=> SARIF log written to {}", sarif.display()),
+                    Err(e) => println!("{:#?}", e),
+                },
+                Err(e) => println!("{:#?}", e),
+            }
+        }
+    }
+
+    pub fn display_stats(self) {
+        let mut files = self
+            .issues
+            .iter()
+            .map(|(path, _)| path)
+            .collect::<Vec<&PathBuf>>();
+        files.dedup();
+        println!("Total {} files", self.total);
+        println!(
+            "Found {} issues in {} files",
+            self.issues.len(),
+            files.len()
+        );
+        println!("Ingored {} files", self.ignored);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_a11y() {
+        let config = Config {
+            typescript: Some(true),
+            ..Config::default()
+        };
+        let path = PathBuf::from("../../tests/a11y/gallery.tsx");
+        let (module, cm, _comments) = parse_module(&path, &config);
+        let issues = a11y_module(&module, &cm)
+            .iter()
+            .map(|issue| (issue.line, issue.rule))
+            .collect::<Vec<(usize, A11yRule)>>();
+        assert_eq!(
+            issues,
+            vec![
+                (8, A11yRule::ImgAlt),
+                (12, A11yRule::ClickEventsHaveRole),
+                (20, A11yRule::AriaHiddenFocusable),
+                (22, A11yRule::AriaHiddenFocusable),
+                (25, A11yRule::MissingLabel),
+                (32, A11yRule::MissingLabel),
+                (44, A11yRule::MissingLabel),
+            ]
+        );
+
+        // The indicators are hidden and out of the tab order
+        let path = PathBuf::from("../../tests/factory_methods/custom_factory.tsx");
+        let (module, cm, _comments) = parse_module(&path, &config);
+        assert_eq!(a11y_module(&module, &cm), vec![]);
+    }
+}
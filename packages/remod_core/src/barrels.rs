@@ -0,0 +1,342 @@
+use std::{
+    collections::BTreeMap,
+    fs,
+    path::{Path, PathBuf},
+};
+
+use glob::Paths;
+use remod_config::Config;
+use swc_common::{SourceMap, Spanned};
+use swc_ecma_ast::{Decl, Module, ModuleDecl, ModuleItem};
+
+use crate::{
+    display_name::module_components,
+    export_style::{default_export_name, is_story_or_test},
+    move_component::{resolve, Resolution},
+    split::SCRIPT_EXTENSIONS,
+    utils::{exported_names, parse_module, should_ignore_entry, snippet},
+};
+
+/// Exports of a module a barrel re-exports
+#[derive(Debug, Default, PartialEq)]
+pub struct BarrelEntry {
+    /// File name of the module without its extension
+    pub module: String,
+    /// Components exported by name
+    pub components: Vec<String>,
+    /// Component exported as the default export
+    pub default: Option<String>,
+    /// `{Component}Props` types of the components
+    pub types: Vec<String>,
+}
+
+/// Names of the exported interfaces and type aliases of the module
+fn exported_types(module: &Module) -> Vec<String> {
+    module
+        .body
+        .iter()
+        .filter_map(|item| match item {
+            ModuleItem::ModuleDecl(ModuleDecl::ExportDecl(export)) => match export.decl {
+                Decl::TsInterface(ref interface) => Some(interface.id.sym.to_string()),
+                Decl::TsTypeAlias(ref alias) => Some(alias.id.sym.to_string()),
+                _ => None,
+            },
+            _ => None,
+        })
+        .collect()
+}
+
+/// The components the module exports with their props types. Returns `None` when it exports none
+pub fn barrel_entry(module: &Module, path: &Path) -> Option<BarrelEntry> {
+    let stem = path.file_stem()?.to_string_lossy().to_string();
+    let default = default_export_name(module)
+        .filter(|name| name.starts_with(|c: char| c.is_ascii_uppercase()));
+    let exported = exported_names(module);
+    let components = module_components(module)
+        .into_iter()
+        .filter(|c| exported.contains(c) && Some(c) != default.as_ref())
+        .collect::<Vec<String>>();
+    if components.is_empty() && default.is_none() {
+        return None;
+    }
+    let types = exported_types(module);
+    let types = components
+        .iter()
+        .chain(default.iter())
+        .map(|c| format!("{}Props", c))
+        .filter(|t| types.contains(t))
+        .collect();
+    Some(BarrelEntry {
+        module: stem,
+        components,
+        default,
+        types,
+    })
+}
+
+/// Whether the module only re-exports other modules
+fn is_barrel(module: &Module) -> bool {
+    module.body.iter().all(|item| match item {
+        ModuleItem::ModuleDecl(ModuleDecl::ExportAll(..)) => true,
+        ModuleItem::ModuleDecl(ModuleDecl::ExportNamed(export)) => export.src.is_some(),
+        _ => false,
+    })
+}
+
+/// Source of the barrel exporting the entries. The re-exports of other modules in the existing
+/// barrel are kept ahead of them, unless the modules no longer exist
+pub fn barrel_source(
+    entries: &[BarrelEntry],
+    existing: Option<(&Path, &Module, &SourceMap)>,
+    export_all: bool,
+) -> String {
+    let mut lines = vec![];
+    if let Some((path, module, cm)) = existing {
+        for item in module.body.iter() {
+            let src = match item {
+                ModuleItem::ModuleDecl(ModuleDecl::ExportAll(export)) => &export.src.value,
+                ModuleItem::ModuleDecl(ModuleDecl::ExportNamed(export)) => match export.src {
+                    Some(ref src) => &src.value,
+                    None => continue,
+                },
+                _ => continue,
+            };
+            let is_entry = entries.iter().any(|entry| {
+                let source = format!("./{}", entry.module);
+                *src == *source
+                    || src
+                        .strip_prefix(source.as_str())
+                        .and_then(|ext| ext.strip_prefix('.'))
+                        .is_some_and(|ext| SCRIPT_EXTENSIONS.contains(&ext))
+            });
+            if !is_entry && resolve(path, src, None) != Resolution::Unresolved {
+                lines.push(snippet(cm, item.span()));
+            }
+        }
+    }
+    for entry in entries {
+        let source = format!("\"./{}\"", entry.module);
+        let default = entry
+            .default
+            .as_ref()
+            .map(|name| format!("default as {}", name));
+        if export_all {
+            lines.push(format!("export * from {};", source));
+            if let Some(default) = default {
+                lines.push(format!("export {{ {} }} from {};", default, source));
+            }
+            continue;
+        }
+        let names = default
+            .into_iter()
+            .chain(entry.components.iter().map(|c| c.to_owned()))
+            .collect::<Vec<String>>();
+        lines.push(format!(
+            "export {{ {} }} from {};",
+            names.join(", "),
+            source
+        ));
+        if !entry.types.is_empty() {
+            lines.push(format!(
+                "export type {{ {} }} from {};",
+                entry.types.join(", "),
+                source
+            ));
+        }
+    }
+    format!("{}\n", lines.join("\n"))
+}
+
+#[derive(Debug, Default)]
+pub struct Barrels {
+    /// Re-export the component modules with `export *` instead of naming their exports
+    pub export_all: bool,
+    /// Only report the barrels that are missing or out of date
+    pub check: bool,
+    /// Count of created barrels
+    pub created: i64,
+    ///  Count of modified barrels
+    pub modified: i64,
+    /// Count of missing or out of date barrels found by the check
+    pub stale: i64,
+    /// Count of ignored files
+    pub ignored: i64,
+    /// Count of total files
+    pub total: i64,
+}
+
+impl Barrels {
+    pub fn update_barrels(&mut self, files: Paths, config: &Config) {
+        // Component modules grouped by their directory
+        let mut directories: BTreeMap<PathBuf, Vec<BarrelEntry>> = BTreeMap::new();
+        for entry in files {
+            match entry {
+                Ok(path) => {
+                    self.total += 1;
+                    let is_index = path.file_stem().is_some_and(|stem| stem == "index");
+                    if should_ignore_entry(&config.ignore, &path) || is_story_or_test(&path, config)
+                    {
+                        self.ignored += 1;
+                        continue;
+                    }
+                    // The barrel of a directory is updated even when no component is left in it
+                    if is_index {
+                        directories
+                            .entry(path.parent().unwrap_or(Path::new("")).to_owned())
+                            .or_default();
+                        self.ignored += 1;
+                        continue;
+                    }
+                    let (module, _cm, _comments) = parse_module(&path, config);
+                    match barrel_entry(&module, &path) {
+                        Some(entry) => directories
+                            .entry(path.parent().unwrap_or(Path::new("")).to_owned())
+                            .or_default()
+                            .push(entry),
+                        None => self.ignored += 1,
+                    }
+                }
+                Err(e) => println!("{:?}", e),
+            }
+        }
+
+        for (directory, mut entries) in directories {
+            entries.sort_by(|a, b| a.module.cmp(&b.module));
+            let existing = ["ts", "tsx", "js", "jsx"]
+                .iter()
+                .map(|ext| directory.join(format!("index.{}", ext)))
+                .find(|path| path.is_file());
+            let (path, source) = match existing {
+                Some(path) => {
+                    let (module, cm, _comments) = parse_module(&path, config);
+                    if !is_barrel(&module) {
+                        println!("=> Skipping {}, it is not a barrel file", path.display());
+                        continue;
+                    }
+                    let source =
+                        barrel_source(&entries, Some((&path, &module, &cm)), self.export_all);
+                    (path, source)
+                }
+                None => {
+                    let ext = if config.typescript.unwrap_or(false) {
+                        "ts"
+                    } else {
+                        "js"
+                    };
+                    let source = barrel_source(&entries, None, self.export_all);
+                    (directory.join(format!("index.{}", ext)), source)
+                }
+            };
+            let current = fs::read_to_string(&path).ok();
+            if current.as_deref() == Some(source.as_str()) {
+                continue;
+            }
+            if self.check {
+                match current {
+                    Some(_) => println!("=> Stale barrel {}", path.display()),
+                    None => println!("=> Missing barrel {}", path.display()),
+                }
+                self.stale += 1;
+                continue;
+            }
+            match fs::write(&path, source) {
+                Ok(_) => {
+                    println!("{}", path.display());
+                    if current.is_some() {
+                        self.modified += 1;
+                    } else {
+                        self.created += 1;
+                    }
+                }
+                Err(e) => println!("{:#?}", e),
+            }
+        }
+    }
+
+    pub fn display_stats(self) {
+        println!("Total {} files", self.total);
+        if self.check {
+            println!("Found {} stale barrels", self.stale);
+        } else {
+            println!("Created {} barrels", self.created);
+            println!("Modified {} barrels", self.modified);
+        }
+        println!("Ingored {} files", self.ignored);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use glob::glob;
+
+    use super::*;
+
+    #[test]
+    fn test_barrels() {
+        let config = Config {
+            typescript: Some(true),
+            ..Config::default()
+        };
+        let directory = PathBuf::from("../../tests/barrels/components");
+        let entries = ["Button.tsx", "Card.tsx", "format.ts"]
+            .iter()
+            .filter_map(|name| {
+                let path = directory.join(name);
+                let (module, _cm, _comments) = parse_module(&path, &config);
+                barrel_entry(&module, &path)
+            })
+            .collect::<Vec<BarrelEntry>>();
+        assert_eq!(
+            entries,
+            vec![
+                BarrelEntry {
+                    module: String::from("Button"),
+                    components: vec![String::from("Button"), String::from("IconButton")],
+                    default: None,
+                    types: vec![String::from("ButtonProps")],
+                },
+                BarrelEntry {
+                    module: String::from("Card"),
+                    components: vec![],
+                    default: Some(String::from("Card")),
+                    types: vec![String::from("CardProps")],
+                },
+            ]
+        );
+
+        let index = directory.join("index.ts");
+        let (module, cm, _comments) = parse_module(&index, &config);
+        assert!(is_barrel(&module));
+        assert_eq!(
+            barrel_source(&entries, Some((&index, &module, &cm)), false),
+            r#"export * from "./hooks";
+export { Button, IconButton } from "./Button";
+export type { ButtonProps } from "./Button";
+export { default as Card } from "./Card";
+export type { CardProps } from "./Card";
+"#
+        );
+        assert_eq!(
+            barrel_source(&entries, None, true),
+            r#"export * from "./Button";
+export * from "./Card";
+export { default as Card } from "./Card";
+"#
+        );
+    }
+
+    #[test]
+    fn test_barrel_of_removed_components() {
+        let config = Config {
+            typescript: Some(true),
+            ..Config::default()
+        };
+        let files = glob("../../tests/barrels/removed/*.ts").unwrap();
+        let mut barrels = Barrels {
+            check: true,
+            ..Default::default()
+        };
+        barrels.update_barrels(files, &config);
+        assert_eq!(barrels.stale, 1);
+    }
+}
@@ -0,0 +1,915 @@
+use std::fs;
+
+use glob::Paths;
+use remod_config::Config;
+use swc_common::{BytePos, SourceMap, Span, Spanned};
+use swc_ecma_ast::{
+    BlockStmt, Callee, Class, ClassMember, Decl, DefaultDecl, Expr, ExprStmt, Function, MemberExpr,
+    MemberProp, MethodKind, Module, ModuleDecl, ModuleItem, ObjectPatProp, Pat, PatOrExpr, Prop,
+    PropName, PropOrSpread, Stmt, ThisExpr,
+};
+use swc_ecma_visit::{Visit, VisitWith};
+
+use crate::utils::{
+    apply_edits, edit_snippet, imported_name, parse_module, reindent, remove_import_specifier,
+    should_ignore_entry, snippet, IdentReferences, ReactImports, SourceEdit,
+};
+
+/// Lifecycle methods and static members without a hook equivalent
+const UNSUPPORTED_MEMBERS: [&str; 9] = [
+    "getSnapshotBeforeUpdate",
+    "componentDidCatch",
+    "getDerivedStateFromError",
+    "getDerivedStateFromProps",
+    "shouldComponentUpdate",
+    "componentWillMount",
+    "componentWillReceiveProps",
+    "componentWillUpdate",
+    "contextType",
+];
+
+fn prop_name(key: &PropName) -> Option<String> {
+    match key {
+        PropName::Ident(id) => Some(id.sym.to_string()),
+        PropName::Str(s) => Some(s.value.to_string()),
+        _ => None,
+    }
+}
+
+fn setter_name(key: &str) -> String {
+    let mut chars = key.chars();
+    match chars.next() {
+        Some(first) => format!("set{}{}", first.to_uppercase(), chars.as_str()),
+        None => String::from("set"),
+    }
+}
+
+/// Inner text of a block, without its braces
+fn block_text(block: &BlockStmt, cm: &SourceMap, edits: &[SourceEdit]) -> String {
+    let inner = Span::new(
+        block.span.lo + BytePos(1),
+        block.span.hi - BytePos(1),
+        Default::default(),
+    );
+    edit_snippet(cm, inner, edits)
+}
+
+/// Rewrites the uses of the component instance, `this.props`, `this.state` and `this.setState`
+/// into the props, state variables and callbacks of a function component
+struct InstanceVisitor<'a> {
+    cm: &'a SourceMap,
+    /// Keys of the state
+    state: &'a [String],
+    /// Names of the instance methods
+    methods: &'a [String],
+    pub edits: Vec<SourceEdit>,
+    /// State variables, props and callbacks the visited code depends on
+    pub deps: Vec<String>,
+    /// Reasons the component can not be converted
+    pub unsupported: Vec<String>,
+}
+
+impl<'a> InstanceVisitor<'a> {
+    fn new(cm: &'a SourceMap, state: &'a [String], methods: &'a [String]) -> Self {
+        InstanceVisitor {
+            cm,
+            state,
+            methods,
+            edits: vec![],
+            deps: vec![],
+            unsupported: vec![],
+        }
+    }
+
+    fn depend(&mut self, name: &str) {
+        if !self.deps.iter().any(|d| d == name) {
+            self.deps.push(name.to_string());
+        }
+    }
+
+    fn unsupported(&mut self, reason: String) {
+        if !self.unsupported.contains(&reason) {
+            self.unsupported.push(reason);
+        }
+    }
+
+    /// Text of the expression with the instance uses rewritten
+    fn rewrite(&mut self, expr: &Expr) -> String {
+        let mut visitor = InstanceVisitor::new(self.cm, self.state, self.methods);
+        expr.visit_with(&mut visitor);
+        for dep in visitor.deps.iter() {
+            self.depend(dep);
+        }
+        for reason in visitor.unsupported {
+            self.unsupported(reason);
+        }
+        edit_snippet(self.cm, expr.span(), &visitor.edits)
+    }
+
+    /// `this.setState({ ... })` as setter calls of the state variables
+    fn set_state(&mut self, stmt: &ExprStmt) -> Option<Result<String, String>> {
+        let call = stmt.expr.as_call()?;
+        let callee = match call.callee {
+            Callee::Expr(ref callee) => callee.as_member()?,
+            _ => return None,
+        };
+        match (&*callee.obj, &callee.prop) {
+            (Expr::This(..), MemberProp::Ident(prop)) if &*prop.sym == "setState" => {}
+            _ => return None,
+        }
+        let update = match (call.args.as_slice(), call.args.first().map(|a| &*a.expr)) {
+            ([_], Some(Expr::Object(update))) => update,
+            _ => {
+                return Some(Err(String::from(
+                    "calls setState with an updater function or a callback",
+                )))
+            }
+        };
+        let column = self.cm.lookup_char_pos(stmt.span.lo).col_display;
+        let mut setters = vec![];
+        for prop in update.props.iter() {
+            let (key, value) = match prop {
+                PropOrSpread::Prop(prop) => match **prop {
+                    Prop::KeyValue(ref kv) => match prop_name(&kv.key) {
+                        Some(key) => (key, self.rewrite(&kv.value)),
+                        None => return Some(Err(String::from("sets a computed state key"))),
+                    },
+                    Prop::Shorthand(ref id) => (id.sym.to_string(), id.sym.to_string()),
+                    _ => return Some(Err(String::from("sets the state with a method"))),
+                },
+                PropOrSpread::Spread(..) => {
+                    return Some(Err(String::from("spreads into setState")))
+                }
+            };
+            if !self.state.contains(&key) {
+                return Some(Err(format!("sets '{}' missing in the initial state", key)));
+            }
+            setters.push(format!("{}({});", setter_name(&key), value));
+        }
+        Some(Ok(setters.join(&format!("\n{}", " ".repeat(column)))))
+    }
+}
+
+impl Visit for InstanceVisitor<'_> {
+    fn visit_expr_stmt(&mut self, n: &ExprStmt) {
+        match self.set_state(n) {
+            Some(Ok(setters)) => self.edits.push(SourceEdit::replace(n.span, setters)),
+            Some(Err(reason)) => self.unsupported(reason),
+            None => n.visit_children_with(self),
+        }
+    }
+
+    fn visit_stmt(&mut self, n: &Stmt) {
+        // const { count } = this.state;
+        if let Stmt::Decl(Decl::Var(ref var)) = n {
+            if let [decl] = var.decls.as_slice() {
+                if let (Pat::Object(ref object), Some(Expr::Member(member))) =
+                    (&decl.name, decl.init.as_deref())
+                {
+                    let is_state = matches!(
+                        (&*member.obj, &member.prop),
+                        (Expr::This(..), MemberProp::Ident(prop)) if &*prop.sym == "state"
+                    );
+                    let keys = object
+                        .props
+                        .iter()
+                        .map(|prop| match prop {
+                            ObjectPatProp::Assign(assign) if assign.value.is_none() => {
+                                Some(assign.key.sym.to_string())
+                            }
+                            _ => None,
+                        })
+                        .collect::<Option<Vec<String>>>();
+                    if let (true, Some(keys)) = (is_state, keys) {
+                        if keys.iter().all(|k| self.state.contains(k)) {
+                            for key in keys.iter() {
+                                self.depend(key);
+                            }
+                            self.edits.push(SourceEdit::remove_lines(self.cm, var.span));
+                            return;
+                        }
+                    }
+                }
+            }
+        }
+        n.visit_children_with(self);
+    }
+
+    fn visit_member_expr(&mut self, n: &MemberExpr) {
+        // this.state.count
+        if let (Expr::Member(ref inner), MemberProp::Ident(ref key)) = (&*n.obj, &n.prop) {
+            if let (Expr::This(..), MemberProp::Ident(ref prop)) = (&*inner.obj, &inner.prop) {
+                if &*prop.sym == "state" {
+                    let key = key.sym.to_string();
+                    if self.state.contains(&key) {
+                        self.edits.push(SourceEdit::replace(n.span, key.to_owned()));
+                        self.depend(&key);
+                    } else {
+                        self.unsupported(format!("reads '{}' missing in the initial state", key));
+                    }
+                    return;
+                }
+            }
+        }
+        if let Expr::This(..) = *n.obj {
+            let name = match n.prop {
+                MemberProp::Ident(ref id) => id.sym.to_string(),
+                _ => return self.unsupported(String::from("uses a computed instance member")),
+            };
+            if name == "props" {
+                self.edits
+                    .push(SourceEdit::replace(n.span, name.to_owned()));
+                self.depend(&name);
+            } else if name == "state" {
+                let state = self.state.join(", ");
+                self.edits
+                    .push(SourceEdit::replace(n.span, format!("{{ {} }}", state)));
+                for key in self.state.iter() {
+                    self.depend(key);
+                }
+            } else if self.methods.contains(&name) {
+                self.edits
+                    .push(SourceEdit::replace(n.span, name.to_owned()));
+                self.depend(&name);
+            } else {
+                self.unsupported(format!("uses 'this.{}'", name));
+            }
+            return;
+        }
+        n.visit_children_with(self);
+    }
+
+    fn visit_this_expr(&mut self, _: &ThisExpr) {
+        self.unsupported(String::from("passes the component instance around"));
+    }
+}
+
+/// Instance method of a class component
+#[derive(Debug)]
+enum Method<'a> {
+    /// `handleClick() { ... }`
+    Function(&'a Function),
+    /// `handleClick = () => { ... }`
+    Arrow(&'a Expr),
+}
+
+/// Parts of a class component
+#[derive(Debug, Default)]
+struct ClassParts<'a> {
+    /// Keys of the state and their initial values
+    state: Vec<(String, &'a Expr)>,
+    /// Instance methods and class property arrow functions
+    methods: Vec<(String, Method<'a>)>,
+    /// Lifecycle methods keyed by name
+    lifecycle: Vec<(String, &'a Function)>,
+    render: Option<&'a BlockStmt>,
+    /// Static class properties
+    statics: Vec<(String, &'a Expr)>,
+    unsupported: Vec<String>,
+}
+
+fn state_keys<'a>(expr: &'a Expr, parts: &mut ClassParts<'a>) {
+    match expr {
+        Expr::Object(object) => {
+            for prop in object.props.iter() {
+                match prop {
+                    PropOrSpread::Prop(prop) => match **prop {
+                        Prop::KeyValue(ref kv) => match prop_name(&kv.key) {
+                            Some(key) => parts.state.push((key, &kv.value)),
+                            None => parts
+                                .unsupported
+                                .push(String::from("has a computed state key")),
+                        },
+                        _ => parts
+                            .unsupported
+                            .push(String::from("has a state that is not plain values")),
+                    },
+                    PropOrSpread::Spread(..) => parts
+                        .unsupported
+                        .push(String::from("spreads into the initial state")),
+                }
+            }
+        }
+        _ => parts
+            .unsupported
+            .push(String::from("has a state that is not an object literal")),
+    }
+}
+
+/// Whether the statement is `this.handleClick = this.handleClick.bind(this)`
+fn is_bind(stmt: &Stmt) -> bool {
+    let assign = match stmt {
+        Stmt::Expr(expr) => match *expr.expr {
+            Expr::Assign(ref assign) => assign,
+            _ => return false,
+        },
+        _ => return false,
+    };
+    let this_member = match assign.left {
+        PatOrExpr::Expr(ref left) => left.as_member(),
+        PatOrExpr::Pat(ref left) => left.as_expr().and_then(|e| e.as_member()),
+    };
+    let bind = assign.right.as_call().and_then(|call| match call.callee {
+        Callee::Expr(ref callee) => callee.as_member(),
+        _ => None,
+    });
+    match (this_member, bind) {
+        (Some(member), Some(bind)) => {
+            member.obj.is_this()
+                && matches!(bind.prop, MemberProp::Ident(ref prop) if &*prop.sym == "bind")
+        }
+        _ => false,
+    }
+}
+
+/// The state initialised in the constructor, `this.state = { ... }`
+fn constructor_state(stmt: &Stmt) -> Option<&Expr> {
+    let assign = stmt.as_expr()?.expr.as_assign()?;
+    let member = match assign.left {
+        PatOrExpr::Expr(ref left) => left.as_member()?,
+        PatOrExpr::Pat(ref left) => left.as_expr()?.as_member()?,
+    };
+    match (&*member.obj, &member.prop) {
+        (Expr::This(..), MemberProp::Ident(prop)) if &*prop.sym == "state" => Some(&assign.right),
+        _ => None,
+    }
+}
+
+fn class_parts(class: &Class) -> ClassParts<'_> {
+    let mut parts = ClassParts::default();
+    for member in class.body.iter() {
+        match member {
+            ClassMember::Constructor(constructor) => {
+                let stmts = constructor
+                    .body
+                    .as_ref()
+                    .map(|b| b.stmts.as_slice())
+                    .unwrap_or_default();
+                for stmt in stmts {
+                    let is_super = matches!(
+                        stmt.as_expr()
+                            .and_then(|e| e.expr.as_call())
+                            .map(|c| &c.callee),
+                        Some(Callee::Super(..))
+                    );
+                    if let Some(state) = constructor_state(stmt) {
+                        state_keys(state, &mut parts);
+                    } else if !is_super && !is_bind(stmt) {
+                        parts.unsupported.push(String::from(
+                            "does more than initialising the state in its constructor",
+                        ));
+                    }
+                }
+            }
+            ClassMember::Method(method) => {
+                let name = match prop_name(&method.key) {
+                    Some(name) => name,
+                    None => {
+                        parts
+                            .unsupported
+                            .push(String::from("has a computed method name"));
+                        continue;
+                    }
+                };
+                if UNSUPPORTED_MEMBERS.contains(&name.as_str()) || name.starts_with("UNSAFE_") {
+                    parts.unsupported.push(format!("uses '{}'", name));
+                } else if method.is_static {
+                    parts
+                        .unsupported
+                        .push(format!("has the static method '{}'", name));
+                } else if method.kind != MethodKind::Method {
+                    parts
+                        .unsupported
+                        .push(format!("has the accessor '{}'", name));
+                } else if method.function.is_generator {
+                    parts
+                        .unsupported
+                        .push(format!("has the generator '{}'", name));
+                } else if name == "render" {
+                    parts.render = method.function.body.as_ref();
+                } else if [
+                    "componentDidMount",
+                    "componentDidUpdate",
+                    "componentWillUnmount",
+                ]
+                .contains(&name.as_str())
+                {
+                    if name == "componentDidUpdate" && !method.function.params.is_empty() {
+                        parts.unsupported.push(String::from(
+                            "compares the previous props or state in componentDidUpdate",
+                        ));
+                    }
+                    parts.lifecycle.push((name, &method.function));
+                } else {
+                    parts
+                        .methods
+                        .push((name, Method::Function(&method.function)));
+                }
+            }
+            ClassMember::ClassProp(prop) => {
+                let name = match prop_name(&prop.key) {
+                    Some(name) => name,
+                    None => {
+                        parts
+                            .unsupported
+                            .push(String::from("has a computed class property"));
+                        continue;
+                    }
+                };
+                match (prop.is_static, prop.value.as_deref()) {
+                    (_, _) if UNSUPPORTED_MEMBERS.contains(&name.as_str()) => {
+                        parts.unsupported.push(format!("uses '{}'", name))
+                    }
+                    (true, Some(value)) => parts.statics.push((name, value)),
+                    (false, Some(value)) if name == "state" => state_keys(value, &mut parts),
+                    (false, Some(value)) if value.is_arrow() => {
+                        parts.methods.push((name, Method::Arrow(value)))
+                    }
+                    _ => parts
+                        .unsupported
+                        .push(format!("has the instance field '{}'", name)),
+                }
+            }
+            ClassMember::Empty(..) | ClassMember::TsIndexSignature(..) => {}
+            _ => parts
+                .unsupported
+                .push(String::from("has private members or static blocks")),
+        }
+    }
+    if parts.render.is_none() {
+        parts.unsupported.push(String::from("has no render method"));
+    }
+    parts
+}
+
+/// Orders the callbacks so the ones they depend on are declared before them
+fn order_callbacks(callbacks: Vec<(String, String, Vec<String>)>) -> Result<Vec<String>, String> {
+    let mut ordered: Vec<(String, String)> = vec![];
+    let mut pending = callbacks;
+    while !pending.is_empty() {
+        let next = pending.iter().position(|(_, _, deps)| {
+            deps.iter()
+                .all(|dep| ordered.iter().any(|(name, _)| name == dep))
+        });
+        match next {
+            Some(index) => {
+                let (name, text, _) = pending.remove(index);
+                ordered.push((name, text));
+            }
+            None => {
+                return Err(format!(
+                    "has methods calling each other, '{}'",
+                    pending
+                        .iter()
+                        .map(|(name, _, _)| name.to_owned())
+                        .collect::<Vec<String>>()
+                        .join("', '")
+                ))
+            }
+        }
+    }
+    Ok(ordered.into_iter().map(|(_, text)| text).collect())
+}
+
+/// A class component rewritten as a function component
+#[derive(Debug, Default)]
+struct FunctionComponent {
+    text: String,
+    /// Hooks of `react` the function component calls
+    hooks: Vec<&'static str>,
+}
+
+fn convert_class(
+    name: &str,
+    class: &Class,
+    cm: &SourceMap,
+    imports: &ReactImports,
+) -> Result<FunctionComponent, Vec<String>> {
+    let parts = class_parts(class);
+    if !parts.unsupported.is_empty() {
+        return Err(parts.unsupported);
+    }
+    let hook = |hook: &str| {
+        imports
+            .named
+            .get(hook)
+            .cloned()
+            .unwrap_or_else(|| hook.to_string())
+    };
+    let state_names = parts
+        .state
+        .iter()
+        .map(|s| s.0.clone())
+        .collect::<Vec<String>>();
+    let method_names = parts
+        .methods
+        .iter()
+        .map(|m| m.0.clone())
+        .collect::<Vec<String>>();
+    let type_params: Vec<String> = class
+        .super_type_params
+        .as_ref()
+        .map(|params| {
+            params
+                .params
+                .iter()
+                .map(|p| snippet(cm, p.span()))
+                .collect()
+        })
+        .unwrap_or_default();
+    let mut component = FunctionComponent::default();
+    let mut unsupported = vec![];
+    let mut sections = vec![];
+
+    let mut state_decls = vec![];
+    for (key, init) in parts.state.iter() {
+        let mut visitor = InstanceVisitor::new(cm, &[], &[]);
+        let init = visitor.rewrite(init);
+        unsupported.extend(visitor.unsupported);
+        let state_type = type_params
+            .get(1)
+            .map(|state| format!("<{}[\"{}\"]>", state, key))
+            .unwrap_or_default();
+        state_decls.push(format!(
+            "  const [{}, {}] = {}{}({});",
+            key,
+            setter_name(key),
+            hook("useState"),
+            state_type,
+            init
+        ));
+    }
+    if !state_decls.is_empty() {
+        component.hooks.push("useState");
+        sections.push(state_decls.join("\n"));
+    }
+
+    let mut callbacks = vec![];
+    let mut uses_props = false;
+    for (method_name, method) in parts.methods.iter() {
+        let mut visitor = InstanceVisitor::new(cm, &state_names, &method_names);
+        let function = match method {
+            Method::Function(function) => {
+                function.visit_with(&mut visitor);
+                let params = match (function.params.first(), function.params.last()) {
+                    (Some(first), Some(last)) => snippet(
+                        cm,
+                        Span::new(first.span.lo, last.span.hi, Default::default()),
+                    ),
+                    _ => String::new(),
+                };
+                let body = function
+                    .body
+                    .as_ref()
+                    .map(|body| edit_snippet(cm, body.span, &visitor.edits))
+                    .unwrap_or_else(|| String::from("{}"));
+                let prefix = if function.is_async { "async " } else { "" };
+                format!("{}({}) => {}", prefix, params, body)
+            }
+            Method::Arrow(arrow) => {
+                arrow.visit_with(&mut visitor);
+                edit_snippet(cm, arrow.span(), &visitor.edits)
+            }
+        };
+        unsupported.extend(visitor.unsupported);
+        uses_props |= visitor.deps.iter().any(|d| d == "props");
+        let deps = visitor
+            .deps
+            .into_iter()
+            .filter(|d| d != method_name)
+            .collect::<Vec<String>>();
+        let text = format!(
+            "  const {} = {}({}, [{}]);",
+            method_name,
+            hook("useCallback"),
+            function,
+            deps.join(", ")
+        );
+        let method_deps = deps
+            .into_iter()
+            .filter(|d| method_names.contains(d))
+            .collect();
+        callbacks.push((method_name.to_owned(), text, method_deps));
+    }
+    if !callbacks.is_empty() {
+        component.hooks.push("useCallback");
+        match order_callbacks(callbacks) {
+            Ok(callbacks) => sections.push(callbacks.join("\n\n")),
+            Err(reason) => unsupported.push(reason),
+        }
+    }
+
+    let mut lifecycle_body = |lifecycle: &str, indent: &str| {
+        parts
+            .lifecycle
+            .iter()
+            .find(|(name, _)| name == lifecycle)
+            .and_then(|(_, function)| function.body.as_ref())
+            .map(|body| {
+                let mut visitor = InstanceVisitor::new(cm, &state_names, &method_names);
+                body.visit_with(&mut visitor);
+                unsupported.extend(visitor.unsupported);
+                uses_props |= visitor.deps.iter().any(|d| d == "props");
+                reindent(&block_text(body, cm, &visitor.edits), indent)
+            })
+    };
+    let did_mount = lifecycle_body("componentDidMount", "    ");
+    let will_unmount = lifecycle_body("componentWillUnmount", "      ");
+    let did_update = lifecycle_body("componentDidUpdate", "    ");
+    let use_effect = hook("useEffect");
+    match (did_mount, will_unmount) {
+        (None, None) => {}
+        (did_mount, will_unmount) => {
+            let mut effect = vec![format!("  {}(() => {{", use_effect)];
+            effect.extend(did_mount.filter(|body| !body.is_empty()));
+            if let Some(will_unmount) = will_unmount {
+                effect.push(format!("    return () => {{\n{}\n    }};", will_unmount));
+            }
+            effect.push(String::from("  }, []);"));
+            sections.push(effect.join("\n"));
+            component.hooks.push("useEffect");
+        }
+    }
+    if let Some(did_update) = did_update {
+        sections.push(format!(
+            "  const hasMounted = {}(false);
+  {}(() => {{
+    if (!hasMounted.current) {{
+      hasMounted.current = true;
+      return;
+    }}
+{}
+  }});",
+            hook("useRef"),
+            use_effect,
+            did_update
+        ));
+        if !component.hooks.contains(&"useEffect") {
+            component.hooks.push("useEffect");
+        }
+        component.hooks.push("useRef");
+    }
+
+    if let Some(render) = parts.render {
+        let mut visitor = InstanceVisitor::new(cm, &state_names, &method_names);
+        render.visit_with(&mut visitor);
+        unsupported.extend(visitor.unsupported);
+        uses_props |= visitor.deps.iter().any(|d| d == "props");
+        sections.push(reindent(&block_text(render, cm, &visitor.edits), "  "));
+    }
+    if !unsupported.is_empty() {
+        unsupported.dedup();
+        return Err(unsupported);
+    }
+
+    let props = match type_params.first() {
+        Some(props_type) => format!("props: {}", props_type),
+        None if uses_props => String::from("props"),
+        None => String::new(),
+    };
+    component.text = format!(
+        "function {}({}) {{\n{}\n}}",
+        name,
+        props,
+        sections.join("\n\n")
+    );
+    for (key, value) in parts.statics.iter() {
+        component.text.push_str(&format!(
+            "\n\n{}.{} = {};",
+            name,
+            key,
+            snippet(cm, value.span())
+        ));
+    }
+    Ok(component)
+}
+
+/// Converts the class components of the module into function components using hooks.
+/// Classes relying on features without a hook equivalent are reported and left as they are.
+/// Returns `None` when nothing could be converted
+pub fn convert_class_components_module(module: &Module, cm: &SourceMap) -> Option<String> {
+    let mut imports = ReactImports::default();
+    module.visit_with(&mut imports);
+    let mut edits = vec![];
+    let mut converted: Vec<Span> = vec![];
+    let mut hooks: Vec<String> = vec![];
+    for item in module.body.iter() {
+        let (name, class) = match item {
+            ModuleItem::Stmt(Stmt::Decl(Decl::Class(decl))) => {
+                (Some(decl.ident.sym.to_string()), &decl.class)
+            }
+            ModuleItem::ModuleDecl(ModuleDecl::ExportDecl(export)) => match export.decl {
+                Decl::Class(ref decl) => (Some(decl.ident.sym.to_string()), &decl.class),
+                _ => continue,
+            },
+            ModuleItem::ModuleDecl(ModuleDecl::ExportDefaultDecl(export)) => match export.decl {
+                DefaultDecl::Class(ref class) => (
+                    class.ident.as_ref().map(|id| id.sym.to_string()),
+                    &class.class,
+                ),
+                _ => continue,
+            },
+            _ => continue,
+        };
+        let is_component = class.super_class.as_ref().is_some_and(|super_class| {
+            imports.is_react_export(super_class, "Component")
+                || imports.is_react_export(super_class, "PureComponent")
+        });
+        if !is_component {
+            continue;
+        }
+        let name = match name {
+            Some(name) => name,
+            None => {
+                println!("=> Anonymous class components can not be converted. Name them first");
+                continue;
+            }
+        };
+        match convert_class(&name, class, cm, &imports) {
+            Ok(component) => {
+                if class.super_class.as_ref().is_some_and(|super_class| {
+                    imports.is_react_export(super_class, "PureComponent")
+                }) {
+                    println!(
+                        "=> '{}' was a PureComponent. Wrap it in memo to keep skipping re-renders",
+                        name
+                    );
+                }
+                edits.push(SourceEdit::replace(class.span, component.text));
+                converted.push(class.span);
+                for hook in component.hooks {
+                    if !imports.named.contains_key(hook) && !hooks.iter().any(|h| h == hook) {
+                        hooks.push(hook.to_string());
+                    }
+                }
+            }
+            Err(reasons) => println!("=> Skipping '{}', it {}", name, reasons.join(", ")),
+        }
+    }
+    if edits.is_empty() {
+        return None;
+    }
+
+    // Component and PureComponent are not needed anymore
+    let mut replaced_import = false;
+    for export in ["Component", "PureComponent"] {
+        let local = match imports.named.get(export) {
+            Some(local) => local,
+            None => continue,
+        };
+        let mut references = IdentReferences::new(vec![local.to_owned()]);
+        module.visit_with(&mut references);
+        if !references
+            .spans
+            .iter()
+            .all(|r| converted.iter().any(|s| s.contains(*r)))
+        {
+            continue;
+        }
+        for decl in imports.decls.iter() {
+            let index = decl
+                .specifiers
+                .iter()
+                .position(|s| imported_name(s).as_deref() == Some(export));
+            match index {
+                // The specifier makes room for the hooks
+                Some(index) if !replaced_import && !hooks.is_empty() => {
+                    edits.push(SourceEdit::replace(
+                        decl.specifiers[index].span(),
+                        hooks.join(", "),
+                    ));
+                    replaced_import = true;
+                }
+                Some(index) => edits.push(remove_import_specifier(cm, decl, index)),
+                None => {}
+            }
+        }
+    }
+    if !replaced_import {
+        edits.extend(imports.add_named(module, &hooks));
+    }
+    apply_edits(cm, edits)
+}
+
+#[derive(Debug, Default)]
+pub struct ClassComponents {
+    ///  Count of modified files
+    pub modified: i64,
+    /// Count of ignored files
+    pub ignored: i64,
+    /// Count of total files
+    pub total: i64,
+}
+
+impl ClassComponents {
+    pub fn convert_class_components(&mut self, files: Paths, config: &Config) {
+        for entry in files {
+            match entry {
+                Ok(path) => {
+                    self.total += 1;
+                    if should_ignore_entry(&config.ignore, &path) {
+                        println!("Skipping {}", path.display());
+                        self.ignored += 1;
+                        continue;
+                    }
+                    let (module, cm, _comments) = parse_module(&path, config);
+                    match convert_class_components_module(&module, &cm) {
+                        Some(output) => match fs::write(&path, output) {
+                            Ok(_) => {
+                                println!("{}", path.display());
+                                self.modified += 1;
+                            }
+                            Err(e) => println!("{:#?}", e),
+                        },
+                        None => self.ignored += 1,
+                    }
+                }
+                Err(e) => println!("{:?}", e),
+            }
+        }
+    }
+
+    pub fn display_stats(self) {
+        println!("Total {} files", self.total);
+        println!("Modified {} files", self.modified);
+        println!("Ingored {} files", self.ignored);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::path::PathBuf;
+
+    use super::*;
+
+    #[test]
+    fn test_convert_class_components() {
+        let config = Config {
+            typescript: Some(true),
+            ..Config::default()
+        };
+        let path = PathBuf::from("../../tests/class_components/counter.tsx");
+        let (module, cm, _comments) = parse_module(&path, &config);
+        let output = convert_class_components_module(&module, &cm).unwrap();
+        assert_eq!(
+            output,
+            r#"import React, { useState, useCallback, useEffect, useRef } from "react";
+
+interface CounterProps {
+  label: string;
+}
+
+interface CounterState {
+  count: number;
+  active: boolean;
+}
+
+export function Counter(props: CounterProps) {
+  const [count, setCount] = useState<CounterState["count"]>(0);
+  const [active, setActive] = useState<CounterState["active"]>(false);
+
+  const toggle = useCallback(() => {
+    setActive(!active);
+  }, [active]);
+
+  const increment = useCallback(() => {
+    setCount(count + 1);
+    toggle();
+  }, [count, toggle]);
+
+  useEffect(() => {
+    document.title = props.label;
+    return () => {
+      document.title = "";
+    };
+  }, []);
+
+  const hasMounted = useRef(false);
+  useEffect(() => {
+    if (!hasMounted.current) {
+      hasMounted.current = true;
+      return;
+    }
+    document.title = `${props.label} ${count}`;
+  });
+
+  return (
+    <button className={active ? "active" : ""} onClick={increment}>
+      {props.label} {count}
+    </button>
+  );
+}
+
+Counter.displayName = "@remod/Counter";
+
+export class ErrorBoundary extends React.Component<CounterProps> {
+  componentDidCatch() {}
+
+  render() {
+    return this.props.children;
+  }
+}
+"#
+        );
+    }
+}
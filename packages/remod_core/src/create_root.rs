@@ -0,0 +1,539 @@
+use std::{collections::HashMap, fs};
+
+use glob::Paths;
+use remod_config::Config;
+use swc_common::{SourceMap, Span, Spanned};
+use swc_ecma_ast::{
+    ArrowExpr, CallExpr, Callee, Expr, ExprStmt, Function, ImportDecl, ImportSpecifier, MemberProp,
+    Module, ModuleDecl, ModuleItem,
+};
+use swc_ecma_visit::{Visit, VisitWith};
+
+use crate::{
+    declaration_style::line_indent,
+    utils::{
+        apply_edits, import_text, imported_name, parse_module, should_ignore_entry, snippet,
+        IdentReferences, SourceEdit,
+    },
+};
+
+/// Legacy root APIs of `react-dom`
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum LegacyApi {
+    Render,
+    Hydrate,
+    Unmount,
+}
+
+impl LegacyApi {
+    fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "render" => Some(LegacyApi::Render),
+            "hydrate" => Some(LegacyApi::Hydrate),
+            "unmountComponentAtNode" => Some(LegacyApi::Unmount),
+            _ => None,
+        }
+    }
+
+    fn name(&self) -> &'static str {
+        match self {
+            LegacyApi::Render => "render",
+            LegacyApi::Hydrate => "hydrate",
+            LegacyApi::Unmount => "unmountComponentAtNode",
+        }
+    }
+}
+
+struct LegacyCall {
+    api: LegacyApi,
+    call: CallExpr,
+    /// Span of the identifier referring to the import
+    reference: Span,
+    /// Span of the expression statement made of the call
+    statement: Option<Span>,
+    /// Whether the call is outside of any function
+    top_level: bool,
+}
+
+/// Finds the calls of the legacy root APIs
+struct LegacyCalls<'a> {
+    /// Local name of the default or namespace import of `react-dom`
+    namespace: Option<&'a str>,
+    /// Imported names of the named imports, keyed by their local name
+    named: &'a HashMap<String, String>,
+    depth: usize,
+    pub calls: Vec<LegacyCall>,
+}
+
+impl LegacyCalls<'_> {
+    fn legacy_api(&self, call: &CallExpr) -> Option<(LegacyApi, Span)> {
+        let callee = match call.callee {
+            Callee::Expr(ref callee) => callee,
+            _ => return None,
+        };
+        match **callee {
+            Expr::Member(ref member) => match (&*member.obj, &member.prop) {
+                (Expr::Ident(ref obj), MemberProp::Ident(ref prop))
+                    if Some(&*obj.sym) == self.namespace =>
+                {
+                    LegacyApi::from_name(&prop.sym).map(|api| (api, obj.span))
+                }
+                _ => None,
+            },
+            Expr::Ident(ref id) => self
+                .named
+                .get(&*id.sym)
+                .and_then(|imported| LegacyApi::from_name(imported))
+                .map(|api| (api, id.span)),
+            _ => None,
+        }
+    }
+
+    fn push(&mut self, call: &CallExpr, statement: Option<Span>) -> bool {
+        match self.legacy_api(call) {
+            Some((api, reference)) => {
+                self.calls.push(LegacyCall {
+                    api,
+                    call: call.to_owned(),
+                    reference,
+                    statement,
+                    top_level: self.depth == 0,
+                });
+                call.args.visit_with(self);
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+impl Visit for LegacyCalls<'_> {
+    fn visit_expr_stmt(&mut self, n: &ExprStmt) {
+        if let Expr::Call(ref call) = *n.expr {
+            if self.push(call, Some(n.span)) {
+                return;
+            }
+        }
+        n.visit_children_with(self);
+    }
+
+    fn visit_call_expr(&mut self, n: &CallExpr) {
+        if !self.push(n, None) {
+            n.visit_children_with(self);
+        }
+    }
+
+    fn visit_function(&mut self, n: &Function) {
+        self.depth += 1;
+        n.visit_children_with(self);
+        self.depth -= 1;
+    }
+
+    fn visit_arrow_expr(&mut self, n: &ArrowExpr) {
+        self.depth += 1;
+        n.visit_children_with(self);
+        self.depth -= 1;
+    }
+}
+
+/// `document.getElementById("root")` may return `null`, which `createRoot` does not accept
+fn root_container(cm: &SourceMap, container: &Expr, typescript: bool) -> String {
+    let text = snippet(cm, container.span());
+    let nullable = match container {
+        Expr::Call(call) => match call.callee {
+            Callee::Expr(ref callee) => match **callee {
+                Expr::Member(ref member) => matches!(
+                    member.prop,
+                    MemberProp::Ident(ref prop)
+                        if &*prop.sym == "getElementById" || &*prop.sym == "querySelector"
+                ),
+                _ => false,
+            },
+            _ => false,
+        },
+        _ => false,
+    };
+    if typescript && nullable {
+        format!("{}!", text)
+    } else {
+        text
+    }
+}
+
+fn line(cm: &SourceMap, span: Span) -> usize {
+    cm.lookup_char_pos(span.lo).line
+}
+
+/// Rewrites `ReactDOM.render`, `ReactDOM.hydrate` and `ReactDOM.unmountComponentAtNode` to the
+/// root APIs of `react-dom/client`. Calls which can not be converted are reported.
+/// Returns `None` when nothing was converted
+pub fn create_root_module(module: &Module, cm: &SourceMap, typescript: bool) -> Option<String> {
+    let decls = module
+        .body
+        .iter()
+        .filter_map(|item| match item {
+            ModuleItem::ModuleDecl(ModuleDecl::Import(decl)) if &*decl.src.value == "react-dom" => {
+                Some(decl)
+            }
+            _ => None,
+        })
+        .collect::<Vec<&ImportDecl>>();
+    let mut namespace = None;
+    let mut named = HashMap::new();
+    for decl in decls.iter() {
+        for specifier in decl.specifiers.iter() {
+            match specifier {
+                ImportSpecifier::Default(default) => {
+                    namespace = Some(default.local.sym.to_string())
+                }
+                ImportSpecifier::Namespace(ns) => namespace = Some(ns.local.sym.to_string()),
+                ImportSpecifier::Named(n) => {
+                    if let Some(imported) = imported_name(specifier) {
+                        named.insert(n.local.sym.to_string(), imported);
+                    }
+                }
+            }
+        }
+    }
+    let mut legacy_calls = LegacyCalls {
+        namespace: namespace.as_deref(),
+        named: &named,
+        depth: 0,
+        calls: vec![],
+    };
+    module.visit_with(&mut legacy_calls);
+    let calls = legacy_calls.calls;
+    if calls.is_empty() {
+        return None;
+    }
+
+    let mut edits = vec![];
+    let mut converted = vec![];
+    let mut client_names: Vec<&str> = vec![];
+    let mut roots: Vec<String> = vec![];
+    // Unmounted containers with the root created for them
+    let mut container_roots: HashMap<String, String> = HashMap::new();
+    for legacy in calls.iter() {
+        if legacy.api == LegacyApi::Unmount {
+            continue;
+        }
+        let args = &legacy.call.args;
+        let statement = match legacy.statement {
+            Some(statement) if args.len() == 2 && args.iter().all(|a| a.spread.is_none()) => {
+                statement
+            }
+            Some(_) if args.len() > 2 => {
+                println!(
+                    "=> Can not convert the `{}` call at line {}, roots do not take a callback. Move it into an effect of the rendered component",
+                    legacy.api.name(),
+                    line(cm, legacy.call.span)
+                );
+                continue;
+            }
+            _ => {
+                println!(
+                    "=> Can not convert the `{}` call at line {}, its return value is used",
+                    legacy.api.name(),
+                    line(cm, legacy.call.span)
+                );
+                continue;
+            }
+        };
+        let element = snippet(cm, args[0].expr.span());
+        let container = snippet(cm, args[1].expr.span());
+        let unmounts = calls
+            .iter()
+            .filter(|c| {
+                c.api == LegacyApi::Unmount
+                    && c.call.args.first().map(|arg| snippet(cm, arg.expr.span()))
+                        == Some(container.to_owned())
+            })
+            .collect::<Vec<&LegacyCall>>();
+        let unmounted = !unmounts.is_empty();
+        // A root created inside a function can not be reached by the unmount, converting the render
+        // alone would leave the unmount working on a container it no longer manages
+        let keeps_root = legacy.top_level || container_roots.contains_key(&container);
+        if unmounted && (!keeps_root || unmounts.iter().any(|c| c.statement.is_none())) {
+            println!(
+                "=> Can not convert the `{}` call at line {} along with the `unmountComponentAtNode` call at line {}. Keep the root created for the container and call `root.unmount()`",
+                legacy.api.name(),
+                line(cm, legacy.call.span),
+                unmounts
+                    .iter()
+                    .map(|c| line(cm, c.call.span).to_string())
+                    .collect::<Vec<String>>()
+                    .join(", ")
+            );
+            continue;
+        }
+        // Inside a function every call would create another root for the container
+        if legacy.api == LegacyApi::Render
+            && !legacy.top_level
+            && !container_roots.contains_key(&container)
+        {
+            println!(
+                "=> Can not convert the `render` call at line {}, it would create a root on every call of its function. Create the root of the container once and call `root.render()`",
+                line(cm, legacy.call.span)
+            );
+            continue;
+        }
+        let root = if let Some(root) = container_roots.get(&container) {
+            // The root of the container is rendered again
+            if legacy.api == LegacyApi::Hydrate {
+                println!(
+                    "=> Can not convert the `hydrate` call at line {}, a root was already created for its container",
+                    line(cm, legacy.call.span)
+                );
+                continue;
+            }
+            client_names.push("createRoot");
+            edits.push(SourceEdit::replace(
+                legacy.call.callee.span(),
+                format!("{}.render", root),
+            ));
+            edits.push(SourceEdit {
+                start: args[0].expr.span_hi(),
+                end: args[1].expr.span_hi(),
+                text: String::new(),
+            });
+            converted.push(legacy.reference);
+            continue;
+        } else if unmounted {
+            let root = (1..)
+                .map(|i| match i {
+                    1 => String::from("root"),
+                    i => format!("root{}", i),
+                })
+                .find(|name| {
+                    let mut references = IdentReferences::new(vec![name.to_owned()]);
+                    module.visit_with(&mut references);
+                    references.spans.is_empty() && !roots.contains(name)
+                })
+                .unwrap_or_default();
+            roots.push(root.to_owned());
+            container_roots.insert(container.to_owned(), root.to_owned());
+            Some(root)
+        } else {
+            None
+        };
+        let callee = legacy.call.callee.span();
+        let root_container = root_container(cm, &args[1].expr, typescript);
+        match legacy.api {
+            LegacyApi::Render => {
+                client_names.push("createRoot");
+                match root {
+                    Some(ref root) => {
+                        edits.push(SourceEdit::insert(
+                            statement.lo,
+                            format!(
+                                "const {} = createRoot({});\n{}",
+                                root,
+                                root_container,
+                                line_indent(cm, statement.lo)
+                            ),
+                        ));
+                        edits.push(SourceEdit::replace(callee, format!("{}.render", root)));
+                    }
+                    None => edits.push(SourceEdit::replace(
+                        callee,
+                        format!("createRoot({}).render", root_container),
+                    )),
+                }
+                // The container argument goes along with its separator
+                edits.push(SourceEdit {
+                    start: args[0].expr.span_hi(),
+                    end: args[1].expr.span_hi(),
+                    text: String::new(),
+                });
+            }
+            LegacyApi::Hydrate => {
+                client_names.push("hydrateRoot");
+                if let Some(ref root) = root {
+                    edits.push(SourceEdit::insert(
+                        statement.lo,
+                        format!("const {} = ", root),
+                    ));
+                }
+                edits.push(SourceEdit::replace(callee, String::from("hydrateRoot")));
+                edits.push(SourceEdit {
+                    start: args[0].expr.span_lo(),
+                    end: args[1].expr.span_hi(),
+                    text: format!("{}, {}", root_container, element),
+                });
+            }
+            LegacyApi::Unmount => {}
+        }
+        converted.push(legacy.reference);
+    }
+    for legacy in calls.iter().filter(|c| c.api == LegacyApi::Unmount) {
+        let root = legacy
+            .call
+            .args
+            .first()
+            .and_then(|arg| container_roots.get(&snippet(cm, arg.expr.span())));
+        match (root, legacy.statement) {
+            (Some(root), Some(_)) => {
+                edits.push(SourceEdit::replace(
+                    legacy.call.span,
+                    format!("{}.unmount()", root),
+                ));
+                converted.push(legacy.reference);
+            }
+            _ => println!(
+                "=> Can not convert the `unmountComponentAtNode` call at line {}. Keep the root created for the container and call `root.unmount()`",
+                line(cm, legacy.call.span)
+            ),
+        }
+    }
+    if converted.is_empty() {
+        return None;
+    }
+
+    // The imports of `react-dom` still referenced by the module are kept
+    client_names.sort();
+    client_names.dedup();
+    let client_import = format!(
+        "import {{ {} }} from \"react-dom/client\";",
+        client_names.join(", ")
+    );
+    let mut client_imported = false;
+    for decl in decls.iter() {
+        let keep = decl
+            .specifiers
+            .iter()
+            .enumerate()
+            .filter(|(_, specifier)| {
+                let local = match specifier {
+                    ImportSpecifier::Default(default) => &default.local,
+                    ImportSpecifier::Namespace(ns) => &ns.local,
+                    ImportSpecifier::Named(n) => &n.local,
+                };
+                let mut references = IdentReferences::new(vec![local.sym.to_string()]);
+                module.visit_with(&mut references);
+                references
+                    .spans
+                    .iter()
+                    .any(|span| !converted.contains(span))
+            })
+            .map(|(index, _)| index)
+            .collect::<Vec<usize>>();
+        let client = if client_imported {
+            String::new()
+        } else {
+            client_imported = true;
+            client_import.to_owned()
+        };
+        if keep.is_empty() {
+            edits.push(SourceEdit::replace(decl.span, client));
+        } else if keep.len() == decl.specifiers.len() {
+            edits.push(SourceEdit::insert(decl.span.hi, format!("\n{}", client)));
+        } else {
+            edits.push(SourceEdit::replace(
+                decl.span,
+                format!("{}\n{}", import_text(cm, decl, &keep), client),
+            ));
+        }
+    }
+    apply_edits(cm, edits)
+}
+
+#[derive(Debug, Default)]
+pub struct CreateRoot {
+    ///  Count of modified files
+    pub modified: i64,
+    /// Count of ignored files
+    pub ignored: i64,
+    /// Count of total files
+    pub total: i64,
+}
+
+impl CreateRoot {
+    pub fn create_root(&mut self, files: Paths, config: &Config) {
+        let typescript = config.typescript.unwrap_or_default();
+        for entry in files {
+            match entry {
+                Ok(path) => {
+                    self.total += 1;
+                    if should_ignore_entry(&config.ignore, &path) {
+                        println!("Skipping {}", path.display());
+                        self.ignored += 1;
+                        continue;
+                    }
+                    let (module, cm, _comments) = parse_module(&path, config);
+                    match create_root_module(&module, &cm, typescript) {
+                        Some(output) => match fs::write(&path, output) {
+                            Ok(_) => {
+                                println!("{}", path.display());
+                                self.modified += 1;
+                            }
+                            Err(e) => println!("{:#?}", e),
+                        },
+                        None => self.ignored += 1,
+                    }
+                }
+                Err(e) => println!("{:?}", e),
+            }
+        }
+    }
+
+    pub fn display_stats(self) {
+        println!("Total {} files", self.total);
+        println!("Modified {} files", self.modified);
+        println!("Ingored {} files", self.ignored);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::path::PathBuf;
+
+    use super::*;
+
+    #[test]
+    fn test_create_root() {
+        let config = Config {
+            typescript: Some(true),
+            ..Config::default()
+        };
+        let path = PathBuf::from("../../tests/create_root/index.tsx");
+        let (module, cm, _comments) = parse_module(&path, &config);
+        let output = create_root_module(&module, &cm, true).unwrap();
+        assert_eq!(
+            output,
+            r#"import React from "react";
+import { createRoot, hydrateRoot } from "react-dom/client";
+import { App } from "./App";
+import { Widget } from "./Widget";
+
+createRoot(document.getElementById("root")!).render(
+  <React.StrictMode>
+    <App />
+  </React.StrictMode>
+);
+
+const widget = document.getElementById("widget") as HTMLElement;
+const root = hydrateRoot(widget, <Widget />);
+
+export function teardown() {
+  root.unmount();
+}
+"#
+        );
+
+        // The render with a callback, the renders inside functions and the unmount keep their
+        // imports
+        let path = PathBuf::from("../../tests/create_root/legacy.tsx");
+        let (module, cm, _comments) = parse_module(&path, &config);
+        let output = create_root_module(&module, &cm, true).unwrap();
+        assert!(output.starts_with(
+            r#"import { render, unmountComponentAtNode, createPortal } from "react-dom";
+import { createRoot } from "react-dom/client";
+"#
+        ));
+        assert!(output
+            .contains("\ncreateRoot(document.getElementById(\"modal\")!).render(<Modal />);\n"));
+        assert!(output.contains("  render(<Modal />, node);\n"));
+        assert!(output.contains("  render(<Modal />, container);\n"));
+        assert!(output.contains("render(<Modal />, container, () =>"));
+    }
+}
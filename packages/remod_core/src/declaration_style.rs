@@ -0,0 +1,525 @@
+use std::fs;
+
+use glob::Paths;
+use remod_config::Config;
+use swc_common::{BytePos, SourceMap, Span, Spanned};
+use swc_ecma_ast::{
+    ArrowExpr, BlockStmtOrExpr, Decl, DefaultDecl, Expr, FnDecl, FnExpr, Function, Ident, Module,
+    ModuleDecl, ModuleItem, Pat, Stmt, ThisExpr, TsType, VarDecl, VarDeclKind,
+};
+use swc_ecma_visit::{Visit, VisitWith};
+
+use crate::{
+    display_name::{module_components, FunctionDelarationWalker},
+    utils::{
+        apply_edits, edit_snippet, parse_module, pat_end, should_ignore_entry, snippet,
+        IdentReferences, SourceEdit,
+    },
+};
+
+/// How components are declared
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ComponentStyle {
+    /// `const Foo = (props) => ...`
+    Arrow,
+    /// `function Foo(props) {...}`
+    Function,
+}
+
+impl ComponentStyle {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "arrow" => Some(ComponentStyle::Arrow),
+            "function" => Some(ComponentStyle::Function),
+            _ => None,
+        }
+    }
+}
+
+/// Finds `this` and `arguments`, which arrow functions do not bind
+#[derive(Default)]
+struct FunctionBindingsVisitor {
+    pub found: bool,
+}
+
+impl Visit for FunctionBindingsVisitor {
+    // Nested functions have bindings of their own
+    fn visit_function(&mut self, _: &Function) {}
+
+    fn visit_this_expr(&mut self, _: &ThisExpr) {
+        self.found = true;
+    }
+
+    fn visit_ident(&mut self, n: &Ident) {
+        self.found |= &*n.sym == "arguments";
+    }
+}
+
+/// Span from the first to the last of the parameters
+fn params_span(params: &[(BytePos, BytePos)]) -> Option<Span> {
+    match (params.first(), params.last()) {
+        (Some(first), Some(last)) => Some(Span::new(first.0, last.1, Default::default())),
+        _ => None,
+    }
+}
+
+/// Props type of a `React.FC<Props>` annotation. `Err` for any other annotation
+fn fc_props_type(ty: &TsType, cm: &SourceMap) -> Result<Option<String>, ()> {
+    if let TsType::TsTypeRef(type_ref) = ty {
+        let name = snippet(cm, type_ref.type_name.span());
+        let name = name.rsplit('.').next().unwrap_or_default();
+        if ["FC", "FunctionComponent", "VFC", "VoidFunctionComponent"].contains(&name) {
+            return Ok(type_ref
+                .type_params
+                .as_ref()
+                .and_then(|params| params.params.first())
+                .map(|param| snippet(cm, param.span())));
+        }
+    }
+    Err(())
+}
+
+/// Indentation of the line holding the position
+pub(crate) fn line_indent(cm: &SourceMap, pos: BytePos) -> String {
+    let loc = cm.lookup_char_pos(pos);
+    let line = loc.file.get_line(loc.line - 1).unwrap_or_default();
+    line.chars()
+        .take_while(|c| *c == ' ' || *c == '\t')
+        .collect()
+}
+
+/// `function Foo(props) {...}` text of an arrow function. The props type of a `React.FC`
+/// annotation moves to the props parameter
+fn arrow_to_function(
+    name: &str,
+    arrow: &ArrowExpr,
+    props_type: Option<String>,
+    cm: &SourceMap,
+) -> String {
+    let params = arrow
+        .params
+        .iter()
+        .map(|p| (p.span_lo(), pat_end(p).max(p.span_hi())))
+        .collect::<Vec<(BytePos, BytePos)>>();
+    let mut edits = vec![];
+    match (props_type, arrow.params.first()) {
+        (Some(props_type), Some(Pat::Ident(first))) if first.type_ann.is_none() => edits.push(
+            SourceEdit::insert(first.id.span.hi, format!(": {}", props_type)),
+        ),
+        (Some(props_type), Some(Pat::Object(first))) if first.type_ann.is_none() => edits.push(
+            SourceEdit::insert(first.span.hi, format!(": {}", props_type)),
+        ),
+        _ => {}
+    }
+    let params = params_span(&params)
+        .map(|span| edit_snippet(cm, span, &edits))
+        .unwrap_or_default();
+    // The trailing comma of `<T,>` is only needed by arrow functions in .tsx files
+    let type_params = arrow
+        .type_params
+        .as_ref()
+        .map(|t| snippet(cm, t.span).replace(",>", ">"))
+        .unwrap_or_default();
+    let return_type = arrow
+        .return_type
+        .as_ref()
+        .map(|t| format!(": {}", snippet(cm, t.type_ann.span())))
+        .unwrap_or_default();
+    let body = match *arrow.body {
+        BlockStmtOrExpr::BlockStmt(ref block) => snippet(cm, block.span),
+        BlockStmtOrExpr::Expr(ref expr) => {
+            let indent = line_indent(cm, arrow.span.lo);
+            format!(
+                "{{\n{}  return {};\n{}}}",
+                indent,
+                snippet(cm, expr.span()),
+                indent
+            )
+        }
+    };
+    format!(
+        "{}function {}{}({}){} {}",
+        if arrow.is_async { "async " } else { "" },
+        name,
+        type_params,
+        params,
+        return_type,
+        body
+    )
+}
+
+/// `(props) => {...}` text of a function
+fn function_to_arrow(function: &Function, cm: &SourceMap) -> Option<String> {
+    let body = function.body.as_ref()?;
+    let mut bindings = FunctionBindingsVisitor::default();
+    body.visit_with(&mut bindings);
+    if bindings.found || function.is_generator {
+        return None;
+    }
+    let params = function
+        .params
+        .iter()
+        .map(|p| (p.span.lo, pat_end(&p.pat).max(p.span.hi)))
+        .collect::<Vec<(BytePos, BytePos)>>();
+    let params = params_span(&params)
+        .map(|span| snippet(cm, span))
+        .unwrap_or_default();
+    let type_params = function
+        .type_params
+        .as_ref()
+        .map(|t| {
+            let text = snippet(cm, t.span);
+            // `<T>(props) =>` reads as a JSX element in .tsx files
+            match (t.params.as_slice(), text.strip_suffix('>')) {
+                ([param], Some(text)) if param.constraint.is_none() && !text.ends_with(',') => {
+                    format!("{},>", text)
+                }
+                _ => text,
+            }
+        })
+        .unwrap_or_default();
+    let return_type = function
+        .return_type
+        .as_ref()
+        .map(|t| format!(": {}", snippet(cm, t.type_ann.span())))
+        .unwrap_or_default();
+    Some(format!(
+        "{}{}({}){} => {}",
+        if function.is_async { "async " } else { "" },
+        type_params,
+        params,
+        return_type,
+        snippet(cm, body.span)
+    ))
+}
+
+/// Edits declaring the arrow function component as a function
+fn var_to_function(var: &VarDecl, cm: &SourceMap) -> Result<Option<SourceEdit>, String> {
+    let decl = match var.decls.as_slice() {
+        [decl] => decl,
+        _ => return Ok(None),
+    };
+    let (name, type_ann) = match decl.name {
+        Pat::Ident(ref id) => (id.sym.to_string(), id.type_ann.as_ref()),
+        _ => return Ok(None),
+    };
+    let props_type = match type_ann {
+        Some(type_ann) => fc_props_type(&type_ann.type_ann, cm).map_err(|_| {
+            format!(
+                "=> '{}' is annotated with a type other than React.FC. Annotate its props instead",
+                name
+            )
+        })?,
+        None => None,
+    };
+    match decl.init.as_deref() {
+        Some(Expr::Arrow(arrow)) if var.kind == VarDeclKind::Const => {
+            let text = arrow_to_function(&name, arrow, props_type, cm);
+            Ok(Some(SourceEdit::replace(var.span, text)))
+        }
+        // memo((props) => ...), the wrapper stays and the arrow becomes a named function
+        Some(Expr::Call(call)) => match call.args.first().map(|arg| &*arg.expr) {
+            Some(Expr::Arrow(arrow)) => {
+                let text = arrow_to_function(&name, arrow, props_type, cm);
+                Ok(Some(SourceEdit::replace(arrow.span, text)))
+            }
+            _ => Ok(None),
+        },
+        _ => Ok(None),
+    }
+}
+
+/// Edits declaring the function component as an arrow function
+fn function_to_var(func: &FnDecl, cm: &SourceMap) -> Result<String, String> {
+    match function_to_arrow(&func.function, cm) {
+        Some(arrow) => Ok(format!("const {} = {};", func.ident.sym, arrow)),
+        None => Err(format!(
+            "=> '{}' uses `this` or `arguments`, which arrow functions do not bind",
+            func.ident.sym
+        )),
+    }
+}
+
+fn declares_component(var: &VarDecl, components: &[String]) -> bool {
+    var.decls.iter().any(|d| {
+        d.name
+            .as_ident()
+            .is_some_and(|id| components.iter().any(|c| c.as_str() == &*id.sym))
+    })
+}
+
+/// Edits declaring the wrapped function component as an arrow function, `memo(function () {...})`.
+/// Named functions are kept, as their name is the display name of the component
+fn wrapped_function_to_arrow(var: &VarDecl, cm: &SourceMap) -> Result<Option<SourceEdit>, String> {
+    let call = match var.decls.as_slice() {
+        [decl] => match decl.init.as_deref() {
+            Some(Expr::Call(call)) => call,
+            _ => return Ok(None),
+        },
+        _ => return Ok(None),
+    };
+    match call.args.first().map(|arg| &*arg.expr) {
+        Some(Expr::Fn(FnExpr {
+            ident: Some(ident), ..
+        })) => Err(format!(
+            "=> '{}' is a named function, converting it to an arrow function would drop its name",
+            ident.sym
+        )),
+        Some(Expr::Fn(func)) => match function_to_arrow(&func.function, cm) {
+            Some(arrow) => Ok(Some(SourceEdit::replace(func.function.span, arrow))),
+            None => Err(String::from(
+                "=> A wrapped component uses `this` or `arguments`, which arrow functions do not bind",
+            )),
+        },
+        _ => Ok(None),
+    }
+}
+
+/// Whether the function is referenced before its declaration, which only works for hoisted functions
+pub(crate) fn used_before_declaration(module: &Module, func: &FnDecl) -> bool {
+    let mut references = IdentReferences::new(vec![func.ident.sym.to_string()]);
+    module.visit_with(&mut references);
+    references
+        .spans
+        .iter()
+        .any(|r| r.lo < func.function.span.lo)
+}
+
+/// Converts the components of the module to the declaration style. Type annotations, generics,
+/// export modifiers and wrapper calls like `memo(...)` are kept. Returns `None` when all the
+/// components already follow the style
+pub fn convert_declaration_style_module(
+    module: &Module,
+    cm: &SourceMap,
+    style: ComponentStyle,
+) -> Option<String> {
+    let components = module_components(module);
+    let is_component = |ident: &Ident| components.iter().any(|c| c.as_str() == &*ident.sym);
+    let mut edits = vec![];
+    for item in module.body.iter() {
+        let var = match item {
+            ModuleItem::Stmt(Stmt::Decl(Decl::Var(var))) => Some(&**var),
+            ModuleItem::ModuleDecl(ModuleDecl::ExportDecl(export)) => {
+                export.decl.as_var().map(|v| &**v)
+            }
+            _ => None,
+        };
+        let func = match item {
+            ModuleItem::Stmt(Stmt::Decl(Decl::Fn(func))) => Some(func),
+            ModuleItem::ModuleDecl(ModuleDecl::ExportDecl(export)) => export.decl.as_fn_decl(),
+            _ => None,
+        };
+        let edit = match (style, var, func) {
+            (ComponentStyle::Function, Some(var), _) if declares_component(var, &components) => {
+                var_to_function(var, cm)
+            }
+            (ComponentStyle::Arrow, Some(var), _) if declares_component(var, &components) => {
+                wrapped_function_to_arrow(var, cm)
+            }
+            (ComponentStyle::Arrow, _, Some(func)) if is_component(&func.ident) => {
+                if used_before_declaration(module, func) {
+                    Err(format!(
+                        "=> '{}' is used before it is declared and needs to stay a function",
+                        func.ident.sym
+                    ))
+                } else {
+                    function_to_var(func, cm)
+                        .map(|text| Some(SourceEdit::replace(func.function.span, text)))
+                }
+            }
+            (ComponentStyle::Arrow, _, _) => match item {
+                // export default function Page() {...}
+                ModuleItem::ModuleDecl(ModuleDecl::ExportDefaultDecl(export)) => {
+                    match export.decl {
+                        DefaultDecl::Fn(ref func) => match func.ident {
+                            Some(ref ident) => {
+                                let decl = FnDecl {
+                                    ident: ident.to_owned(),
+                                    declare: false,
+                                    function: func.function.to_owned(),
+                                };
+                                let mut function_decl_visitor = FunctionDelarationWalker {
+                                    function_decls: vec![],
+                                };
+                                decl.visit_with(&mut function_decl_visitor);
+                                if function_decl_visitor.function_decls.is_empty() {
+                                    continue;
+                                }
+                                if used_before_declaration(module, &decl) {
+                                    Err(format!(
+                                    "=> '{}' is used before it is declared and needs to stay a function",
+                                    ident.sym
+                                ))
+                                } else {
+                                    function_to_var(&decl, cm).map(|text| {
+                                        Some(SourceEdit::replace(
+                                            export.span,
+                                            format!("{}\n\nexport default {};", text, ident.sym),
+                                        ))
+                                    })
+                                }
+                            }
+                            _ => Ok(None),
+                        },
+                        _ => Ok(None),
+                    }
+                }
+                _ => Ok(None),
+            },
+            _ => Ok(None),
+        };
+        match edit {
+            Ok(Some(edit)) => edits.push(edit),
+            Ok(None) => {}
+            Err(e) => println!("{}", e),
+        }
+    }
+    if edits.is_empty() {
+        return None;
+    }
+    apply_edits(cm, edits)
+}
+
+#[derive(Debug)]
+pub struct DeclarationStyle {
+    /// The declaration style to convert the components to
+    pub style: ComponentStyle,
+    ///  Count of modified files
+    pub modified: i64,
+    /// Count of ignored files
+    pub ignored: i64,
+    /// Count of total files
+    pub total: i64,
+}
+
+impl DeclarationStyle {
+    pub fn new(style: ComponentStyle) -> Self {
+        DeclarationStyle {
+            style,
+            modified: 0,
+            ignored: 0,
+            total: 0,
+        }
+    }
+
+    pub fn convert_declaration_style(&mut self, files: Paths, config: &Config) {
+        for entry in files {
+            match entry {
+                Ok(path) => {
+                    self.total += 1;
+                    if should_ignore_entry(&config.ignore, &path) {
+                        println!("Skipping {}", path.display());
+                        self.ignored += 1;
+                        continue;
+                    }
+                    let (module, cm, _comments) = parse_module(&path, config);
+                    match convert_declaration_style_module(&module, &cm, self.style) {
+                        Some(output) => match fs::write(&path, output) {
+                            Ok(_) => {
+                                println!("{}", path.display());
+                                self.modified += 1;
+                            }
+                            Err(e) => println!("{:#?}", e),
+                        },
+                        None => self.ignored += 1,
+                    }
+                }
+                Err(e) => println!("{:?}", e),
+            }
+        }
+    }
+
+    pub fn display_stats(self) {
+        println!("Total {} files", self.total);
+        println!("Modified {} files", self.modified);
+        println!("Ingored {} files", self.ignored);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::path::PathBuf;
+
+    use super::*;
+
+    fn convert(path: &str, style: ComponentStyle) -> String {
+        let config = Config {
+            typescript: Some(true),
+            ..Config::default()
+        };
+        let (module, cm, _comments) = parse_module(&PathBuf::from(path), &config);
+        convert_declaration_style_module(&module, &cm, style).unwrap()
+    }
+
+    #[test]
+    fn test_arrows_to_functions() {
+        let output = convert(
+            "../../tests/declaration_style/arrows.tsx",
+            ComponentStyle::Function,
+        );
+        assert_eq!(
+            output,
+            r#"import React, { memo } from "react";
+
+interface ListProps<T> {
+  items: T[];
+}
+
+export function Title({ text }: { text: string }) {
+  return <h1>{text}</h1>;
+}
+
+export function List<T>({ items }: ListProps<T>): JSX.Element {
+  return <ul>{items.map((item) => <li>{String(item)}</li>)}</ul>;
+}
+
+export const Badge = memo(function Badge(props: { label: string }) {
+  return <span>{props.label}</span>;
+});
+
+Badge.displayName = "Badge";
+
+const format = (value: string) => value.trim();
+"#
+        );
+    }
+
+    #[test]
+    fn test_functions_to_arrows() {
+        let output = convert(
+            "../../tests/declaration_style/functions.tsx",
+            ComponentStyle::Arrow,
+        );
+        assert_eq!(
+            output,
+            r#"import React, { memo } from "react";
+
+interface ListProps<T> {
+  items: T[];
+}
+
+export const List = <T,>({ items }: ListProps<T>): JSX.Element => {
+  return <ul>{items.map((item) => <li>{String(item)}</li>)}</ul>;
+};
+
+export const Badge = memo(function Badge(props: { label: string }) {
+  return <span>{props.label}</span>;
+});
+
+export const Tag = memo((props: { text: string }) => {
+  return <em>{props.text}</em>;
+});
+
+const Page = () => {
+  return <List items={[1, 2]} />;
+};
+
+export default Page;
+
+function Legacy() {
+  return <div>{arguments.length}</div>;
+}
+"#
+        );
+    }
+}
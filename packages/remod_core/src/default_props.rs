@@ -0,0 +1,479 @@
+use std::fs;
+
+use glob::Paths;
+use remod_config::Config;
+use swc_common::{BytePos, SourceMap, Span, Spanned};
+use swc_ecma_ast::{
+    CallExpr, Callee, Decl, Expr, FnDecl, Module, ModuleDecl, ModuleItem, ObjectLit, ObjectPatProp,
+    Pat, PatOrExpr, Prop, PropName, PropOrSpread, Stmt, VarDeclarator,
+};
+use swc_ecma_visit::{Visit, VisitWith};
+
+use crate::{
+    declaration_style::line_indent,
+    display_name::ExpressionStatementVisitor,
+    props::PropsTypeVisitor,
+    storybook::is_hook_name,
+    test_ids::{component_body, ComponentBody},
+    utils::{apply_edits, parse_module, should_ignore_entry, snippet, IdentReferences, SourceEdit},
+};
+
+/// Object literals declared at the top level of the module, `const defaultProps = {...}`
+pub(crate) fn module_objects(module: &Module) -> Vec<(String, ObjectLit, Span)> {
+    let mut objects = vec![];
+    for item in module.body.iter() {
+        let var = match item {
+            ModuleItem::Stmt(Stmt::Decl(Decl::Var(var))) => var,
+            _ => continue,
+        };
+        if var.decls.len() != 1 {
+            continue;
+        }
+        if let (Pat::Ident(ref id), Some(Expr::Object(object))) =
+            (&var.decls[0].name, var.decls[0].init.as_deref())
+        {
+            objects.push((id.sym.to_string(), object.to_owned(), var.span));
+        }
+    }
+    objects
+}
+
+/// Finds the top level objects passed to the hooks called by a component, `useProps("Button", defaultProps, props)`
+pub(crate) struct HookDefaultsVisitor<'a> {
+    /// Component to look for
+    pub component: String,
+    /// Names of the top level objects
+    pub objects: &'a [String],
+    /// Object passed to the hook and the span of the argument
+    pub arguments: Vec<(String, Span)>,
+    pub in_component: bool,
+}
+
+impl Visit for HookDefaultsVisitor<'_> {
+    fn visit_var_declarator(&mut self, n: &VarDeclarator) {
+        let in_component = self.in_component;
+        if let Pat::Ident(ref id) = n.name {
+            self.in_component |= id.sym.as_str() == self.component;
+        }
+        n.visit_children_with(self);
+        self.in_component = in_component;
+    }
+
+    fn visit_fn_decl(&mut self, n: &FnDecl) {
+        let in_component = self.in_component;
+        self.in_component |= n.ident.sym.as_str() == self.component;
+        n.visit_children_with(self);
+        self.in_component = in_component;
+    }
+
+    fn visit_call_expr(&mut self, n: &CallExpr) {
+        if let (true, Callee::Expr(ref callee)) = (self.in_component, &n.callee) {
+            if let Expr::Ident(ref hook) = **callee {
+                if is_hook_name(&hook.sym) {
+                    for arg in n.args.iter() {
+                        if let Expr::Ident(ref id) = *arg.expr {
+                            if self.objects.iter().any(|o| o.as_str() == &*id.sym) {
+                                self.arguments.push((id.sym.to_string(), arg.expr.span()));
+                            }
+                        }
+                    }
+                }
+            }
+        }
+        n.visit_children_with(self);
+    }
+}
+
+pub(crate) fn prop_key(key: &PropName) -> Option<String> {
+    match key {
+        PropName::Ident(id) => Some(id.sym.to_string()),
+        PropName::Str(s) => Some(s.value.to_string()),
+        _ => None,
+    }
+}
+
+/// The default values keyed by the prop names
+pub(crate) fn default_values(
+    defaults: &ObjectLit,
+    cm: &SourceMap,
+) -> Option<Vec<(String, String)>> {
+    defaults
+        .props
+        .iter()
+        .map(|prop| match prop {
+            PropOrSpread::Prop(prop) => match **prop {
+                Prop::KeyValue(ref kv) => Some((prop_key(&kv.key)?, snippet(cm, kv.value.span()))),
+                Prop::Shorthand(ref id) => Some((id.sym.to_string(), id.sym.to_string())),
+                _ => None,
+            },
+            PropOrSpread::Spread(..) => None,
+        })
+        .collect()
+}
+
+/// Edits of the props destructuring and the defaults which are kept
+type DestructuringEdits = (Vec<SourceEdit>, Vec<(String, String)>);
+
+/// Edits moving the defaults into the destructuring of the props parameter, with the defaults
+/// of the props which are not destructured and stay where they are
+fn destructuring_defaults(
+    component: &str,
+    param: &Pat,
+    defaults: &[(String, String)],
+) -> Result<DestructuringEdits, String> {
+    let object = match param {
+        Pat::Object(object) => object,
+        Pat::Assign(assign) => return destructuring_defaults(component, &assign.left, defaults),
+        _ => {
+            return Err(format!(
+                "=> Props of '{}' are not destructured. Destructure them to move the defaults",
+                component
+            ))
+        }
+    };
+    let mut edits = vec![];
+    let mut kept = vec![];
+    for (key, value) in defaults {
+        let prop = object.props.iter().find(|prop| match prop {
+            ObjectPatProp::KeyValue(kv) => prop_key(&kv.key).as_ref() == Some(key),
+            ObjectPatProp::Assign(assign) => &*assign.key.sym == key,
+            ObjectPatProp::Rest(..) => false,
+        });
+        match prop {
+            Some(ObjectPatProp::KeyValue(kv)) => match *kv.value {
+                Pat::Assign(..) => {
+                    println!("=> '{}' of '{}' already has a default", key, component)
+                }
+                _ => edits.push(SourceEdit::insert(
+                    kv.value.span_hi(),
+                    format!(" = {}", value),
+                )),
+            },
+            Some(ObjectPatProp::Assign(assign)) => match assign.value {
+                Some(..) => println!("=> '{}' of '{}' already has a default", key, component),
+                None => edits.push(SourceEdit::insert(
+                    assign.key.span.hi,
+                    format!(" = {}", value),
+                )),
+            },
+            // Destructuring the prop would take it out of the rest passed on by the component
+            _ => kept.push((key.to_owned(), value.to_owned())),
+        }
+    }
+    Ok((edits, kept))
+}
+
+/// Entries of the object literal of the defaults, `variant: "filled"`
+fn default_entries(defaults: &[(String, String)]) -> Vec<String> {
+    defaults
+        .iter()
+        .map(|(key, value)| {
+            if key
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '$')
+            {
+                format!("{}: {}", key, value)
+            } else {
+                format!("\"{}\": {}", key, value)
+            }
+        })
+        .collect()
+}
+
+/// Object literal of the defaults, `{ variant: "filled" }`
+fn defaults_object(defaults: &[(String, String)]) -> String {
+    if defaults.is_empty() {
+        return String::from("{}");
+    }
+    format!("{{ {} }}", default_entries(defaults).join(", "))
+}
+
+/// Edits folding the defaults of the props collected by the rest of the destructuring into it,
+/// `({ ...restProps }) => { const rest = { type: "button", ...restProps }; ... }`
+fn rest_defaults(
+    component: &str,
+    module: &Module,
+    cm: &SourceMap,
+    param: &Pat,
+    defaults: &[(String, String)],
+) -> Result<Vec<SourceEdit>, String> {
+    if defaults.is_empty() {
+        return Ok(vec![]);
+    }
+    let skipped = format!(
+        "=> Skipping the defaultProps of '{}', '{}' is not destructured",
+        component, defaults[0].0
+    );
+    let object = match param {
+        Pat::Object(object) => object,
+        Pat::Assign(assign) => return rest_defaults(component, module, cm, &assign.left, defaults),
+        _ => return Err(skipped),
+    };
+    let rest = object.props.iter().find_map(|prop| match prop {
+        ObjectPatProp::Rest(rest) => rest.arg.as_ident(),
+        _ => None,
+    });
+    let rest = match rest {
+        Some(rest) => rest,
+        None => {
+            return Err(format!(
+                "{} and there is no rest to fold its default into",
+                skipped
+            ))
+        }
+    };
+    let name = rest.id.sym.to_string();
+    let renamed = format!("{}Props", name);
+    let mut references = IdentReferences::new(vec![renamed.to_owned()]);
+    module.visit_with(&mut references);
+    if !references.spans.is_empty() {
+        return Err(format!("{} and '{}' is taken", skipped, renamed));
+    }
+    let declaration = format!(
+        "const {} = {{ {}, ...{} }};",
+        name,
+        default_entries(defaults).join(", "),
+        renamed
+    );
+    let body_edit = match component_body(module, component) {
+        Some(ComponentBody::Block(block)) => match block.stmts.first() {
+            Some(first) => SourceEdit::insert(
+                first.span_lo(),
+                format!("{}\n{}", declaration, line_indent(cm, first.span_lo())),
+            ),
+            None => SourceEdit::insert(block.span.lo + BytePos(1), format!(" {} ", declaration)),
+        },
+        Some(ComponentBody::Expr(expr)) => SourceEdit::replace(
+            expr.span(),
+            format!(
+                "{{\n  {}\n  return {};\n}}",
+                declaration,
+                snippet(cm, expr.span())
+            ),
+        ),
+        None => return Err(skipped),
+    };
+    Ok(vec![SourceEdit::replace(rest.id.span, renamed), body_edit])
+}
+
+/// Moves the `defaultProps` of the function components of the module into default values of
+/// their props destructuring. Returns `None` when nothing could be migrated
+pub fn migrate_default_props_module(module: &Module, cm: &SourceMap) -> Option<String> {
+    let mut edits: Vec<SourceEdit> = vec![];
+
+    // Component.defaultProps = {...}
+    let mut expression_stmts_visitor = ExpressionStatementVisitor {
+        property: "defaultProps",
+        assignment_expressions: vec![],
+        expressions: vec![],
+    };
+    module.visit_with(&mut expression_stmts_visitor);
+    for expr in expression_stmts_visitor.expressions.iter() {
+        let assign = match **expr {
+            Expr::Assign(ref assign) => assign,
+            _ => continue,
+        };
+        let component = match assign.left {
+            PatOrExpr::Pat(ref pat) => match **pat {
+                Pat::Expr(ref left) => match **left {
+                    Expr::Member(ref mem) => match *mem.obj {
+                        Expr::Ident(ref obj) => obj.sym.to_string(),
+                        _ => continue,
+                    },
+                    _ => continue,
+                },
+                _ => continue,
+            },
+            _ => continue,
+        };
+        let defaults = match *assign.right {
+            Expr::Object(ref object) => default_values(object, cm),
+            _ => None,
+        };
+        let mut props_type_visitor = PropsTypeVisitor::new(&component);
+        module.visit_with(&mut props_type_visitor);
+        match (defaults, props_type_visitor.param) {
+            (Some(defaults), Some(param)) => {
+                match destructuring_defaults(&component, &param, &defaults).and_then(
+                    |(param_edits, kept)| {
+                        rest_defaults(&component, module, cm, &param, &kept)
+                            .map(|rest_edits| [param_edits, rest_edits].concat())
+                    },
+                ) {
+                    Ok(param_edits) => {
+                        edits.extend(param_edits);
+                        edits.push(SourceEdit::remove_lines(cm, expr.span()));
+                    }
+                    Err(e) => println!("{}", e),
+                }
+            }
+            (None, _) => println!(
+                "=> defaultProps of '{}' are not a plain object literal",
+                component
+            ),
+            (_, None) => println!("=> '{}' is not a function component with props", component),
+        }
+    }
+
+    // const defaultProps = {...} passed to the hooks of a component
+    let objects = module_objects(module);
+    let object_names = objects.iter().map(|o| o.0.clone()).collect::<Vec<String>>();
+    let mut moved_arguments: Vec<Span> = vec![];
+    let mut moved_objects: Vec<String> = vec![];
+    for item in module.body.iter() {
+        let components = match item {
+            ModuleItem::Stmt(Stmt::Decl(Decl::Fn(func))) => vec![func.ident.sym.to_string()],
+            ModuleItem::ModuleDecl(ModuleDecl::ExportDecl(export)) => match export.decl {
+                Decl::Fn(ref func) => vec![func.ident.sym.to_string()],
+                Decl::Var(ref var) => var
+                    .decls
+                    .iter()
+                    .filter_map(|d| d.name.as_ident().map(|id| id.sym.to_string()))
+                    .collect(),
+                _ => continue,
+            },
+            ModuleItem::Stmt(Stmt::Decl(Decl::Var(var))) => var
+                .decls
+                .iter()
+                .filter_map(|d| d.name.as_ident().map(|id| id.sym.to_string()))
+                .collect(),
+            _ => continue,
+        };
+        for component in components {
+            let mut hook_defaults_visitor = HookDefaultsVisitor {
+                component: component.to_owned(),
+                objects: &object_names,
+                arguments: vec![],
+                in_component: false,
+            };
+            item.visit_with(&mut hook_defaults_visitor);
+            for (name, arg_span) in hook_defaults_visitor.arguments {
+                let mut props_type_visitor = PropsTypeVisitor::new(&component);
+                module.visit_with(&mut props_type_visitor);
+                let defaults = objects
+                    .iter()
+                    .find(|o| o.0 == name)
+                    .and_then(|o| default_values(&o.1, cm));
+                match (defaults, props_type_visitor.param) {
+                    (Some(defaults), Some(param)) => {
+                        match destructuring_defaults(&component, &param, &defaults) {
+                            Ok((param_edits, kept)) => {
+                                if param_edits.is_empty() {
+                                    continue;
+                                }
+                                edits.extend(param_edits);
+                                edits.push(SourceEdit::replace(arg_span, defaults_object(&kept)));
+                                moved_arguments.push(arg_span);
+                                moved_objects.push(name);
+                            }
+                            Err(e) => println!("{}", e),
+                        }
+                    }
+                    _ => println!(
+                        "=> Could not move '{}' passed to the hooks of '{}'",
+                        name, component
+                    ),
+                }
+            }
+        }
+    }
+    for (name, _, decl_span) in objects.iter().filter(|o| moved_objects.contains(&o.0)) {
+        let mut references = IdentReferences::new(vec![name.to_owned()]);
+        module.visit_with(&mut references);
+        if references
+            .spans
+            .iter()
+            .all(|r| decl_span.contains(*r) || moved_arguments.contains(r))
+        {
+            edits.push(SourceEdit::remove_lines(cm, *decl_span));
+        }
+    }
+    if edits.is_empty() {
+        return None;
+    }
+    apply_edits(cm, edits)
+}
+
+#[derive(Debug, Default)]
+pub struct DefaultProps {
+    ///  Count of modified files
+    pub modified: i64,
+    /// Count of ignored files
+    pub ignored: i64,
+    /// Count of total files
+    pub total: i64,
+}
+
+impl DefaultProps {
+    pub fn migrate_default_props(&mut self, files: Paths, config: &Config) {
+        for entry in files {
+            match entry {
+                Ok(path) => {
+                    self.total += 1;
+                    if should_ignore_entry(&config.ignore, &path) {
+                        println!("Skipping {}", path.display());
+                        self.ignored += 1;
+                        continue;
+                    }
+                    let (module, cm, _comments) = parse_module(&path, config);
+                    match migrate_default_props_module(&module, &cm) {
+                        Some(output) => match fs::write(&path, output) {
+                            Ok(_) => {
+                                println!("{}", path.display());
+                                self.modified += 1;
+                            }
+                            Err(e) => println!("{:#?}", e),
+                        },
+                        None => self.ignored += 1,
+                    }
+                }
+                Err(e) => println!("{:?}", e),
+            }
+        }
+    }
+
+    pub fn display_stats(self) {
+        println!("Total {} files", self.total);
+        println!("Modified {} files", self.modified);
+        println!("Ingored {} files", self.ignored);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::path::PathBuf;
+
+    use super::*;
+
+    #[test]
+    fn test_migrate_default_props() {
+        let config = Config {
+            typescript: Some(true),
+            ..Config::default()
+        };
+        let path = PathBuf::from("../../tests/default_props/button.tsx");
+        let (module, cm, _comments) = parse_module(&path, &config);
+        let output = migrate_default_props_module(&module, &cm).unwrap();
+        assert_eq!(
+            output,
+            r#"import * as React from "react";
+
+export const Button = ({ label, size = "md", disabled: isDisabled = false, ...othersProps }: ButtonProps) => {
+  const others = { type: "button", ...othersProps };
+  return <button disabled={isDisabled} className={size} {...others}>{label}</button>;
+};
+
+export function Badge({ color = "blue", ...others }: BadgeProps) {
+  const props = useProps("Badge", { variant: "filled" }, others);
+  return <span className={color}>{props.children}</span>;
+}
+
+export const Link = ({ href }: LinkProps) => <a href={href} />;
+
+Link.defaultProps = {
+  target: "_blank",
+};
+"#
+        );
+    }
+}
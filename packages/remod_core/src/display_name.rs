@@ -8,8 +8,9 @@ use remod_config::Config;
 use swc_atoms::Atom;
 use swc_common::{chain, util::take::Take, SourceFileAndLine, Spanned};
 use swc_ecma_ast::{
-    ArrowExpr, AssignExpr, BindingIdent, BlockStmt, BlockStmtOrExpr, Expr, ExprStmt, FnExpr, Ident,
-    Lit, MemberExpr, MemberProp, ModuleItem, Pat, PatOrExpr, Program, Stmt, VarDeclarator,
+    ArrowExpr, AssignExpr, BindingIdent, BlockStmt, BlockStmtOrExpr, CallExpr, Expr, ExprStmt,
+    FnExpr, Ident, Lit, MemberExpr, MemberProp, Module, ModuleItem, Pat, PatOrExpr, Program, Stmt,
+    VarDeclarator,
 };
 use swc_ecma_visit::{Visit, VisitMut, VisitMutWith, VisitWith};
 
@@ -58,6 +59,17 @@ impl VariableDeclarationWalker {
             None => {}
         }
     }
+    /// Components wrapped in factory calls, `memo(forwardRef((props, ref) => ...))`
+    fn process_call_expression(&mut self, call: CallExpr, n: &VarDeclarator) {
+        if let Some(arg) = call.args.first() {
+            match *arg.expr.to_owned() {
+                Expr::Fn(expr) => self.process_func_expression(expr, n),
+                Expr::Arrow(expr) => self.process_arrow_expression(expr, n),
+                Expr::Call(call) => self.process_call_expression(call, n),
+                _ => {}
+            }
+        }
+    }
 }
 
 impl Visit for VariableDeclarationWalker {
@@ -65,16 +77,7 @@ impl Visit for VariableDeclarationWalker {
         match &n.init {
             Some(expr) => match *expr.to_owned() {
                 Expr::Arrow(exp) => self.process_arrow_expression(exp, n),
-                Expr::Call(call) => {
-                    if call.args.len() > 0 {
-                        let arg = &call.args[0];
-                        match *arg.expr.to_owned() {
-                            Expr::Fn(expr) => self.process_func_expression(expr, n),
-                            Expr::Arrow(expr) => self.process_arrow_expression(expr, n),
-                            _ => {}
-                        }
-                    }
-                }
+                Expr::Call(call) => self.process_call_expression(call, n),
                 _ => {}
             },
             None => {}
@@ -91,14 +94,11 @@ impl Visit for FunctionDelarationWalker {
             Some(body) => {
                 for stmt in body.stmts.iter() {
                     match stmt {
-                        swc_ecma_ast::Stmt::Return(ret) => match &ret.arg {
-                            Some(expr) => {
-                                if is_jsx_like(expr) {
-                                    self.function_decls.push(func.ident.clone());
-                                }
-                            }
-                            None => todo!(),
-                        },
+                        swc_ecma_ast::Stmt::Return(ret)
+                            if ret.arg.as_ref().is_some_and(is_jsx_like) =>
+                        {
+                            self.function_decls.push(func.ident.clone());
+                        }
                         _ => {}
                     }
                 }
@@ -108,9 +108,34 @@ impl Visit for FunctionDelarationWalker {
     }
 }
 
-struct ExpressionStatementVisitor {
-    assignment_expressions: Vec<MemberExpr>,
-    expressions: Vec<Box<Expr>>,
+/// The components of the module found by the declaration walkers
+pub(crate) fn module_components(module: &Module) -> Vec<String> {
+    let mut variable_decl_visitor = VariableDeclarationWalker { variables: vec![] };
+    let mut function_decl_visitor = FunctionDelarationWalker {
+        function_decls: vec![],
+    };
+    let program = Program::Module(module.to_owned());
+    program.visit_with(&mut variable_decl_visitor);
+    program.visit_with(&mut function_decl_visitor);
+    variable_decl_visitor
+        .variables
+        .iter()
+        .map(|v| v.sym.to_string())
+        .chain(
+            function_decl_visitor
+                .function_decls
+                .iter()
+                .map(|f| f.sym.to_string()),
+        )
+        .collect()
+}
+
+/// Collects the `Component.property = ...` assignment statements
+pub(crate) struct ExpressionStatementVisitor {
+    /// The assigned property, `displayName` or `defaultProps`
+    pub property: &'static str,
+    pub assignment_expressions: Vec<MemberExpr>,
+    pub expressions: Vec<Box<Expr>>,
 }
 impl Visit for ExpressionStatementVisitor {
     fn visit_expr_stmt(&mut self, n: &ExprStmt) {
@@ -120,7 +145,7 @@ impl Visit for ExpressionStatementVisitor {
                     Pat::Expr(ref expr) => match **expr {
                         Expr::Member(ref mem) => match mem.prop {
                             MemberProp::Ident(ref id) => {
-                                if &*id.sym == "displayName" {
+                                if &*id.sym == self.property {
                                     self.assignment_expressions.push(mem.to_owned());
                                     self.expressions.push(n.expr.to_owned())
                                 }
@@ -274,7 +299,7 @@ pub struct DisplayName {
 }
 
 impl DisplayName {
-    fn create_stmts(&mut self, symbol: &Atom) -> String {
+    pub(crate) fn create_stmts(&mut self, symbol: &Atom) -> String {
         let display_name = format!("{}.displayName = \"{}_{}\"", symbol, self.prefix, symbol);
         return display_name;
     }
@@ -297,6 +322,7 @@ impl DisplayName {
                         function_decls: vec![],
                     };
                     let mut expression_stmts_visitor = ExpressionStatementVisitor {
+                        property: "displayName",
                         assignment_expressions: vec![],
                         expressions: vec![],
                     };
@@ -385,6 +411,7 @@ impl DisplayName {
                     let program = Program::Module(_module);
 
                     let mut expr_vistor = ExpressionStatementVisitor {
+                        property: "displayName",
                         assignment_expressions: vec![],
                         expressions: vec![],
                     };
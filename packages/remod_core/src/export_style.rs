@@ -0,0 +1,783 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use glob::Paths;
+use remod_config::Config;
+use swc_common::{BytePos, SourceMap, Span, Spanned};
+use swc_ecma_ast::{
+    CallExpr, Callee, Decl, DefaultDecl, ExportSpecifier, Expr, ImportDecl, ImportSpecifier, Lit,
+    Module, ModuleDecl, ModuleExportName, ModuleItem, Stmt,
+};
+use swc_ecma_visit::{Visit, VisitWith};
+
+use crate::{
+    display_name::module_components,
+    move_component::{resolve, PathAliases, Resolution},
+    split::normalize,
+    utils::{apply_edits, exported_names, parse_module, should_ignore_entry, snippet, SourceEdit},
+};
+
+/// How components are exported
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ExportStyle {
+    /// `export const Foo = ...`
+    Named,
+    /// `export default Foo`
+    Default,
+}
+
+impl ExportStyle {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "named" => Some(ExportStyle::Named),
+            "default" => Some(ExportStyle::Default),
+            _ => None,
+        }
+    }
+}
+
+/// Name of the default export of the module, `Foo` of `export default Foo` or `export { Foo as default }`
+pub(crate) fn default_export_name(module: &Module) -> Option<String> {
+    module.body.iter().find_map(|item| match item {
+        ModuleItem::ModuleDecl(ModuleDecl::ExportDefaultDecl(export)) => match export.decl {
+            DefaultDecl::Fn(ref func) => func.ident.as_ref().map(|id| id.sym.to_string()),
+            DefaultDecl::Class(ref class) => class.ident.as_ref().map(|id| id.sym.to_string()),
+            _ => None,
+        },
+        ModuleItem::ModuleDecl(ModuleDecl::ExportDefaultExpr(export)) => {
+            export.expr.as_ident().map(|id| id.sym.to_string())
+        }
+        ModuleItem::ModuleDecl(ModuleDecl::ExportNamed(export)) if export.src.is_none() => {
+            export.specifiers.iter().find_map(|s| {
+                let named = s.as_named()?;
+                match (&named.orig, &named.exported) {
+                    (ModuleExportName::Ident(orig), Some(ModuleExportName::Ident(exported)))
+                        if &*exported.sym == "default" =>
+                    {
+                        Some(orig.sym.to_string())
+                    }
+                    _ => None,
+                }
+            })
+        }
+        _ => None,
+    })
+}
+
+fn is_component_name(name: &str) -> bool {
+    name.starts_with(|c: char| c.is_ascii_uppercase())
+}
+
+/// Offset of the text in the source file from the position
+fn find_from(cm: &SourceMap, pos: BytePos, text: &str) -> Option<BytePos> {
+    let file = cm.lookup_byte_offset(pos).sf;
+    let offset = (pos - file.start_pos).0 as usize;
+    let found = file.src[offset..].find(text)?;
+    Some(pos + BytePos(found as u32))
+}
+
+/// Removes the `default` keyword of `export default function Foo` along with the space after it
+fn remove_default_keyword(cm: &SourceMap, span: Span) -> Option<SourceEdit> {
+    let start = find_from(cm, span.lo, "default")?;
+    let file = cm.lookup_byte_offset(start).sf;
+    let offset = (start - file.start_pos).0 as usize + "default".len();
+    let next = file.src[offset..]
+        .find(|c: char| !c.is_whitespace())
+        .map_or(offset, |i| offset + i);
+    Some(SourceEdit {
+        start,
+        end: file.start_pos + BytePos(next as u32),
+        text: String::new(),
+    })
+}
+
+/// Top level statement declaring the name and whether it is exported
+fn declaration<'a>(module: &'a Module, name: &str) -> Option<(&'a ModuleItem, bool)> {
+    let declares = |decl: &Decl| match decl {
+        Decl::Fn(func) => &*func.ident.sym == name,
+        Decl::Class(class) => &*class.ident.sym == name,
+        Decl::Var(var) => var
+            .decls
+            .iter()
+            .any(|d| d.name.as_ident().is_some_and(|id| &*id.sym == name)),
+        _ => false,
+    };
+    module.body.iter().find_map(|item| match item {
+        ModuleItem::Stmt(Stmt::Decl(decl)) if declares(decl) => Some((item, false)),
+        ModuleItem::ModuleDecl(ModuleDecl::ExportDecl(export)) if declares(&export.decl) => {
+            Some((item, true))
+        }
+        _ => None,
+    })
+}
+
+/// Converts the default export of a component into a named export, naming anonymous components
+/// after the file. Returns the edits and the name of the component
+fn to_named_export(
+    module: &Module,
+    cm: &SourceMap,
+    path: &Path,
+) -> Option<(Vec<SourceEdit>, String)> {
+    let stem = path
+        .file_stem()
+        .map_or(String::new(), |s| s.to_string_lossy().to_string());
+    let anonymous_name = || {
+        if is_component_name(&stem) && stem.chars().all(|c| c.is_alphanumeric() || c == '_') {
+            if declaration(module, &stem).is_some() {
+                println!(
+                    "=> Skipping {}, '{}' is already declared",
+                    path.display(),
+                    stem
+                );
+                return None;
+            }
+            Some(stem.to_owned())
+        } else {
+            println!(
+                "=> Skipping {}, the default export has no name to export it by",
+                path.display()
+            );
+            None
+        }
+    };
+    let mut edits = vec![];
+    let mut component = None;
+    for item in module.body.iter() {
+        match item {
+            ModuleItem::ModuleDecl(ModuleDecl::ExportDefaultDecl(export)) => {
+                let (ident, keyword) = match export.decl {
+                    DefaultDecl::Fn(ref func) => (&func.ident, "function"),
+                    DefaultDecl::Class(ref class) => (&class.ident, "class"),
+                    DefaultDecl::TsInterfaceDecl(..) => continue,
+                };
+                match ident {
+                    Some(ident) if is_component_name(&ident.sym) => {
+                        edits.push(remove_default_keyword(cm, export.span)?);
+                        component = Some(ident.sym.to_string());
+                    }
+                    Some(_) => return None,
+                    None => {
+                        let name = anonymous_name()?;
+                        // `export default function (props)` becomes `export function Name(props)`
+                        let start = find_from(cm, export.span.lo, "default")?;
+                        let keyword_start = find_from(cm, start, keyword)?;
+                        let file = cm.lookup_byte_offset(keyword_start).sf;
+                        let offset = (keyword_start - file.start_pos).0 as usize + keyword.len();
+                        let next = file.src[offset..]
+                            .find(|c: char| !c.is_whitespace())
+                            .map_or(offset, |i| offset + i);
+                        edits.push(SourceEdit {
+                            start,
+                            end: file.start_pos + BytePos(next as u32),
+                            text: format!(
+                                "{} {}{}",
+                                keyword,
+                                name,
+                                if keyword == "class" { " " } else { "" }
+                            ),
+                        });
+                        component = Some(name);
+                    }
+                }
+            }
+            ModuleItem::ModuleDecl(ModuleDecl::ExportDefaultExpr(export)) => match *export.expr {
+                Expr::Ident(ref ident) => {
+                    let name = ident.sym.to_string();
+                    if !module_components(module).contains(&name) && !is_component_name(&name) {
+                        return None;
+                    }
+                    match declaration(module, &name) {
+                        Some((_, true)) => edits.push(SourceEdit::remove_lines(cm, export.span)),
+                        Some((decl, false)) => {
+                            edits.push(SourceEdit::insert(decl.span_lo(), String::from("export ")));
+                            edits.push(SourceEdit::remove_lines(cm, export.span));
+                        }
+                        None => edits.push(SourceEdit::replace(
+                            export.span,
+                            format!("export {{ {} }};", name),
+                        )),
+                    }
+                    component = Some(name);
+                }
+                Expr::Arrow(..) | Expr::Fn(..) => {
+                    let name = anonymous_name()?;
+                    edits.push(SourceEdit::replace(
+                        export.span,
+                        format!(
+                            "export const {} = {};",
+                            name,
+                            snippet(cm, export.expr.span())
+                        ),
+                    ));
+                    component = Some(name);
+                }
+                _ => {
+                    println!(
+                        "=> Skipping {}, the default export is not a component declaration",
+                        path.display()
+                    );
+                    return None;
+                }
+            },
+            ModuleItem::ModuleDecl(ModuleDecl::ExportNamed(export)) if export.src.is_none() => {
+                for specifier in export.specifiers.iter() {
+                    let named = match specifier {
+                        ExportSpecifier::Named(named) => named,
+                        _ => continue,
+                    };
+                    match (&named.orig, &named.exported) {
+                        (
+                            ModuleExportName::Ident(orig),
+                            Some(ModuleExportName::Ident(exported)),
+                        ) if &*exported.sym == "default" && is_component_name(&orig.sym) => {
+                            edits.push(SourceEdit::replace(named.span, orig.sym.to_string()));
+                            component = Some(orig.sym.to_string());
+                        }
+                        _ => {}
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+    Some((edits, component?))
+}
+
+/// Converts the named export of the primary component, the one named after the file or the only
+/// exported one, into the default export. Returns the edits and the name of the component
+fn to_default_export(module: &Module, path: &Path) -> Option<(Vec<SourceEdit>, String)> {
+    if default_export_name(module).is_some()
+        || module.body.iter().any(|item| {
+            matches!(
+                item,
+                ModuleItem::ModuleDecl(
+                    ModuleDecl::ExportDefaultDecl(..) | ModuleDecl::ExportDefaultExpr(..)
+                )
+            )
+        })
+    {
+        return None;
+    }
+    let exported = exported_names(module);
+    let components = module_components(module)
+        .into_iter()
+        .filter(|c| exported.contains(c))
+        .collect::<Vec<String>>();
+    let stem = path
+        .file_stem()
+        .map_or(String::new(), |s| s.to_string_lossy().to_string());
+    let component = match components.iter().find(|c| **c == stem) {
+        Some(component) => component.to_owned(),
+        None if components.len() == 1 => components[0].to_owned(),
+        None if components.is_empty() => return None,
+        None => {
+            println!(
+                "=> Skipping {}, it exports several components and none is named after the file",
+                path.display()
+            );
+            return None;
+        }
+    };
+
+    let mut edits = vec![];
+    for item in module.body.iter() {
+        match item {
+            ModuleItem::ModuleDecl(ModuleDecl::ExportDecl(export)) => match export.decl {
+                Decl::Fn(ref func) if *func.ident.sym == *component => {
+                    edits.push(SourceEdit {
+                        start: export.span.lo,
+                        end: export.decl.span_lo(),
+                        text: String::from("export default "),
+                    });
+                }
+                Decl::Var(ref var)
+                    if var
+                        .decls
+                        .iter()
+                        .any(|d| d.name.as_ident().is_some_and(|id| *id.sym == *component)) =>
+                {
+                    if var.decls.len() > 1 {
+                        println!(
+                            "=> Skipping {}, '{}' is declared along with other variables",
+                            path.display(),
+                            component
+                        );
+                        return None;
+                    }
+                    edits.push(SourceEdit {
+                        start: export.span.lo,
+                        end: export.decl.span_lo(),
+                        text: String::new(),
+                    });
+                    let end = module
+                        .body
+                        .last()
+                        .map_or(export.span.hi, |last| last.span_hi());
+                    edits.push(SourceEdit::insert(
+                        end,
+                        format!("\n\nexport default {};", component),
+                    ));
+                }
+                _ => {}
+            },
+            ModuleItem::ModuleDecl(ModuleDecl::ExportNamed(export)) if export.src.is_none() => {
+                for specifier in export.specifiers.iter() {
+                    if let ExportSpecifier::Named(named) = specifier {
+                        match (&named.orig, &named.exported) {
+                            (ModuleExportName::Ident(orig), None) if *orig.sym == *component => {
+                                edits.push(SourceEdit::replace(
+                                    named.span,
+                                    format!("{} as default", component),
+                                ));
+                            }
+                            _ => {}
+                        }
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+    Some((edits, component))
+}
+
+fn export_edits(
+    module: &Module,
+    cm: &SourceMap,
+    path: &Path,
+    style: ExportStyle,
+) -> Option<(Vec<SourceEdit>, String)> {
+    match style {
+        ExportStyle::Named => to_named_export(module, cm, path),
+        ExportStyle::Default => to_default_export(module, path),
+    }
+}
+
+fn apply_export_edits(cm: &SourceMap, edits: Vec<SourceEdit>) -> Option<String> {
+    if edits.is_empty() {
+        return None;
+    }
+    // Removing a trailing `export default` leaves the blank line before it
+    apply_edits(cm, edits).map(|output| format!("{}\n", output.trim_end()))
+}
+
+/// Converts the export of the component declared by the module. Returns the source and the name of
+/// the component, `None` when it is exported in the style already
+pub fn export_style_module(
+    module: &Module,
+    cm: &SourceMap,
+    path: &Path,
+    style: ExportStyle,
+) -> Option<(String, String)> {
+    let (edits, component) = export_edits(module, cm, path, style)?;
+    Some((apply_export_edits(cm, edits)?, component))
+}
+
+/// Component whose export was converted
+#[derive(Debug)]
+pub struct ExportChange {
+    /// The module declaring the component
+    pub path: PathBuf,
+    pub component: String,
+    /// The style the component is exported in now
+    pub style: ExportStyle,
+}
+
+/// Collects the specifiers of `import()` calls
+#[derive(Default)]
+struct DynamicImports {
+    sources: Vec<String>,
+}
+
+impl Visit for DynamicImports {
+    fn visit_call_expr(&mut self, n: &CallExpr) {
+        if let (Callee::Import(..), Some(Expr::Lit(Lit::Str(src)))) =
+            (&n.callee, n.args.first().map(|arg| &*arg.expr))
+        {
+            self.sources.push(src.value.to_string());
+        }
+        n.visit_children_with(self);
+    }
+}
+
+/// The import declaration importing the component as the default export
+fn default_import_text(cm: &SourceMap, decl: &ImportDecl, component: &str) -> Option<String> {
+    let mut default = None;
+    let mut named = vec![];
+    let mut conflicting = false;
+    for specifier in decl.specifiers.iter() {
+        match specifier {
+            ImportSpecifier::Named(specifier) => {
+                let imported = match specifier.imported {
+                    Some(ModuleExportName::Ident(ref id)) => id.sym.to_string(),
+                    Some(ModuleExportName::Str(ref s)) => s.value.to_string(),
+                    None => specifier.local.sym.to_string(),
+                };
+                if imported == component && !specifier.is_type_only {
+                    default = Some(specifier.local.sym.to_string());
+                } else {
+                    named.push(snippet(cm, specifier.span));
+                }
+            }
+            ImportSpecifier::Namespace(..) | ImportSpecifier::Default(..) => conflicting = true,
+        }
+    }
+    // An existing default or namespace import does not fit along with the new default import
+    if conflicting {
+        return None;
+    }
+    let default = default?;
+    let type_only = if decl.type_only { "type " } else { "" };
+    let src = snippet(cm, decl.src.span);
+    Some(if named.is_empty() {
+        format!("import {}{} from {};", type_only, default, src)
+    } else {
+        format!(
+            "import {}{}, {{ {} }} from {};",
+            type_only,
+            default,
+            named.join(", "),
+            src
+        )
+    })
+}
+
+/// The import declaration importing the default export as the named component
+fn named_import_text(cm: &SourceMap, decl: &ImportDecl, component: &str) -> Option<String> {
+    let local = decl.specifiers.iter().find_map(|s| match s {
+        ImportSpecifier::Default(default) => Some(default.local.sym.to_string()),
+        _ => None,
+    })?;
+    let specifier = if local == component {
+        local
+    } else {
+        format!("{} as {}", component, local)
+    };
+    let mut named = vec![specifier];
+    let mut namespace = None;
+    for specifier in decl.specifiers.iter() {
+        match specifier {
+            ImportSpecifier::Named(..) => named.push(snippet(cm, specifier.span())),
+            ImportSpecifier::Namespace(..) => namespace = Some(snippet(cm, specifier.span())),
+            ImportSpecifier::Default(..) => {}
+        }
+    }
+    let type_only = if decl.type_only { "type " } else { "" };
+    let src = snippet(cm, decl.src.span);
+    let import = format!(
+        "import {}{{ {} }} from {};",
+        type_only,
+        named.join(", "),
+        src
+    );
+    Some(match namespace {
+        Some(namespace) => format!(
+            "import {}{} from {};\n{}",
+            type_only, namespace, src, import
+        ),
+        None => import,
+    })
+}
+
+fn import_edits(
+    module: &Module,
+    cm: &SourceMap,
+    path: &Path,
+    changes: &[ExportChange],
+    aliases: Option<&PathAliases>,
+) -> Vec<SourceEdit> {
+    let change = |source: &str| match resolve(path, source, aliases) {
+        Resolution::File(file) => changes
+            .iter()
+            .find(|change| normalize(&change.path) == normalize(&file)),
+        _ => None,
+    };
+    let mut edits = vec![];
+    for item in module.body.iter() {
+        match item {
+            ModuleItem::ModuleDecl(ModuleDecl::Import(decl)) => {
+                let change = match change(&decl.src.value) {
+                    Some(change) => change,
+                    None => continue,
+                };
+                let text = match change.style {
+                    ExportStyle::Named => named_import_text(cm, decl, &change.component),
+                    ExportStyle::Default => default_import_text(cm, decl, &change.component),
+                };
+                if let Some(text) = text {
+                    edits.push(SourceEdit::replace(decl.span, text));
+                }
+            }
+            ModuleItem::ModuleDecl(ModuleDecl::ExportNamed(export)) => {
+                let change = match export.src.as_ref().and_then(|src| change(&src.value)) {
+                    Some(change) => change,
+                    None => continue,
+                };
+                let (from, to) = match change.style {
+                    ExportStyle::Named => ("default", change.component.as_str()),
+                    ExportStyle::Default => (change.component.as_str(), "default"),
+                };
+                for specifier in export.specifiers.iter() {
+                    let named = match specifier {
+                        ExportSpecifier::Named(named) => named,
+                        _ => continue,
+                    };
+                    let orig = match named.orig {
+                        ModuleExportName::Ident(ref id) if &*id.sym == from => id,
+                        _ => continue,
+                    };
+                    let exported = match named.exported {
+                        Some(ModuleExportName::Ident(ref id)) => id.sym.to_string(),
+                        Some(ModuleExportName::Str(ref s)) => s.value.to_string(),
+                        None => orig.sym.to_string(),
+                    };
+                    let text = if exported == to {
+                        to.to_owned()
+                    } else {
+                        format!("{} as {}", to, exported)
+                    };
+                    edits.push(SourceEdit::replace(named.span, text));
+                }
+            }
+            // `export *` leaves the default export out
+            ModuleItem::ModuleDecl(ModuleDecl::ExportAll(export)) => {
+                if let Some(change) = change(&export.src.value) {
+                    if change.style == ExportStyle::Default {
+                        edits.push(SourceEdit::insert(
+                            export.span.hi,
+                            format!(
+                                "\nexport {{ default as {} }} from {};",
+                                change.component,
+                                snippet(cm, export.src.span)
+                            ),
+                        ));
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+    let mut dynamic_imports = DynamicImports::default();
+    module.visit_with(&mut dynamic_imports);
+    for source in dynamic_imports.sources {
+        // `lazy` needs the default export
+        if let Some(change) = change(&source).filter(|c| c.style == ExportStyle::Named) {
+            println!(
+                "=> {} imports '{}' dynamically, update the use of its exports to '{}'",
+                path.display(),
+                source,
+                change.component
+            );
+        }
+    }
+    edits
+}
+
+/// Rewrites the imports and re-exports of the converted components to their new export style.
+/// Returns `None` when the module imports none of them
+pub fn update_imports_module(
+    module: &Module,
+    cm: &SourceMap,
+    path: &Path,
+    changes: &[ExportChange],
+    aliases: Option<&PathAliases>,
+) -> Option<String> {
+    apply_export_edits(cm, import_edits(module, cm, path, changes, aliases))
+}
+
+/// Stories and tests default export their metadata, their components are not converted
+pub(crate) fn is_story_or_test(path: &Path, config: &Config) -> bool {
+    let name = path
+        .file_name()
+        .map_or(String::new(), |n| n.to_string_lossy().to_string());
+    [".stories.", ".story.", ".test.", ".spec."]
+        .iter()
+        .any(|part| name.contains(part))
+        || [&config.story_file_ext, &config.test_file_ext]
+            .iter()
+            .any(|ext| ext.as_ref().is_some_and(|ext| name.ends_with(ext.as_str())))
+}
+
+#[derive(Debug)]
+pub struct ExportStyleConversion {
+    /// The export style to convert the components to
+    pub style: ExportStyle,
+    ///  Count of modified files
+    pub modified: i64,
+    /// Count of ignored files
+    pub ignored: i64,
+    /// Count of total files
+    pub total: i64,
+}
+
+impl ExportStyleConversion {
+    pub fn new(style: ExportStyle) -> Self {
+        ExportStyleConversion {
+            style,
+            modified: 0,
+            ignored: 0,
+            total: 0,
+        }
+    }
+
+    pub fn convert_export_style(&mut self, files: Paths, config: &Config) {
+        let mut paths = vec![];
+        for entry in files {
+            match entry {
+                Ok(path) => {
+                    self.total += 1;
+                    if should_ignore_entry(&config.ignore, &path) {
+                        println!("Skipping {}", path.display());
+                        self.ignored += 1;
+                        continue;
+                    }
+                    paths.push(path);
+                }
+                Err(e) => println!("{:?}", e),
+            }
+        }
+
+        let mut changes = vec![];
+        let mut modules = vec![];
+        for path in paths.iter() {
+            let (module, cm, _comments) = parse_module(path, config);
+            let edits = if is_story_or_test(path, config) {
+                vec![]
+            } else {
+                match export_edits(&module, &cm, path, self.style) {
+                    Some((edits, component)) => {
+                        changes.push(ExportChange {
+                            path: path.to_owned(),
+                            component,
+                            style: self.style,
+                        });
+                        edits
+                    }
+                    None => vec![],
+                }
+            };
+            modules.push((path, module, cm, edits));
+        }
+        // The importers resolve against the files on disk, which stay in place
+        let aliases = PathAliases::load(Path::new("tsconfig.json"))
+            .or_else(|| PathAliases::load(Path::new("jsconfig.json")));
+        for (path, module, cm, mut edits) in modules {
+            edits.extend(import_edits(&module, &cm, path, &changes, aliases.as_ref()));
+            match apply_export_edits(&cm, edits) {
+                Some(output) => match fs::write(path, output) {
+                    Ok(_) => {
+                        println!("{}", path.display());
+                        self.modified += 1;
+                    }
+                    Err(e) => println!("{:#?}", e),
+                },
+                None => self.ignored += 1,
+            }
+        }
+    }
+
+    pub fn display_stats(self) {
+        println!("Total {} files", self.total);
+        println!("Modified {} files", self.modified);
+        println!("Ingored {} files", self.ignored);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_export_style() {
+        let config = Config {
+            typescript: Some(true),
+            ..Config::default()
+        };
+        let root = PathBuf::from("../../tests/export_style");
+        let convert = |name: &str, style: ExportStyle| {
+            let path = root.join(name);
+            let (module, cm, _comments) = parse_module(&path, &config);
+            export_style_module(&module, &cm, &path, style)
+        };
+
+        let (card, component) = convert("Card.tsx", ExportStyle::Named).unwrap();
+        assert_eq!(component, "Card");
+        assert_eq!(
+            card,
+            r#"import type { ReactNode } from "react";
+
+export const Card = ({ children }: { children: ReactNode }) => <div>{children}</div>;
+
+Card.displayName = "Card";
+"#
+        );
+        let (avatar, _) = convert("Avatar.tsx", ExportStyle::Named).unwrap();
+        assert!(avatar.contains("\nexport function Avatar({ size }: { size: AvatarSize }) {\n"));
+        let (header, component) = convert("Header.tsx", ExportStyle::Named).unwrap();
+        assert_eq!(component, "Header");
+        assert_eq!(
+            header,
+            "export const Header = () => <header>Remod</header>;\n"
+        );
+        assert!(convert("Badge.tsx", ExportStyle::Named).is_none());
+
+        let changes = ["Card", "Avatar", "Header"]
+            .iter()
+            .map(|name| ExportChange {
+                path: root.join(format!("{}.tsx", name)),
+                component: name.to_string(),
+                style: ExportStyle::Named,
+            })
+            .collect::<Vec<ExportChange>>();
+        let path = root.join("Page.tsx");
+        let (module, cm, _comments) = parse_module(&path, &config);
+        let page = update_imports_module(&module, &cm, &path, &changes, None).unwrap();
+        assert!(page.starts_with(
+            r#"import { lazy } from "react";
+import { Card } from "./Card";
+import { Avatar as Picture, type AvatarSize } from "./Avatar";
+import { Header } from "./Header";
+import { Badge, BADGE_SIZES } from "./Badge";
+"#
+        ));
+        let path = root.join("index.ts");
+        let (module, cm, _comments) = parse_module(&path, &config);
+        assert_eq!(
+            update_imports_module(&module, &cm, &path, &changes, None).unwrap(),
+            r#"export { Card } from "./Card";
+export { Avatar as default } from "./Avatar";
+export * from "./Badge";
+"#
+        );
+
+        let (badge, component) = convert("Badge.tsx", ExportStyle::Default).unwrap();
+        assert_eq!(component, "Badge");
+        assert_eq!(
+            badge,
+            r#"export const BADGE_SIZES = ["small", "large"];
+
+const Badge = ({ label }: { label: string }) => <span>{label}</span>;
+
+export default Badge;
+"#
+        );
+        let changes = vec![ExportChange {
+            path: root.join("Badge.tsx"),
+            component,
+            style: ExportStyle::Default,
+        }];
+        let path = root.join("Page.tsx");
+        let (module, cm, _comments) = parse_module(&path, &config);
+        let page = update_imports_module(&module, &cm, &path, &changes, None).unwrap();
+        assert!(page.contains("\nimport Badge, { BADGE_SIZES } from \"./Badge\";\n"));
+        let path = root.join("index.ts");
+        let (module, cm, _comments) = parse_module(&path, &config);
+        assert!(update_imports_module(&module, &cm, &path, &changes, None)
+            .unwrap()
+            .ends_with(
+                "export * from \"./Badge\";\nexport { default as Badge } from \"./Badge\";\n"
+            ));
+    }
+}
@@ -0,0 +1,380 @@
+use std::fs;
+
+use glob::Paths;
+use remod_config::Config;
+use swc_common::{BytePos, SourceMap, Span, Spanned};
+use swc_ecma_ast::{CallExpr, Callee, Expr, Module, Pat, TsType, VarDeclarator};
+use swc_ecma_visit::{Visit, VisitWith};
+
+use crate::utils::{
+    apply_edits, imported_name, parse_module, pat_end, remove_import_specifier,
+    should_ignore_entry, snippet, IdentReferences, ReactImports, SourceEdit,
+};
+
+/// Finds the `forwardRef` calls wrapping an inline function
+struct ForwardRefVisitor<'a> {
+    imports: &'a ReactImports,
+    /// Name of the variable being declared
+    component: Option<String>,
+    /// The component and its `forwardRef` call
+    pub calls: Vec<(String, CallExpr)>,
+}
+
+impl Visit for ForwardRefVisitor<'_> {
+    fn visit_var_declarator(&mut self, n: &VarDeclarator) {
+        let component = self.component.take();
+        self.component = n.name.as_ident().map(|id| id.sym.to_string());
+        n.visit_children_with(self);
+        self.component = component;
+    }
+
+    fn visit_call_expr(&mut self, n: &CallExpr) {
+        if let Callee::Expr(ref callee) = n.callee {
+            if self.imports.is_react_export(callee, "forwardRef") {
+                let component = self
+                    .component
+                    .to_owned()
+                    .unwrap_or_else(|| String::from("anonymous component"));
+                self.calls.push((component, n.to_owned()));
+            }
+        }
+        n.visit_children_with(self);
+    }
+}
+
+/// Element type of the ref, `HTMLDivElement` of `React.ForwardedRef<HTMLDivElement>`
+fn ref_element(ty: &TsType, cm: &SourceMap) -> String {
+    if let TsType::TsTypeRef(type_ref) = ty {
+        let name = snippet(cm, type_ref.type_name.span());
+        let name = name.rsplit('.').next().unwrap_or_default();
+        if let (true, Some(params)) = (
+            ["ForwardedRef", "Ref", "RefObject", "MutableRefObject"].contains(&name),
+            type_ref.type_params.as_ref(),
+        ) {
+            if let Some(param) = params.params.first() {
+                return snippet(cm, param.span());
+            }
+        }
+    }
+    snippet(cm, ty.span())
+}
+
+/// Edits unwrapping the `forwardRef` call and moving the ref into the props of the component
+fn unwrap_forward_ref(
+    component: &str,
+    call: &CallExpr,
+    ref_type: &str,
+    cm: &SourceMap,
+) -> Result<Vec<SourceEdit>, String> {
+    let function = match call.args.first() {
+        Some(arg) if arg.spread.is_none() => &arg.expr,
+        _ => {
+            return Err(format!(
+                "=> '{}' does not forward a ref to a function",
+                component
+            ))
+        }
+    };
+    let params = match **function {
+        Expr::Arrow(ref arrow) => arrow.params.to_owned(),
+        Expr::Fn(ref func) => func.function.params.iter().map(|p| p.pat.to_owned()).collect(),
+        _ => {
+            return Err(format!(
+                "=> '{}' forwards a ref to a function declared elsewhere. Move it into the call to unwrap it",
+                component
+            ))
+        }
+    };
+    let mut edits = vec![
+        SourceEdit {
+            start: call.span.lo,
+            end: function.span_lo(),
+            text: String::new(),
+        },
+        SourceEdit {
+            start: function.span_hi(),
+            end: call.span.hi,
+            text: String::new(),
+        },
+    ];
+    let (props, forwarded_ref) = match (params.first(), params.get(1)) {
+        (Some(props), Some(forwarded_ref)) => (props, forwarded_ref),
+        // The ref is not used, the function only needs to be unwrapped
+        _ => return Ok(edits),
+    };
+    let ref_binding = match forwarded_ref {
+        Pat::Ident(id) if &*id.sym == "ref" => String::from("ref"),
+        Pat::Ident(id) => format!("ref: {}", id.sym),
+        _ => {
+            return Err(format!(
+                "=> The ref of '{}' is destructured. Rename it to unwrap the component",
+                component
+            ))
+        }
+    };
+    let generics = call
+        .type_args
+        .as_ref()
+        .map(|args| args.params.to_owned())
+        .unwrap_or_default();
+    let ref_element = match forwarded_ref {
+        Pat::Ident(id) if id.type_ann.is_some() => {
+            Some(ref_element(&id.type_ann.as_ref().unwrap().type_ann, cm))
+        }
+        _ => generics.first().map(|ty| snippet(cm, ty.span())),
+    };
+    // The ref is removed after the default value of the props
+    let props_end = pat_end(props);
+    let (props, props_type) = match props {
+        Pat::Assign(assign) => (&*assign.left, None),
+        Pat::Ident(id) => (
+            props,
+            id.type_ann.as_ref().map(|t| (*t.type_ann).to_owned()),
+        ),
+        Pat::Object(obj) => (
+            props,
+            obj.type_ann.as_ref().map(|t| (*t.type_ann).to_owned()),
+        ),
+        _ => (props, None),
+    };
+    let props_type = props_type.or_else(|| generics.get(1).map(|ty| *ty.to_owned()));
+    let ref_prop = ref_element.map(|element| format!("{{ ref?: {}<{}> }}", ref_type, element));
+    let type_text = match (props_type, ref_prop) {
+        (Some(TsType::TsUnionOrIntersectionType(union)), Some(ref_prop))
+            if union.is_ts_union_type() =>
+        {
+            Some(format!("({}) & {}", snippet(cm, union.span()), ref_prop))
+        }
+        (Some(props_type), Some(ref_prop)) => {
+            Some(format!("{} & {}", snippet(cm, props_type.span()), ref_prop))
+        }
+        (Some(props_type), None) => Some(snippet(cm, props_type.span())),
+        (None, ref_prop) => ref_prop,
+    };
+    let annotation = type_text.map(|t| format!(": {}", t)).unwrap_or_default();
+
+    match props {
+        Pat::Ident(id) => edits.push(SourceEdit::replace(
+            Span::new(id.id.span.lo, pat_end(props), Default::default()),
+            format!("{{ {}, ...{} }}{}", ref_binding, id.sym, annotation),
+        )),
+        Pat::Object(obj) => {
+            match obj.props.first() {
+                Some(first) => edits.push(SourceEdit::insert(
+                    first.span_lo(),
+                    format!("{}, ", ref_binding),
+                )),
+                None => edits.push(SourceEdit::insert(
+                    obj.span.lo + BytePos(1),
+                    format!(" {} ", ref_binding),
+                )),
+            }
+            let pattern_end = obj.type_ann.as_ref().map_or(obj.span.hi, |t| t.span.lo);
+            edits.push(SourceEdit {
+                start: pattern_end,
+                end: pat_end(props),
+                text: annotation,
+            });
+        }
+        _ => {
+            return Err(format!(
+                "=> Props of '{}' are neither a name nor destructured",
+                component
+            ))
+        }
+    }
+    edits.push(SourceEdit {
+        start: props_end,
+        end: pat_end(forwarded_ref),
+        text: String::new(),
+    });
+    Ok(edits)
+}
+
+/// Unwraps the components of the module wrapped in `forwardRef`, passing the ref as a prop
+/// instead. Returns `None` when nothing could be unwrapped
+pub fn remove_forward_ref_module(module: &Module, cm: &SourceMap) -> Option<String> {
+    let mut imports = ReactImports::default();
+    module.visit_with(&mut imports);
+    let mut forward_ref_visitor = ForwardRefVisitor {
+        imports: &imports,
+        component: None,
+        calls: vec![],
+    };
+    module.visit_with(&mut forward_ref_visitor);
+    let ref_type = imports
+        .namespace
+        .as_ref()
+        .map(|namespace| format!("{}.Ref", namespace))
+        .or_else(|| imports.named.get("Ref").cloned());
+
+    let mut edits: Vec<SourceEdit> = vec![];
+    let mut unwrapped: Vec<Span> = vec![];
+    let mut needs_ref_import = false;
+    for (component, call) in forward_ref_visitor.calls.iter() {
+        // Nested calls are unwrapped along with the outer one
+        if unwrapped.iter().any(|s| s.contains(call.span)) {
+            continue;
+        }
+        match unwrap_forward_ref(component, call, ref_type.as_deref().unwrap_or("Ref"), cm) {
+            Ok(call_edits) => {
+                needs_ref_import |=
+                    ref_type.is_none() && call_edits.iter().any(|e| e.text.contains("ref?: Ref<"));
+                edits.extend(call_edits);
+                unwrapped.push(call.span);
+            }
+            Err(e) => println!("{}", e),
+        }
+    }
+    if edits.is_empty() {
+        return None;
+    }
+
+    // The named import of forwardRef is not needed anymore
+    if let Some(local) = imports.named.get("forwardRef") {
+        let mut references = IdentReferences::new(vec![local.to_owned()]);
+        module.visit_with(&mut references);
+        let unused = references
+            .spans
+            .iter()
+            .all(|r| unwrapped.iter().any(|s| s.contains(*r)));
+        for decl in imports.decls.iter() {
+            let index = decl
+                .specifiers
+                .iter()
+                .position(|s| imported_name(s).as_deref() == Some("forwardRef"));
+            match (unused, index) {
+                (true, Some(index)) if needs_ref_import => {
+                    edits.push(SourceEdit::replace(
+                        decl.specifiers[index].span(),
+                        String::from("type Ref"),
+                    ));
+                    needs_ref_import = false;
+                }
+                (true, Some(index)) => edits.push(remove_import_specifier(cm, decl, index)),
+                _ => {}
+            }
+        }
+    }
+    if needs_ref_import {
+        edits.push(SourceEdit::insert(
+            module.span.lo,
+            String::from("import type { Ref } from \"react\";\n"),
+        ));
+    }
+    apply_edits(cm, edits)
+}
+
+#[derive(Debug, Default)]
+pub struct ForwardRef {
+    ///  Count of modified files
+    pub modified: i64,
+    /// Count of ignored files
+    pub ignored: i64,
+    /// Count of total files
+    pub total: i64,
+}
+
+impl ForwardRef {
+    pub fn remove_forward_ref(&mut self, files: Paths, config: &Config) {
+        for entry in files {
+            match entry {
+                Ok(path) => {
+                    self.total += 1;
+                    if should_ignore_entry(&config.ignore, &path) {
+                        println!("Skipping {}", path.display());
+                        self.ignored += 1;
+                        continue;
+                    }
+                    let (module, cm, _comments) = parse_module(&path, config);
+                    match remove_forward_ref_module(&module, &cm) {
+                        Some(output) => match fs::write(&path, output) {
+                            Ok(_) => {
+                                println!("{}", path.display());
+                                self.modified += 1;
+                            }
+                            Err(e) => println!("{:#?}", e),
+                        },
+                        None => self.ignored += 1,
+                    }
+                }
+                Err(e) => println!("{:?}", e),
+            }
+        }
+    }
+
+    pub fn display_stats(self) {
+        println!("Total {} files", self.total);
+        println!("Modified {} files", self.modified);
+        println!("Ingored {} files", self.ignored);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::path::PathBuf;
+
+    use super::*;
+
+    #[test]
+    fn test_remove_forward_ref() {
+        let config = Config {
+            typescript: Some(true),
+            ..Config::default()
+        };
+        let path = PathBuf::from("../../tests/forward_ref/input.tsx");
+        let (module, cm, _comments) = parse_module(&path, &config);
+        let output = remove_forward_ref_module(&module, &cm).unwrap();
+        assert_eq!(
+            output,
+            r#"import React, { useState } from "react";
+
+interface InputProps {
+  label: string;
+}
+
+export const Input = ({ ref, label, ...others }: InputProps & { ref?: React.Ref<HTMLInputElement> }) => {
+  const [value, setValue] = useState("");
+  return <input ref={ref} aria-label={label} value={value} {...others} />;
+};
+
+Input.displayName = "@remod/Input";
+
+export const TextArea = function TextArea(
+  { ref: forwardedRef, ...props }: InputProps & { ref?: React.Ref<HTMLTextAreaElement> }
+) {
+  return <textarea ref={forwardedRef} aria-label={props.label} />;
+};
+"#
+        );
+    }
+
+    #[test]
+    fn test_forward_ref_of_other_library() {
+        let config = Config {
+            typescript: Some(true),
+            ..Config::default()
+        };
+        let path = PathBuf::from("../../tests/forward_ref/other_library.tsx");
+        let (module, cm, _comments) = parse_module(&path, &config);
+        assert_eq!(remove_forward_ref_module(&module, &cm), None);
+    }
+
+    #[test]
+    fn test_remove_forward_ref_factory_methods() {
+        let config = Config {
+            typescript: Some(true),
+            ..Config::default()
+        };
+        let path = PathBuf::from("../../tests/factory_methods/forward_ref.tsx");
+        let (module, cm, _comments) = parse_module(&path, &config);
+        let output = remove_forward_ref_module(&module, &cm).unwrap();
+        assert!(!output.contains("forwardRef"));
+        assert_eq!(
+            output
+                .matches("({ ref, ...props }: Props & { ref?: React.Ref<unknown> })")
+                .count(),
+            3
+        );
+    }
+}
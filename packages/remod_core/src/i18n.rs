@@ -0,0 +1,516 @@
+use std::{fs, path::PathBuf};
+
+use glob::Paths;
+use remod_config::Config;
+use serde_json::{Map, Value};
+use swc_common::{BytePos, SourceMap, Span, Spanned};
+use swc_ecma_ast::{
+    JSXAttr, JSXAttrName, JSXAttrValue, JSXElement, JSXElementChild, JSXElementName, JSXExpr,
+    JSXFragment, JSXText, Lit, Module, ModuleDecl, ModuleItem,
+};
+use swc_ecma_visit::{Visit, VisitWith};
+
+use crate::{
+    declaration_style::line_indent,
+    display_name::module_components,
+    test_ids::{component_body, ComponentBody},
+    utils::{
+        apply_edits, edit_snippet, imported_name, parse_module, should_ignore_entry, snippet,
+        IdentReferences, SourceEdit,
+    },
+};
+
+const HOOK: &str = "useTranslation";
+
+/// Attributes holding text shown to the user
+const TRANSLATED_ATTRIBUTES: [&str; 4] = ["title", "placeholder", "aria-label", "alt"];
+
+/// Elements whose text is not meant to be translated
+const UNTRANSLATED_ELEMENTS: [&str; 4] = ["code", "pre", "script", "style"];
+
+/// Text to translate and the span it replaces
+struct Translatable {
+    span: Span,
+    message: String,
+    /// Identifiers interpolated in the message, `{{name}}`
+    params: Vec<String>,
+}
+
+fn has_letters(text: &str) -> bool {
+    text.chars().any(char::is_alphabetic)
+}
+
+fn collapse_whitespace(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<&str>>().join(" ")
+}
+
+/// Span of the text without its surrounding whitespace
+fn trimmed_span(cm: &SourceMap, text: &JSXText) -> Span {
+    let src = snippet(cm, text.span);
+    let leading = src.len() - src.trim_start().len();
+    let trailing = src.len() - src.trim_end().len();
+    Span::new(
+        text.span.lo + BytePos(leading as u32),
+        text.span.hi - BytePos(trailing as u32),
+        text.span.ctxt,
+    )
+}
+
+/// Collects the JSX text and the text attributes
+struct TranslatableVisitor<'a> {
+    cm: &'a SourceMap,
+    pub texts: Vec<Translatable>,
+}
+
+impl TranslatableVisitor<'_> {
+    /// The children are translated as a single message when they only mix text and identifiers,
+    /// `Welcome back, {name}!`
+    fn visit_children(&mut self, children: &[JSXElementChild]) {
+        let content = children
+            .iter()
+            .filter(|child| match child {
+                JSXElementChild::JSXText(text) => !text.raw.trim().is_empty(),
+                _ => true,
+            })
+            .collect::<Vec<&JSXElementChild>>();
+        let interpolated = content.iter().all(|child| match child {
+            JSXElementChild::JSXText(..) => true,
+            JSXElementChild::JSXExprContainer(container) => {
+                matches!(container.expr, JSXExpr::Expr(ref expr) if expr.is_ident())
+            }
+            _ => false,
+        });
+        let has_text = content.iter().any(|child| match child {
+            JSXElementChild::JSXText(text) => has_letters(&text.raw),
+            _ => false,
+        });
+        if interpolated && has_text && content.len() > 1 {
+            let mut message = String::new();
+            let mut params = vec![];
+            for child in content.iter() {
+                match child {
+                    JSXElementChild::JSXText(text) => message.push_str(&text.raw),
+                    JSXElementChild::JSXExprContainer(container) => {
+                        if let JSXExpr::Expr(ref expr) = container.expr {
+                            let name = expr.as_ident().map(|id| id.sym.to_string());
+                            if let Some(name) = name {
+                                message.push_str(&format!("{{{{{}}}}}", name));
+                                if !params.contains(&name) {
+                                    params.push(name);
+                                }
+                            }
+                        }
+                    }
+                    _ => {}
+                }
+            }
+            let lo = match content.first() {
+                Some(JSXElementChild::JSXText(text)) => trimmed_span(self.cm, text).lo,
+                Some(child) => child.span_lo(),
+                None => return,
+            };
+            let hi = match content.last() {
+                Some(JSXElementChild::JSXText(text)) => trimmed_span(self.cm, text).hi,
+                Some(child) => child.span_hi(),
+                None => return,
+            };
+            self.texts.push(Translatable {
+                span: Span::new(lo, hi, Default::default()),
+                message: collapse_whitespace(&message),
+                params,
+            });
+            return;
+        }
+        for child in children.iter() {
+            match child {
+                JSXElementChild::JSXText(text) if has_letters(&text.raw) => {
+                    self.texts.push(Translatable {
+                        span: trimmed_span(self.cm, text),
+                        message: collapse_whitespace(&text.raw),
+                        params: vec![],
+                    })
+                }
+                JSXElementChild::JSXText(..) => {}
+                _ => child.visit_with(self),
+            }
+        }
+    }
+}
+
+impl Visit for TranslatableVisitor<'_> {
+    fn visit_jsx_element(&mut self, n: &JSXElement) {
+        if let JSXElementName::Ident(ref id) = n.opening.name {
+            if UNTRANSLATED_ELEMENTS.contains(&&*id.sym) {
+                return;
+            }
+        }
+        n.opening.visit_with(self);
+        self.visit_children(&n.children);
+    }
+
+    fn visit_jsx_fragment(&mut self, n: &JSXFragment) {
+        self.visit_children(&n.children);
+    }
+
+    fn visit_jsx_attr(&mut self, n: &JSXAttr) {
+        if let (JSXAttrName::Ident(ref name), Some(JSXAttrValue::Lit(Lit::Str(ref value)))) =
+            (&n.name, &n.value)
+        {
+            if TRANSLATED_ATTRIBUTES.contains(&&*name.sym) && has_letters(&value.value) {
+                self.texts.push(Translatable {
+                    span: value.span,
+                    message: collapse_whitespace(&value.value),
+                    params: vec![],
+                });
+            }
+            return;
+        }
+        n.visit_children_with(self);
+    }
+}
+
+/// `Welcome back, {{name}}!` as `welcomeBackName`
+fn message_slug(message: &str) -> String {
+    let words = message
+        .chars()
+        .filter(|c| *c != '\'' && *c != '’')
+        .map(|c| if c.is_alphanumeric() { c } else { ' ' })
+        .collect::<String>();
+    let slug = words
+        .split_whitespace()
+        .take(4)
+        .enumerate()
+        .map(|(i, word)| {
+            let word = word.to_lowercase();
+            if i == 0 {
+                return word;
+            }
+            let mut chars = word.chars();
+            match chars.next() {
+                Some(first) => first.to_uppercase().chain(chars).collect(),
+                None => word,
+            }
+        })
+        .collect::<String>();
+    if slug.is_empty() {
+        String::from("text")
+    } else {
+        slug
+    }
+}
+
+/// Key of the message in the messages of the component, reusing the key of an identical message.
+/// The existing entry of the component has to be an object
+fn message_key(messages: &mut Map<String, Value>, component: &str, message: &str) -> String {
+    let entries = messages
+        .entry(component)
+        .or_insert_with(|| Value::Object(Map::new()))
+        .as_object_mut()
+        .unwrap();
+    if let Some((key, _)) = entries.iter().find(|(_, v)| v.as_str() == Some(message)) {
+        return key.to_owned();
+    }
+    let slug = message_slug(message);
+    let mut key = slug.clone();
+    let mut count = 2;
+    while entries.contains_key(&key) {
+        key = format!("{}{}", slug, count);
+        count += 1;
+    }
+    entries.insert(key.clone(), Value::String(message.to_owned()));
+    key
+}
+
+/// Replaces the JSX text and text attributes of the components with `t("Component.key")` calls
+/// and adds the messages. Returns `None` when there was nothing to translate
+pub fn translate_module(
+    module: &Module,
+    cm: &SourceMap,
+    messages: &mut Map<String, Value>,
+    hook_import: &str,
+) -> Option<String> {
+    let mut edits = vec![];
+    let mut uses_hook = false;
+    for component in module_components(module) {
+        let body = match component_body(module, &component) {
+            Some(body) => body,
+            None => continue,
+        };
+        let mut visitor = TranslatableVisitor { cm, texts: vec![] };
+        let body_span = match body {
+            ComponentBody::Block(block) => {
+                block.visit_with(&mut visitor);
+                block.span
+            }
+            ComponentBody::Expr(expr) => {
+                expr.visit_with(&mut visitor);
+                expr.span()
+            }
+        };
+        if visitor.texts.is_empty() {
+            continue;
+        }
+        if messages
+            .get(&component)
+            .is_some_and(|entry| !entry.is_object())
+        {
+            println!(
+                "=> Skipping '{}', its entry in the messages file is not an object",
+                component
+            );
+            continue;
+        }
+        let has_hook = snippet(cm, body_span).contains(&format!("{}(", HOOK));
+        if !has_hook {
+            let mut references = IdentReferences::new(vec![String::from("t")]);
+            module.visit_with(&mut references);
+            if references
+                .spans
+                .iter()
+                .any(|span| body_span.contains(*span))
+            {
+                println!(
+                    "=> Skipping '{}', it already declares `t`. Rename it to translate the component",
+                    component
+                );
+                continue;
+            }
+        }
+        let component_edits = visitor
+            .texts
+            .iter()
+            .map(|text| {
+                let key = message_key(messages, &component, &text.message);
+                let params = if text.params.is_empty() {
+                    String::new()
+                } else {
+                    format!(", {{ {} }}", text.params.join(", "))
+                };
+                SourceEdit::replace(
+                    text.span,
+                    format!("{{t(\"{}.{}\"{})}}", component, key, params),
+                )
+            })
+            .collect::<Vec<SourceEdit>>();
+        if has_hook {
+            edits.extend(component_edits);
+            continue;
+        }
+        uses_hook = true;
+        match body {
+            ComponentBody::Block(block) => {
+                let indent = match block.stmts.first() {
+                    Some(stmt) => line_indent(cm, stmt.span_lo()),
+                    None => format!("{}  ", line_indent(cm, block.span.lo)),
+                };
+                edits.push(SourceEdit::insert(
+                    block.span.lo + BytePos(1),
+                    format!("\n{}const {{ t }} = {}();", indent, HOOK),
+                ));
+                edits.extend(component_edits);
+            }
+            ComponentBody::Expr(expr) => {
+                // The expression body becomes a block to call the hook
+                let indent = line_indent(cm, expr.span_lo());
+                let returned = edit_snippet(cm, expr.span(), &component_edits)
+                    .lines()
+                    .enumerate()
+                    .map(|(i, line)| {
+                        if i == 0 || line.is_empty() {
+                            line.to_string()
+                        } else {
+                            format!("  {}", line)
+                        }
+                    })
+                    .collect::<Vec<String>>()
+                    .join("\n");
+                edits.push(SourceEdit::replace(
+                    expr.span(),
+                    format!(
+                        "{{\n{indent}  const {{ t }} = {}();\n{indent}  return {};\n{indent}}}",
+                        HOOK,
+                        returned,
+                        indent = indent
+                    ),
+                ));
+            }
+        }
+    }
+    if edits.is_empty() {
+        return None;
+    }
+
+    let imports = module
+        .body
+        .iter()
+        .filter_map(|item| match item {
+            ModuleItem::ModuleDecl(ModuleDecl::Import(decl)) => Some(decl),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+    let imported = imports.iter().any(|decl| {
+        decl.specifiers
+            .iter()
+            .any(|s| imported_name(s).as_deref() == Some(HOOK))
+    });
+    if uses_hook && !imported {
+        edits.push(match imports.last() {
+            Some(decl) => SourceEdit::insert(
+                decl.span.hi,
+                format!("\nimport {{ {} }} from \"{}\";", HOOK, hook_import),
+            ),
+            None => SourceEdit::insert(
+                module.span.lo,
+                format!("import {{ {} }} from \"{}\";\n", HOOK, hook_import),
+            ),
+        });
+    }
+    apply_edits(cm, edits)
+}
+
+#[derive(Debug, Default)]
+pub struct I18n {
+    /// Extracted messages by component
+    pub messages: Map<String, Value>,
+    ///  Count of modified files
+    pub modified: i64,
+    /// Count of ignored files
+    pub ignored: i64,
+    /// Count of total files
+    pub total: i64,
+}
+
+impl I18n {
+    pub fn translate(&mut self, files: Paths, config: &Config) {
+        let hook_import = config
+            .i18n_import
+            .to_owned()
+            .unwrap_or_else(|| String::from("react-i18next"));
+        let messages_path = PathBuf::from(
+            config
+                .i18n_messages
+                .to_owned()
+                .unwrap_or_else(|| String::from("messages.json")),
+        );
+        if let Ok(contents) = fs::read_to_string(&messages_path) {
+            match serde_json::from_str(&contents) {
+                Ok(Value::Object(messages)) => self.messages = messages,
+                _ => {
+                    println!(
+                        "{} is not a JSON object. Fix or remove it to extract the messages",
+                        messages_path.display()
+                    );
+                    return;
+                }
+            }
+        }
+        for entry in files {
+            match entry {
+                Ok(path) => {
+                    self.total += 1;
+                    if should_ignore_entry(&config.ignore, &path) {
+                        println!("Skipping {}", path.display());
+                        self.ignored += 1;
+                        continue;
+                    }
+                    let (module, cm, _comments) = parse_module(&path, config);
+                    match translate_module(&module, &cm, &mut self.messages, &hook_import) {
+                        Some(output) => match fs::write(&path, output) {
+                            Ok(_) => {
+                                println!("{}", path.display());
+                                self.modified += 1;
+                            }
+                            Err(e) => println!("{:#?}", e),
+                        },
+                        None => self.ignored += 1,
+                    }
+                }
+                Err(e) => println!("{:?}", e),
+            }
+        }
+        if self.modified == 0 {
+            return;
+        }
+        match serde_json::to_string_pretty(&self.messages) {
+            Ok(json) => match fs::write(&messages_path, format!("{}\n", json)) {
+                Ok(_) => println!("=> Messages written to {}", messages_path.display()),
+                Err(e) => println!("{:#?}", e),
+            },
+            Err(e) => println!("{:#?}", e),
+        }
+    }
+
+    pub fn display_stats(self) {
+        println!("Total {} files", self.total);
+        println!("Modified {} files", self.modified);
+        println!("Ingored {} files", self.ignored);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::path::PathBuf;
+
+    use super::*;
+
+    #[test]
+    fn test_translate() {
+        let config = Config {
+            typescript: Some(true),
+            ..Config::default()
+        };
+        let path = PathBuf::from("../../tests/i18n/profile.tsx");
+        let (module, cm, _comments) = parse_module(&path, &config);
+        let mut messages = Map::new();
+        let output = translate_module(&module, &cm, &mut messages, "react-i18next").unwrap();
+        assert_eq!(
+            output,
+            r#"import { useState } from "react";
+import { useTranslation } from "react-i18next";
+
+export const ProfileCard = ({ name }: { name: string }) => {
+  const { t } = useTranslation();
+  return (
+    <div className="profile" title={t("ProfileCard.userProfile")}>
+      <h2>{t("ProfileCard.welcomeBackName", { name })}</h2>
+      <p>
+        {t("ProfileCard.updateYourDetailsBelow")}
+      </p>
+      <code>npm install</code>
+    </div>
+  );
+};
+
+export function SearchBox() {
+  const { t } = useTranslation();
+  const [query, setQuery] = useState("");
+  return (
+    <label>
+      {t("SearchBox.search")}
+      <input
+        value={query}
+        placeholder={t("SearchBox.searchUsers")}
+        aria-label={t("SearchBox.searchUsers")}
+        onChange={(e) => setQuery(e.target.value)}
+      />
+      <span>{query.length} / 100</span>
+    </label>
+  );
+}
+"#
+        );
+        assert_eq!(
+            messages["ProfileCard"]["welcomeBackName"],
+            "Welcome back, {{name}}!"
+        );
+        assert_eq!(messages["SearchBox"].as_object().unwrap().len(), 2);
+
+        // The existing message of the component is kept
+        let mut messages = Map::new();
+        messages.insert(String::from("SearchBox"), Value::from("Search"));
+        let output = translate_module(&module, &cm, &mut messages, "react-i18next").unwrap();
+        assert!(output.contains("      Search\n"));
+        assert_eq!(messages["SearchBox"], "Search");
+        assert!(messages["ProfileCard"].is_object());
+    }
+}
@@ -2,7 +2,30 @@
 extern crate swc_common;
 extern crate swc_ecma_parser;
 
+pub mod a11y;
+pub mod barrels;
+pub mod class_components;
+pub mod create_root;
+pub mod declaration_style;
+pub mod default_props;
 pub mod display_name;
+pub mod export_style;
+pub mod forward_ref;
+pub mod i18n;
+pub mod memo;
+pub mod move_component;
+pub mod prop_types;
+pub mod props;
+pub mod props_docs;
+pub mod props_interface;
+pub mod react_import;
+pub mod rename_component;
+pub mod scaffold;
+pub mod split;
 pub mod storybook;
+pub mod test_ids;
+pub mod testing_library;
+pub mod unused;
+pub mod use_client;
 pub mod utils;
 pub mod arrow_components;
@@ -0,0 +1,262 @@
+use std::fs;
+
+use glob::{Paths, Pattern};
+use remod_config::Config;
+use swc_common::{SourceMap, Spanned};
+use swc_ecma_ast::{
+    Decl, DefaultDecl, ExportDecl, Expr, FnDecl, Module, ModuleDecl, ModuleItem, Stmt, VarDecl,
+};
+use swc_ecma_visit::VisitWith;
+
+use crate::{
+    declaration_style::used_before_declaration,
+    display_name::module_components,
+    storybook::HookUsageVisitor,
+    utils::{
+        apply_edits, exported_names, parse_module, should_ignore_entry, snippet, ReactImports,
+        SourceEdit,
+    },
+};
+
+/// Whether the component calls no hooks and reads no contexts, so it only depends on its props
+fn is_pure(component: &str, module: &Module) -> bool {
+    let mut hook_usage_visitor = HookUsageVisitor::new(component);
+    module.visit_with(&mut hook_usage_visitor);
+    hook_usage_visitor.usages.is_empty()
+}
+
+/// Edits wrapping the expression in `memo(...)`
+fn wrap(expr: &Expr, memo: &str) -> Vec<SourceEdit> {
+    vec![
+        SourceEdit::insert(expr.span_lo(), format!("{}(", memo)),
+        SourceEdit::insert(expr.span_hi(), String::from(")")),
+    ]
+}
+
+/// Edits wrapping the arrow function or function expression component in `memo(...)`
+fn wrap_var(
+    name: &str,
+    var: &VarDecl,
+    memo: &str,
+    imports: &ReactImports,
+) -> Result<Vec<SourceEdit>, String> {
+    let init = match var.decls.as_slice() {
+        [decl] => match decl.init.as_deref() {
+            Some(init) => init,
+            None => return Ok(vec![]),
+        },
+        _ => return Ok(vec![]),
+    };
+    match init {
+        Expr::Arrow(arrow) if arrow.type_params.is_some() => Err(format!(
+            "=> '{}' is generic, which memo does not keep",
+            name
+        )),
+        Expr::Arrow(..) | Expr::Fn(..) => Ok(wrap(init, memo)),
+        Expr::Call(call) => match call.callee.as_expr() {
+            Some(callee) if imports.is_react_export(callee, "memo") => Ok(vec![]),
+            Some(callee) if imports.is_react_export(callee, "forwardRef") => Ok(wrap(init, memo)),
+            _ => Err(format!(
+                "=> '{}' is wrapped in a call other than forwardRef",
+                name
+            )),
+        },
+        _ => Ok(vec![]),
+    }
+}
+
+/// `const Foo = memo(function Foo(props) {...});` text of a function declaration
+fn wrap_function(
+    func: &FnDecl,
+    memo: &str,
+    module: &Module,
+    cm: &SourceMap,
+) -> Result<String, String> {
+    if func.function.type_params.is_some() {
+        return Err(format!(
+            "=> '{}' is generic, which memo does not keep",
+            func.ident.sym
+        ));
+    }
+    if used_before_declaration(module, func) {
+        return Err(format!(
+            "=> '{}' is used before it is declared and needs to stay a function",
+            func.ident.sym
+        ));
+    }
+    Ok(format!(
+        "const {} = {}({});",
+        func.ident.sym,
+        memo,
+        snippet(cm, func.function.span)
+    ))
+}
+
+/// Wraps the exported components of the module in `memo(...)`. Components are selected by
+/// name patterns, and with `pure_only` only when they call no hooks and read no contexts.
+/// Returns `None` when no component was wrapped
+pub fn memo_module(
+    module: &Module,
+    cm: &SourceMap,
+    names: &[Pattern],
+    pure_only: bool,
+) -> Option<String> {
+    let components = module_components(module);
+    let exported = exported_names(module);
+    let selected = |name: &str| {
+        components.iter().any(|c| c == name)
+            && exported.iter().any(|e| e == name)
+            && (names.is_empty() || names.iter().any(|p| p.matches(name)))
+            && (!pure_only || is_pure(name, module))
+    };
+    let mut imports = ReactImports::default();
+    module.visit_with(&mut imports);
+    let memo = imports
+        .local_name("memo")
+        .unwrap_or_else(|| String::from("memo"));
+
+    let mut edits = vec![];
+    for item in module.body.iter() {
+        let result = match item {
+            ModuleItem::Stmt(Stmt::Decl(Decl::Var(var)))
+            | ModuleItem::ModuleDecl(ModuleDecl::ExportDecl(ExportDecl {
+                decl: Decl::Var(var),
+                ..
+            })) => match var.decls.first().and_then(|d| d.name.as_ident()) {
+                Some(id) if selected(&id.sym) => wrap_var(&id.sym, var, &memo, &imports),
+                _ => Ok(vec![]),
+            },
+            ModuleItem::Stmt(Stmt::Decl(Decl::Fn(func)))
+            | ModuleItem::ModuleDecl(ModuleDecl::ExportDecl(ExportDecl {
+                decl: Decl::Fn(func),
+                ..
+            })) if selected(&func.ident.sym) => wrap_function(func, &memo, module, cm)
+                .map(|text| vec![SourceEdit::replace(func.function.span, text)]),
+            // export default function Foo() {...}
+            ModuleItem::ModuleDecl(ModuleDecl::ExportDefaultDecl(export)) => match export.decl {
+                DefaultDecl::Fn(ref func) => match func.ident {
+                    Some(ref ident) if selected(&ident.sym) => {
+                        let decl = FnDecl {
+                            ident: ident.to_owned(),
+                            declare: false,
+                            function: func.function.to_owned(),
+                        };
+                        wrap_function(&decl, &memo, module, cm).map(|text| {
+                            vec![SourceEdit::replace(
+                                export.span,
+                                format!("{}\n\nexport default {};", text, ident.sym),
+                            )]
+                        })
+                    }
+                    _ => Ok(vec![]),
+                },
+                _ => Ok(vec![]),
+            },
+            _ => Ok(vec![]),
+        };
+        match result {
+            Ok(item_edits) => edits.extend(item_edits),
+            Err(e) => println!("{}", e),
+        }
+    }
+    if edits.is_empty() {
+        return None;
+    }
+    if !imports.named.contains_key("memo") && imports.namespace.is_none() {
+        edits.extend(imports.add_named(module, &[String::from("memo")]));
+    }
+    apply_edits(cm, edits)
+}
+
+#[derive(Debug, Default)]
+pub struct Memo {
+    /// Patterns of the component names to wrap
+    pub names: Vec<Pattern>,
+    /// Only wrap components which call no hooks and read no contexts
+    pub pure_only: bool,
+    ///  Count of modified files
+    pub modified: i64,
+    /// Count of ignored files
+    pub ignored: i64,
+    /// Count of total files
+    pub total: i64,
+}
+
+impl Memo {
+    pub fn wrap_in_memo(&mut self, files: Paths, config: &Config) {
+        for entry in files {
+            match entry {
+                Ok(path) => {
+                    self.total += 1;
+                    if should_ignore_entry(&config.ignore, &path) {
+                        println!("Skipping {}", path.display());
+                        self.ignored += 1;
+                        continue;
+                    }
+                    let (module, cm, _comments) = parse_module(&path, config);
+                    match memo_module(&module, &cm, &self.names, self.pure_only) {
+                        Some(output) => match fs::write(&path, output) {
+                            Ok(_) => {
+                                println!("{}", path.display());
+                                self.modified += 1;
+                            }
+                            Err(e) => println!("{:#?}", e),
+                        },
+                        None => self.ignored += 1,
+                    }
+                }
+                Err(e) => println!("{:?}", e),
+            }
+        }
+    }
+
+    pub fn display_stats(self) {
+        println!("Total {} files", self.total);
+        println!("Modified {} files", self.modified);
+        println!("Ingored {} files", self.ignored);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::path::PathBuf;
+
+    use super::*;
+
+    #[test]
+    fn test_memo() {
+        let config = Config {
+            typescript: Some(true),
+            ..Config::default()
+        };
+        let path = PathBuf::from("../../tests/memo/components.tsx");
+        let (module, cm, _comments) = parse_module(&path, &config);
+        let output = memo_module(&module, &cm, &[], true).unwrap();
+        assert_eq!(
+            output,
+            r#"import { forwardRef, useState, memo } from "react";
+
+export const Label = memo(({ text }: { text: string }) => <label>{text}</label>);
+
+Label.displayName = "@remod/Label";
+
+export const ListItem = memo(function ListItem({ children }: { children: React.ReactNode }) {
+  return <li>{children}</li>;
+});
+
+export const Toggle = () => {
+  const [on, setOn] = useState(false);
+  return <button onClick={() => setOn(!on)}>{on ? "On" : "Off"}</button>;
+};
+
+export const Field = memo(forwardRef<HTMLInputElement>((props, ref) => <input ref={ref} {...props} />));
+
+const Internal = () => <span />;
+"#
+        );
+        let names = [Pattern::new("Tog*").unwrap()];
+        let output = memo_module(&module, &cm, &names, false).unwrap();
+        assert!(output.contains("export const Toggle = memo(() => {"));
+        assert!(output.contains("export const Label = ({ text }"));
+    }
+}
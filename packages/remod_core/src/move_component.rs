@@ -0,0 +1,583 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use glob::Paths;
+use remod_config::Config;
+use serde_json::Value;
+use swc_common::{SourceMap, Span};
+use swc_ecma_ast::{CallExpr, Callee, Expr, Lit, Module, ModuleDecl, ModuleItem, Str};
+use swc_ecma_visit::{Visit, VisitWith};
+
+use crate::{
+    split::{normalize, SCRIPT_EXTENSIONS},
+    utils::{apply_edits, parse_module, should_ignore_entry, snippet, SourceEdit},
+};
+
+/// Removes the comments and trailing commas tsconfig files allow
+fn strip_jsonc(text: &str) -> String {
+    let mut stripped = String::new();
+    let mut chars = text.chars().peekable();
+    let mut in_string = false;
+    while let Some(c) = chars.next() {
+        if in_string {
+            stripped.push(c);
+            match c {
+                '\\' => stripped.extend(chars.next()),
+                '"' => in_string = false,
+                _ => {}
+            }
+            continue;
+        }
+        match (c, chars.peek()) {
+            ('"', _) => {
+                in_string = true;
+                stripped.push(c);
+            }
+            ('/', Some('/')) => {
+                while chars.peek().is_some_and(|c| *c != '\n') {
+                    chars.next();
+                }
+            }
+            ('/', Some('*')) => {
+                chars.next();
+                let mut last = ' ';
+                for c in chars.by_ref() {
+                    if last == '*' && c == '/' {
+                        break;
+                    }
+                    last = c;
+                }
+            }
+            (',', _) => {
+                let rest = chars.clone().collect::<String>();
+                if !matches!(rest.trim_start().chars().next(), Some('}' | ']')) {
+                    stripped.push(c);
+                }
+            }
+            _ => stripped.push(c),
+        }
+    }
+    stripped
+}
+
+/// `compilerOptions.paths` of a tsconfig, `"@/*": ["src/*"]`
+#[derive(Debug, Default)]
+pub struct PathAliases {
+    /// Directory the alias targets are relative to
+    base: PathBuf,
+    /// Patterns with their targets, in the order of the tsconfig
+    paths: Vec<(String, Vec<String>)>,
+}
+
+impl PathAliases {
+    /// Reads the aliases of the tsconfig. Returns `None` when it has no readable `paths`
+    pub fn load(tsconfig: &Path) -> Option<Self> {
+        let contents = fs::read_to_string(tsconfig).ok()?;
+        let json: Value = serde_json::from_str(&strip_jsonc(&contents)).ok()?;
+        let options = json.get("compilerOptions")?;
+        let base = normalize(
+            &tsconfig.parent()?.join(
+                options
+                    .get("baseUrl")
+                    .and_then(|b| b.as_str())
+                    .unwrap_or("."),
+            ),
+        );
+        let paths = options
+            .get("paths")?
+            .as_object()?
+            .iter()
+            .map(|(pattern, targets)| {
+                let targets = targets
+                    .as_array()
+                    .map(|targets| {
+                        targets
+                            .iter()
+                            .filter_map(|t| t.as_str().map(|t| t.to_owned()))
+                            .collect()
+                    })
+                    .unwrap_or_default();
+                (pattern.to_owned(), targets)
+            })
+            .collect();
+        Some(PathAliases { base, paths })
+    }
+
+    /// Pattern matching the specifier with the text its `*` stands for
+    fn matching<'a>(&'a self, specifier: &str) -> Option<(&'a str, &'a [String], String)> {
+        self.paths.iter().find_map(|(pattern, targets)| {
+            let wildcard = match pattern.split_once('*') {
+                Some((prefix, suffix)) => specifier
+                    .strip_prefix(prefix)?
+                    .strip_suffix(suffix)?
+                    .to_owned(),
+                None if pattern == specifier => String::new(),
+                None => return None,
+            };
+            Some((pattern.as_str(), targets.as_slice(), wildcard))
+        })
+    }
+
+    /// Specifier reaching the file through an alias, preferring the pattern the import used
+    fn specifier(&self, file: &Path, preferred: Option<&str>, original: &str) -> Option<String> {
+        let mut patterns = self.paths.iter().collect::<Vec<&(String, Vec<String>)>>();
+        patterns.sort_by_key(|(pattern, _)| Some(pattern.as_str()) != preferred);
+        patterns.into_iter().find_map(|(pattern, targets)| {
+            let (prefix, suffix) = pattern.split_once('*')?;
+            targets.iter().find_map(|target| {
+                let (target_prefix, target_suffix) = target.split_once('*')?;
+                let target_dir = normalize(&self.base.join(target_prefix));
+                let rest = module_path(file, original)
+                    .strip_prefix(&target_dir)
+                    .ok()?
+                    .to_string_lossy()
+                    .replace('\\', "/");
+                let rest = rest.strip_suffix(target_suffix).unwrap_or(&rest);
+                Some(format!("{}{}{}", prefix, rest, suffix))
+            })
+        })
+    }
+}
+
+/// What an import specifier refers to
+#[derive(Debug, PartialEq)]
+pub(crate) enum Resolution {
+    File(PathBuf),
+    /// A relative or aliased specifier no file exists for
+    Unresolved,
+    /// A package
+    External,
+}
+
+/// The file, the file with a script extension or the `index` module of the directory
+fn resolve_file(base: &Path) -> Option<PathBuf> {
+    if base.is_file() {
+        return Some(base.to_owned());
+    }
+    let with_extension = |base: &Path, ext: &str| {
+        let mut file = base.as_os_str().to_owned();
+        file.push(".");
+        file.push(ext);
+        PathBuf::from(file)
+    };
+    SCRIPT_EXTENSIONS
+        .iter()
+        .map(|ext| with_extension(base, ext))
+        .chain(
+            SCRIPT_EXTENSIONS
+                .iter()
+                .map(|ext| with_extension(&base.join("index"), ext)),
+        )
+        .find(|file| file.is_file())
+}
+
+pub(crate) fn resolve(
+    importer: &Path,
+    specifier: &str,
+    aliases: Option<&PathAliases>,
+) -> Resolution {
+    if specifier.starts_with('.') {
+        return match importer.parent() {
+            Some(dir) => resolve_file(&normalize(&dir.join(specifier)))
+                .map_or(Resolution::Unresolved, Resolution::File),
+            None => Resolution::Unresolved,
+        };
+    }
+    let (aliases, targets, wildcard) = match aliases.and_then(|a| Some((a, a.matching(specifier)?)))
+    {
+        Some((aliases, (_, targets, wildcard))) => (aliases, targets, wildcard),
+        None => return Resolution::External,
+    };
+    targets
+        .iter()
+        .find_map(|target| {
+            resolve_file(&normalize(
+                &aliases.base.join(target.replace('*', &wildcard)),
+            ))
+        })
+        .map_or(Resolution::Unresolved, Resolution::File)
+}
+
+/// Path an import of the file is written with, following the style of the original specifier:
+/// without an extension unless it had one, and without `/index` unless it had it
+fn module_path(file: &Path, original: &str) -> PathBuf {
+    let last = original.rsplit('/').next().unwrap_or(original);
+    let file_name = file
+        .file_name()
+        .map_or(String::new(), |n| n.to_string_lossy().to_string());
+    if last == file_name {
+        return file.to_owned();
+    }
+    let stem = file.with_extension("");
+    if stem.file_name().is_some_and(|n| n == "index") && last != "index" {
+        if let Some(dir) = stem.parent() {
+            return dir.to_owned();
+        }
+    }
+    stem
+}
+
+/// Relative specifier reaching the file from the directory
+fn relative_specifier(dir: &Path, file: &Path, original: &str) -> Option<String> {
+    let relative = pathdiff::diff_paths(module_path(file, original), dir)?;
+    let relative = relative.to_string_lossy().replace('\\', "/");
+    Some(if relative.is_empty() {
+        String::from(".")
+    } else if relative.starts_with("..") {
+        relative
+    } else {
+        format!("./{}", relative)
+    })
+}
+
+/// Collects the module specifiers of imports, re-exports, `import()`, `require` and `jest.mock`
+#[derive(Default)]
+struct ModuleSpecifiers {
+    specifiers: Vec<Str>,
+}
+
+impl Visit for ModuleSpecifiers {
+    fn visit_module_item(&mut self, n: &ModuleItem) {
+        match n {
+            ModuleItem::ModuleDecl(ModuleDecl::Import(decl)) => {
+                self.specifiers.push(*decl.src.to_owned())
+            }
+            ModuleItem::ModuleDecl(ModuleDecl::ExportNamed(export)) => {
+                if let Some(ref src) = export.src {
+                    self.specifiers.push(*src.to_owned())
+                }
+            }
+            ModuleItem::ModuleDecl(ModuleDecl::ExportAll(export)) => {
+                self.specifiers.push(*export.src.to_owned())
+            }
+            _ => n.visit_children_with(self),
+        }
+    }
+    fn visit_call_expr(&mut self, n: &CallExpr) {
+        let loads_module = match n.callee {
+            Callee::Import(..) => true,
+            Callee::Expr(ref callee) => match **callee {
+                Expr::Ident(ref id) => &*id.sym == "require",
+                Expr::Member(ref member) => {
+                    member
+                        .obj
+                        .as_ident()
+                        .is_some_and(|id| matches!(&*id.sym, "jest" | "vi"))
+                        && member
+                            .prop
+                            .as_ident()
+                            .is_some_and(|id| matches!(&*id.sym, "mock" | "doMock" | "unmock"))
+                }
+                _ => false,
+            },
+            Callee::Super(..) => false,
+        };
+        if let (true, Some(Expr::Lit(Lit::Str(src)))) =
+            (loads_module, n.args.first().map(|arg| &*arg.expr))
+        {
+            self.specifiers.push(src.to_owned());
+        }
+        n.visit_children_with(self);
+    }
+}
+
+/// Module rewritten for the moved files
+#[derive(Debug, Default)]
+pub struct MovedModule {
+    /// The rewritten source, `None` when no specifier changed
+    pub source: Option<String>,
+    /// Relative and aliased specifiers no file exists for
+    pub unresolved: Vec<String>,
+}
+
+/// Points the specifiers of the module at `path` to the new locations of the moved files. When the
+/// module is one of the moved files its relative specifiers are rewritten for its new directory
+pub fn move_module(
+    module: &Module,
+    cm: &SourceMap,
+    path: &Path,
+    moves: &[(PathBuf, PathBuf)],
+    aliases: Option<&PathAliases>,
+) -> MovedModule {
+    let moved_to = |file: &Path| {
+        let file = normalize(file);
+        moves
+            .iter()
+            .find(|(from, _)| normalize(from) == file)
+            .map_or(file, |(_, to)| normalize(to))
+    };
+    let new_path = moved_to(path);
+    let new_dir = new_path.parent().unwrap_or(Path::new(""));
+
+    let mut specifiers = ModuleSpecifiers::default();
+    module.visit_with(&mut specifiers);
+    let mut moved = MovedModule::default();
+    let mut edits = vec![];
+    for src in specifiers.specifiers {
+        let file = match resolve(path, &src.value, aliases) {
+            Resolution::File(file) => file,
+            Resolution::Unresolved => {
+                moved.unresolved.push(src.value.to_string());
+                continue;
+            }
+            Resolution::External => continue,
+        };
+        let new_file = moved_to(&file);
+        let specifier = if src.value.starts_with('.') {
+            if new_file == normalize(&file) && new_path == normalize(path) {
+                continue;
+            }
+            relative_specifier(new_dir, &new_file, &src.value)
+        } else {
+            if new_file == normalize(&file) {
+                continue;
+            }
+            let preferred = aliases.and_then(|a| a.matching(&src.value).map(|(p, _, _)| p));
+            aliases
+                .and_then(|a| a.specifier(&new_file, preferred, &src.value))
+                .or_else(|| relative_specifier(new_dir, &new_file, &src.value))
+        };
+        match specifier {
+            Some(specifier) if *specifier != *src.value => {
+                let span: Span = src.span;
+                let quote = snippet(cm, span).chars().next().unwrap_or('"');
+                edits.push(SourceEdit::replace(
+                    span,
+                    format!("{q}{}{q}", specifier, q = quote),
+                ));
+            }
+            _ => {}
+        }
+    }
+    if !edits.is_empty() {
+        moved.source = apply_edits(cm, edits);
+    }
+    moved
+}
+
+/// Files next to the file sharing its name, `Button.stories.tsx` and `Button.module.css` of `Button.tsx`
+fn sibling_files(file: &Path) -> Vec<PathBuf> {
+    let (dir, stem) = match (file.parent(), file.file_stem()) {
+        (Some(dir), Some(stem)) => (dir, format!("{}.", stem.to_string_lossy())),
+        _ => return vec![],
+    };
+    let entries = match fs::read_dir(if dir.as_os_str().is_empty() {
+        Path::new(".")
+    } else {
+        dir
+    }) {
+        Ok(entries) => entries,
+        Err(_) => return vec![],
+    };
+    let mut siblings = entries
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|path| {
+            path.is_file()
+                && path.file_name() != file.file_name()
+                && path
+                    .file_name()
+                    .is_some_and(|name| name.to_string_lossy().starts_with(&stem))
+        })
+        .map(|path| dir.join(path.file_name().unwrap_or_default()))
+        .collect::<Vec<PathBuf>>();
+    siblings.sort();
+    siblings
+}
+
+#[derive(Debug, Default)]
+pub struct MoveComponent {
+    /// The file to move
+    pub from: PathBuf,
+    /// The path to move it to, or the directory to move it into
+    pub to: PathBuf,
+    ///  Count of modified files
+    pub modified: i64,
+    /// Count of moved files
+    pub moved: i64,
+    /// Count of ignored files
+    pub ignored: i64,
+    /// Count of total files
+    pub total: i64,
+}
+
+impl MoveComponent {
+    pub fn move_component(&mut self, files: Paths, config: &Config) {
+        if !self.from.is_file() {
+            println!("=> Cannot move {}, no such file", self.from.display());
+            return;
+        }
+        let to = if self.to.is_dir() || self.to.to_string_lossy().ends_with(['/', '\\']) {
+            self.to.join(self.from.file_name().unwrap_or_default())
+        } else {
+            self.to.to_owned()
+        };
+        let from_stem = self
+            .from
+            .file_stem()
+            .map_or(String::new(), |s| s.to_string_lossy().to_string());
+        let to_stem = to
+            .file_stem()
+            .map_or(String::new(), |s| s.to_string_lossy().to_string());
+        let to_dir = to.parent().map_or(PathBuf::new(), |dir| dir.to_owned());
+        let mut moves = vec![(self.from.to_owned(), to.to_owned())];
+        for sibling in sibling_files(&self.from) {
+            let name = sibling
+                .file_name()
+                .map_or(String::new(), |n| n.to_string_lossy().to_string());
+            moves.push((
+                sibling.to_owned(),
+                to_dir.join(format!("{}{}", to_stem, &name[from_stem.len()..])),
+            ));
+        }
+        if let Some((_, to)) = moves.iter().find(|(_, to)| to.exists()) {
+            println!("=> Cannot move, {} already exists", to.display());
+            return;
+        }
+
+        let aliases = PathAliases::load(Path::new("tsconfig.json"))
+            .or_else(|| PathAliases::load(Path::new("jsconfig.json")));
+        let mut paths = vec![];
+        for entry in files {
+            match entry {
+                Ok(path) => {
+                    self.total += 1;
+                    if should_ignore_entry(&config.ignore, &path) {
+                        println!("Skipping {}", path.display());
+                        self.ignored += 1;
+                        continue;
+                    }
+                    paths.push(path);
+                }
+                Err(e) => println!("{:?}", e),
+            }
+        }
+        // Moved scripts the glob does not match are rewritten too
+        for (from, _) in moves.iter() {
+            let is_script = from
+                .extension()
+                .is_some_and(|e| SCRIPT_EXTENSIONS.contains(&&*e.to_string_lossy()));
+            if is_script && !paths.iter().any(|path| normalize(path) == normalize(from)) {
+                paths.push(from.to_owned());
+            }
+        }
+
+        // Every file is rewritten before moving any, the specifiers resolve against the old tree
+        let mut outputs = vec![];
+        for path in paths.iter() {
+            let (module, cm, _comments) = parse_module(path, config);
+            let moved = move_module(&module, &cm, path, &moves, aliases.as_ref());
+            for unresolved in moved.unresolved.iter() {
+                println!(
+                    "=> Unresolved import '{}' in {}",
+                    unresolved,
+                    path.display()
+                );
+            }
+            if let Some(source) = moved.source {
+                outputs.push((path.to_owned(), source));
+            }
+        }
+        for (path, source) in outputs {
+            match fs::write(&path, source) {
+                Ok(_) => {
+                    println!("{}", path.display());
+                    self.modified += 1;
+                }
+                Err(e) => println!("{:#?}", e),
+            }
+        }
+        if let Err(e) = fs::create_dir_all(&to_dir) {
+            println!("{:#?}", e);
+            return;
+        }
+        for (from, to) in moves {
+            match fs::rename(&from, &to) {
+                Ok(_) => {
+                    println!("=> Moved {} to {}", from.display(), to.display());
+                    self.moved += 1;
+                }
+                Err(e) => println!("{:#?}", e),
+            }
+        }
+    }
+
+    pub fn display_stats(self) {
+        println!("Total {} files", self.total);
+        println!("Modified {} files", self.modified);
+        println!("Moved {} files", self.moved);
+        println!("Ingored {} files", self.ignored);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_move_component() {
+        let config = Config {
+            typescript: Some(true),
+            ..Config::default()
+        };
+        let root = PathBuf::from("../../tests/move");
+        let aliases = PathAliases::load(&root.join("tsconfig.json")).unwrap();
+        let from = root.join("src/a/Button.tsx");
+        assert_eq!(
+            sibling_files(&from),
+            vec![
+                root.join("src/a/Button.module.css"),
+                root.join("src/a/Button.stories.tsx")
+            ]
+        );
+        let moves = vec![
+            (from.to_owned(), root.join("src/ui/Button.tsx")),
+            (
+                root.join("src/a/Button.module.css"),
+                root.join("src/ui/Button.module.css"),
+            ),
+            (
+                root.join("src/a/Button.stories.tsx"),
+                root.join("src/ui/Button.stories.tsx"),
+            ),
+        ];
+
+        let (module, cm, _comments) = parse_module(&from, &config);
+        let moved = move_module(&module, &cm, &from, &moves, Some(&aliases));
+        assert_eq!(moved.unresolved, vec![String::from("./Missing")]);
+        assert_eq!(
+            moved.source.unwrap(),
+            r#"import { cx } from "@lib";
+import { formatLabel } from "../lib/format";
+import { Icon } from "../a/Icon";
+import styles from "./Button.module.css";
+import { Missing } from "./Missing";
+
+export const Button = ({ label }: { label: string }) => (
+  <button className={cx(styles.button)}>
+    <Icon />
+    {formatLabel(label)}
+  </button>
+);
+"#
+        );
+
+        let path = root.join("src/a/Button.stories.tsx");
+        let (module, cm, _comments) = parse_module(&path, &config);
+        let moved = move_module(&module, &cm, &path, &moves, Some(&aliases));
+        assert!(moved.source.is_none());
+
+        let path = root.join("src/pages/Home.tsx");
+        let (module, cm, _comments) = parse_module(&path, &config);
+        let moved = move_module(&module, &cm, &path, &moves, Some(&aliases));
+        assert!(moved.unresolved.is_empty());
+        assert!(moved.source.unwrap().starts_with(
+            r#"import { Button } from "@/ui/Button";
+import { Icon } from "../a/Icon";
+
+const Lazy = import("../ui/Button.tsx");
+"#
+        ));
+    }
+}
@@ -0,0 +1,602 @@
+use std::{collections::HashMap, fs, path::PathBuf};
+
+use glob::Paths;
+use remod_config::Config;
+use swc_common::comments::{CommentKind, Comments, SingleThreadedComments};
+use swc_common::{BytePos, SourceMap, Span, Spanned};
+use swc_ecma_ast::{
+    Callee, ClassMember, Decl, Expr, ExprStmt, ImportDecl, ImportSpecifier, MemberExpr, MemberProp,
+    Module, ModuleDecl, ModuleExportName, ModuleItem, ObjectLit, Pat, PatOrExpr, Prop, PropName,
+    PropOrSpread, Stmt,
+};
+use swc_ecma_visit::{Visit, VisitWith};
+
+use crate::utils::{
+    apply_edits, line_start_with_comments, parse_module, should_ignore_entry, snippet,
+    IdentReferences, ReactImports, SourceEdit,
+};
+
+/// Local names the `prop-types` package is imported with
+#[derive(Debug, Default)]
+struct PropTypesImports {
+    /// `import PropTypes from 'prop-types'` and `import * as PropTypes from 'prop-types'`
+    namespaces: Vec<String>,
+    /// `import { string as str } from 'prop-types'`, keyed by the local name
+    named: HashMap<String, String>,
+    /// The import declarations of `prop-types`
+    decls: Vec<ImportDecl>,
+    /// Names `react` is imported with, for the types of the `node` and `element` validators
+    react: ReactImports,
+}
+
+impl Visit for PropTypesImports {
+    fn visit_import_decl(&mut self, n: &ImportDecl) {
+        if &*n.src.value != "prop-types" {
+            return;
+        }
+        for specifier in n.specifiers.iter() {
+            match specifier {
+                ImportSpecifier::Default(default) => {
+                    self.namespaces.push(default.local.sym.to_string())
+                }
+                ImportSpecifier::Namespace(namespace) => {
+                    self.namespaces.push(namespace.local.sym.to_string())
+                }
+                ImportSpecifier::Named(named) => {
+                    let imported = match named.imported {
+                        Some(ModuleExportName::Ident(ref id)) => id.sym.to_string(),
+                        Some(ModuleExportName::Str(ref s)) => s.value.to_string(),
+                        None => named.local.sym.to_string(),
+                    };
+                    self.named.insert(named.local.sym.to_string(), imported);
+                }
+            }
+        }
+        self.decls.push(n.to_owned());
+    }
+}
+
+impl PropTypesImports {
+    /// Name of the `prop-types` validator the expression refers to
+    fn validator(&self, expr: &Expr) -> Option<String> {
+        match expr {
+            Expr::Ident(id) => self.named.get(id.sym.as_str()).cloned(),
+            Expr::Member(MemberExpr {
+                obj,
+                prop: MemberProp::Ident(prop),
+                ..
+            }) => match **obj {
+                Expr::Ident(ref ns) if self.namespaces.contains(&ns.sym.to_string()) => {
+                    Some(prop.sym.to_string())
+                }
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+
+    /// Name the type of `react` is reachable with, `React.ReactNode` or `ReactNode` when it is
+    /// not imported yet
+    fn react_type(&self, name: &str) -> String {
+        self.react
+            .local_name(name)
+            .unwrap_or_else(|| name.to_string())
+    }
+
+    fn local_names(&self) -> Vec<String> {
+        self.namespaces
+            .iter()
+            .chain(self.named.keys())
+            .cloned()
+            .collect()
+    }
+}
+
+/// Wraps function and union types so they can be used as array items
+fn array_item(ty: String) -> String {
+    if ty.contains("=>") || ty.contains('|') {
+        format!("({})", ty)
+    } else {
+        ty
+    }
+}
+
+/// Maps a `prop-types` validator to the equivalent TypeScript type and whether it is required
+fn prop_type_to_ts(expr: &Expr, imports: &PropTypesImports, cm: &SourceMap) -> (String, bool) {
+    if let Expr::Member(MemberExpr {
+        obj,
+        prop: MemberProp::Ident(prop),
+        ..
+    }) = expr
+    {
+        if &*prop.sym == "isRequired" {
+            return (prop_type_to_ts(obj, imports, cm).0, true);
+        }
+    }
+    let ty = match expr {
+        Expr::Call(call) => {
+            let validator = match call.callee {
+                Callee::Expr(ref callee) => imports.validator(callee),
+                _ => None,
+            };
+            let arg = call.args.first().map(|a| &*a.expr);
+            match (validator.as_deref(), arg) {
+                (Some("arrayOf"), Some(item)) => {
+                    format!("{}[]", array_item(prop_type_to_ts(item, imports, cm).0))
+                }
+                (Some("objectOf"), Some(item)) => {
+                    format!("Record<string, {}>", prop_type_to_ts(item, imports, cm).0)
+                }
+                (Some("instanceOf"), Some(class)) => snippet(cm, class.span()),
+                (Some("oneOf"), Some(Expr::Array(values))) => values
+                    .elems
+                    .iter()
+                    .flatten()
+                    .map(|value| snippet(cm, value.expr.span()))
+                    .collect::<Vec<String>>()
+                    .join(" | "),
+                (Some("oneOfType"), Some(Expr::Array(types))) => types
+                    .elems
+                    .iter()
+                    .flatten()
+                    .map(|ty| prop_type_to_ts(&ty.expr, imports, cm).0)
+                    .collect::<Vec<String>>()
+                    .join(" | "),
+                (Some("shape") | Some("exact"), Some(Expr::Object(shape))) => {
+                    let members = shape_members(shape, imports, cm, None)
+                        .iter()
+                        .map(|m| m.trim().trim_end_matches(';').to_string())
+                        .collect::<Vec<String>>()
+                        .join("; ");
+                    format!("{{ {} }}", members)
+                }
+                _ => String::from("any"),
+            }
+        }
+        _ => match imports.validator(expr).as_deref() {
+            Some("string") => String::from("string"),
+            Some("number") => String::from("number"),
+            Some("bool") => String::from("boolean"),
+            Some("symbol") => String::from("symbol"),
+            Some("func") => String::from("(...args: any[]) => any"),
+            Some("array") => String::from("any[]"),
+            Some("object") => String::from("Record<string, any>"),
+            Some("node") => imports.react_type("ReactNode"),
+            Some("element") => imports.react_type("ReactElement"),
+            Some("elementType") => imports.react_type("ElementType"),
+            _ => String::from("any"),
+        },
+    };
+    (ty, false)
+}
+
+fn prop_key(key: &PropName) -> Option<String> {
+    match key {
+        PropName::Ident(id) => Some(id.sym.to_string()),
+        PropName::Str(s) => {
+            let value = s.value.to_string();
+            if value
+                .chars()
+                .all(|c| c.is_alphanumeric() || c == '_' || c == '$')
+            {
+                Some(value)
+            } else {
+                Some(format!("\"{}\"", value))
+            }
+        }
+        _ => None,
+    }
+}
+
+/// Interface members for the validators of the object. Comments leading the validators are kept
+fn shape_members(
+    shape: &ObjectLit,
+    imports: &PropTypesImports,
+    cm: &SourceMap,
+    comments: Option<&SingleThreadedComments>,
+) -> Vec<String> {
+    shape
+        .props
+        .iter()
+        .filter_map(|prop| match prop {
+            PropOrSpread::Prop(prop) => match **prop {
+                Prop::KeyValue(ref kv) => {
+                    let name = prop_key(&kv.key)?;
+                    let (ty, required) = prop_type_to_ts(&kv.value, imports, cm);
+                    let leading = comments
+                        .and_then(|c| c.get_leading(prop.span_lo()))
+                        .unwrap_or_default()
+                        .iter()
+                        .map(|c| match c.kind {
+                            CommentKind::Block => format!("  /*{}*/\n", c.text),
+                            CommentKind::Line => format!("  //{}\n", c.text),
+                        })
+                        .collect::<String>();
+                    Some(format!(
+                        "{}  {}{}: {};",
+                        leading,
+                        name,
+                        if required { "" } else { "?" },
+                        ty
+                    ))
+                }
+                _ => None,
+            },
+            PropOrSpread::Spread(..) => None,
+        })
+        .collect()
+}
+
+/// `Foo.propTypes = {...}` assignments and `static propTypes = {...}` class properties
+#[derive(Default)]
+struct PropTypesVisitor {
+    /// Component, the validators and the span of the statement declaring them
+    prop_types: Vec<(String, ObjectLit, Span)>,
+}
+
+impl Visit for PropTypesVisitor {
+    fn visit_expr_stmt(&mut self, n: &ExprStmt) {
+        if let Expr::Assign(ref assign) = *n.expr {
+            if let PatOrExpr::Pat(ref pat) = assign.left {
+                if let Pat::Expr(ref expr) = **pat {
+                    if let Expr::Member(ref mem) = **expr {
+                        if let (Expr::Ident(ref obj), MemberProp::Ident(ref prop)) =
+                            (&*mem.obj, &mem.prop)
+                        {
+                            if let (true, Expr::Object(ref shape)) =
+                                (&*prop.sym == "propTypes", &*assign.right)
+                            {
+                                self.prop_types.push((
+                                    obj.sym.to_string(),
+                                    shape.to_owned(),
+                                    n.span,
+                                ));
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    fn visit_class_decl(&mut self, n: &swc_ecma_ast::ClassDecl) {
+        for member in n.class.body.iter() {
+            if let ClassMember::ClassProp(ref prop) = member {
+                if let (true, PropName::Ident(ref key), Some(Expr::Object(shape))) =
+                    (prop.is_static, &prop.key, prop.value.as_deref())
+                {
+                    if &*key.sym == "propTypes" {
+                        self.prop_types.push((
+                            n.ident.sym.to_string(),
+                            shape.to_owned(),
+                            prop.span,
+                        ));
+                    }
+                }
+            }
+        }
+        n.visit_children_with(self);
+    }
+}
+
+/// Position the props parameter of the function declared at `span` can be inserted at
+fn empty_params_pos(cm: &SourceMap, span: Span) -> Option<BytePos> {
+    let source = snippet(cm, span);
+    source
+        .find('(')
+        .map(|offset| span.lo + BytePos(offset as u32 + 1))
+}
+
+/// Annotation of the props parameter, `None` when it is already typed
+fn annotate_param(
+    params: &[Pat],
+    fn_span: Span,
+    interface: &str,
+    cm: &SourceMap,
+) -> Option<SourceEdit> {
+    match params.first() {
+        Some(Pat::Ident(id)) if id.type_ann.is_none() => {
+            Some(SourceEdit::insert(id.span.hi, format!(": {}", interface)))
+        }
+        Some(Pat::Object(obj)) if obj.type_ann.is_none() => {
+            Some(SourceEdit::insert(obj.span.hi, format!(": {}", interface)))
+        }
+        Some(Pat::Assign(assign)) => {
+            annotate_param(&[*assign.left.to_owned()], fn_span, interface, cm)
+        }
+        Some(_) => None,
+        None => empty_params_pos(cm, fn_span)
+            .map(|pos| SourceEdit::insert(pos, format!("props: {}", interface))),
+    }
+}
+
+fn annotate_expr(expr: &Expr, interface: &str, cm: &SourceMap) -> Option<SourceEdit> {
+    match expr {
+        Expr::Arrow(arrow) => annotate_param(&arrow.params, arrow.span, interface, cm),
+        Expr::Fn(func) => annotate_param(
+            &func
+                .function
+                .params
+                .iter()
+                .map(|p| p.pat.to_owned())
+                .collect::<Vec<Pat>>(),
+            func.function.span,
+            interface,
+            cm,
+        ),
+        Expr::Paren(paren) => annotate_expr(&paren.expr, interface, cm),
+        Expr::Call(call) => call
+            .args
+            .first()
+            .and_then(|arg| annotate_expr(&arg.expr, interface, cm)),
+        _ => None,
+    }
+}
+
+/// Start of the declaration of the component and the edit annotating its props
+fn component_decl(
+    module: &Module,
+    component: &str,
+    interface: &str,
+    cm: &SourceMap,
+) -> Option<(Span, bool, Option<SourceEdit>)> {
+    for item in module.body.iter() {
+        let (decl, exported) = match item {
+            ModuleItem::Stmt(Stmt::Decl(decl)) => (decl, false),
+            ModuleItem::ModuleDecl(ModuleDecl::ExportDecl(export)) => (&export.decl, true),
+            _ => continue,
+        };
+        let annotation = match decl {
+            Decl::Fn(func) if &*func.ident.sym == component => annotate_param(
+                &func
+                    .function
+                    .params
+                    .iter()
+                    .map(|p| p.pat.to_owned())
+                    .collect::<Vec<Pat>>(),
+                func.function.span,
+                interface,
+                cm,
+            ),
+            Decl::Class(class) if &*class.ident.sym == component => {
+                match (&class.class.super_class, &class.class.super_type_params) {
+                    (Some(super_class), None) => Some(SourceEdit::insert(
+                        super_class.span_hi(),
+                        format!("<{}>", interface),
+                    )),
+                    _ => None,
+                }
+            }
+            Decl::Var(var) => {
+                let declarator = var.decls.iter().find(|d| match d.name {
+                    Pat::Ident(ref id) => &*id.sym == component,
+                    _ => false,
+                });
+                match declarator {
+                    Some(d) => d
+                        .init
+                        .as_ref()
+                        .and_then(|init| annotate_expr(init, interface, cm)),
+                    None => continue,
+                }
+            }
+            _ => continue,
+        };
+        return Some((item.span(), exported, annotation));
+    }
+    None
+}
+
+/// Rewrites the `propTypes` of the components of the module into TypeScript props interfaces.
+/// Returns `None` when the module declares no `propTypes`
+pub fn migrate_prop_types_module(
+    module: &Module,
+    cm: &SourceMap,
+    comments: &SingleThreadedComments,
+) -> Option<String> {
+    let mut imports = PropTypesImports::default();
+    module.visit_with(&mut imports);
+    module.visit_with(&mut imports.react);
+    let mut prop_types_visitor = PropTypesVisitor::default();
+    module.visit_with(&mut prop_types_visitor);
+    if prop_types_visitor.prop_types.is_empty() {
+        return None;
+    }
+
+    let mut edits: Vec<SourceEdit> = vec![];
+    let mut removed: Vec<Span> = vec![];
+    let mut react_types: Vec<String> = vec![];
+    for (component, shape, span) in prop_types_visitor.prop_types.iter() {
+        let interface = format!("{}Props", component);
+        let members = shape_members(shape, &imports, cm, Some(comments));
+        for name in ["ReactNode", "ReactElement", "ElementType"] {
+            if imports.react.local_name(name).is_none()
+                && members.iter().any(|m| m.contains(name))
+                && !react_types.iter().any(|t| t == name)
+            {
+                react_types.push(name.to_string());
+            }
+        }
+        match component_decl(module, component, &interface, cm) {
+            Some((decl_span, exported, annotation)) => {
+                edits.push(SourceEdit::insert(
+                    line_start_with_comments(cm, comments, decl_span.lo),
+                    format!(
+                        "{}interface {} {{\n{}\n}}\n\n",
+                        if exported { "export " } else { "" },
+                        interface,
+                        members.join("\n")
+                    ),
+                ));
+                match annotation {
+                    Some(edit) => edits.push(edit),
+                    None => println!("=> Props of '{}' are already typed", component),
+                }
+            }
+            None => {
+                println!("=> Could not find the declaration of '{}'", component);
+                continue;
+            }
+        }
+        edits.push(SourceEdit::remove_lines(cm, *span));
+        removed.push(*span);
+    }
+
+    let mut references = IdentReferences::new(imports.local_names());
+    module.visit_with(&mut references);
+    let mut react_import = imports.react.add_named(module, &react_types);
+    if references
+        .spans
+        .iter()
+        .all(|r| removed.iter().any(|s| s.contains(*r)))
+    {
+        for decl in imports.decls.iter() {
+            // The import of `react` takes the place of the first import of `prop-types`
+            match (imports.react.decls.is_empty(), react_import.take()) {
+                (true, Some(import)) => edits.push(SourceEdit::replace(
+                    decl.span,
+                    import.text.trim().to_string(),
+                )),
+                _ => edits.push(SourceEdit::remove_lines(cm, decl.span)),
+            }
+        }
+    }
+    edits.extend(react_import);
+    apply_edits(cm, edits)
+}
+
+#[derive(Debug, Default)]
+pub struct PropTypes {
+    ///  Count of modified files
+    pub modified: i64,
+    /// Count of ignored files
+    pub ignored: i64,
+    /// Count of total files
+    pub total: i64,
+}
+
+impl PropTypes {
+    pub fn migrate_prop_types(&mut self, files: Paths, config: &Config) {
+        for entry in files {
+            match entry {
+                Ok(path) => {
+                    self.total += 1;
+                    if should_ignore_entry(&config.ignore, &path) {
+                        println!("Skipping {}", path.display());
+                        self.ignored += 1;
+                        continue;
+                    }
+                    let (module, cm, comments) = parse_module(&path, config);
+                    match migrate_prop_types_module(&module, &cm, &comments) {
+                        Some(output) => self.write_migrated_file(&path, output),
+                        None => self.ignored += 1,
+                    }
+                }
+                Err(e) => println!("{:?}", e),
+            }
+        }
+    }
+
+    /// Writes the migrated source, renaming JavaScript files to TSX
+    fn write_migrated_file(&mut self, path: &PathBuf, output: String) {
+        let new_path = match path.extension().and_then(|e| e.to_str()) {
+            Some("js") | Some("jsx") => path.with_extension("tsx"),
+            _ => path.to_owned(),
+        };
+        if &new_path != path && new_path.exists() {
+            println!(
+                "=> Skipping {}, {} already exists",
+                path.display(),
+                new_path.display()
+            );
+            self.ignored += 1;
+            return;
+        }
+        match fs::write(&new_path, output) {
+            Ok(_) => {
+                if &new_path != path {
+                    let _ = fs::remove_file(path);
+                }
+                println!("{} => {}", path.display(), new_path.display());
+                self.modified += 1;
+            }
+            Err(e) => println!("{:#?}", e),
+        }
+    }
+
+    pub fn display_stats(self) {
+        println!("Total {} files", self.total);
+        println!("Modified {} files", self.modified);
+        println!("Ingored {} files", self.ignored);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_migrate_prop_types() {
+        let config = Config {
+            typescript: Some(true),
+            ..Config::default()
+        };
+        let path = PathBuf::from("../../tests/prop_types/button.jsx");
+        let (module, cm, comments) = parse_module(&path, &config);
+        let output = migrate_prop_types_module(&module, &cm, &comments).unwrap();
+        assert_eq!(
+            output,
+            r#"import React from "react";
+
+export interface ButtonProps {
+  /** Text of the button */
+  label: string;
+  size?: "small" | "large";
+  onClick?: (...args: any[]) => any;
+  items?: { id: number; name?: string }[];
+  value?: string | number;
+}
+
+export const Button = ({ label, size }: ButtonProps) => {
+  return <button className={size}>{label}</button>;
+};
+
+interface CardProps {
+  title?: React.ReactNode;
+}
+
+class Card extends React.Component<CardProps> {
+  render() {
+    return <div>{this.props.title}</div>;
+  }
+}
+"#
+        );
+
+        // `ReactNode` is imported when `React` is not in scope
+        let path = PathBuf::from("../../tests/prop_types/card.jsx");
+        let (module, cm, comments) = parse_module(&path, &config);
+        let output = migrate_prop_types_module(&module, &cm, &comments).unwrap();
+        assert_eq!(
+            output,
+            r#"import { ReactNode } from "react";
+
+export interface CardProps {
+  title: string;
+  children?: ReactNode;
+}
+
+export function Card({ title, children }: CardProps) {
+  return (
+    <section>
+      <h2>{title}</h2>
+      {children}
+    </section>
+  );
+}
+
+export default Card;
+"#
+        );
+    }
+}
@@ -0,0 +1,350 @@
+use swc_common::comments::SingleThreadedComments;
+use swc_common::{SourceMap, Spanned};
+use swc_ecma_ast::{
+    CallExpr, Callee, Decl, Expr, FnDecl, Module, ModuleDecl, ModuleItem, Pat, Stmt, TsEntityName,
+    TsType, TsTypeElement, TsTypeRef, VarDeclarator,
+};
+use swc_ecma_visit::{Visit, VisitWith};
+
+use crate::utils::{leading_doc_comment, snippet};
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct PropDefinition {
+    /// Name of the prop
+    pub name: String,
+    /// Type of the prop as written in the source
+    pub type_text: String,
+    /// Whether the prop must be passed to the component
+    pub required: bool,
+    /// JSDoc description of the prop
+    pub description: Option<String>,
+    /// Default value of the prop as written in the source
+    pub default_value: Option<String>,
+}
+
+/// Finds the props parameter of a component and its type annotation
+pub struct PropsTypeVisitor {
+    /// Component to look for
+    component: String,
+    /// The first parameter of the component
+    pub param: Option<Pat>,
+    /// Type annotation of the first parameter of the component
+    pub props_type: Option<TsType>,
+}
+
+impl PropsTypeVisitor {
+    pub fn new(component: &str) -> Self {
+        PropsTypeVisitor {
+            component: component.to_string(),
+            param: None,
+            props_type: None,
+        }
+    }
+}
+
+/// Type annotation of the parameter, looking through default values
+pub fn param_type(pat: &Pat) -> Option<TsType> {
+    match pat {
+        Pat::Ident(id) => id.type_ann.as_ref().map(|t| *t.type_ann.to_owned()),
+        Pat::Object(obj) => obj.type_ann.as_ref().map(|t| *t.type_ann.to_owned()),
+        Pat::Assign(assign) => param_type(&assign.left),
+        _ => None,
+    }
+}
+
+/// First parameter of a component declared as an arrow or wrapped in a factory call
+pub fn component_param(expr: &Expr) -> Option<Pat> {
+    match expr {
+        Expr::Arrow(arrow) => arrow.params.first().cloned(),
+        Expr::Fn(func) => func.function.params.first().map(|p| p.pat.to_owned()),
+        Expr::Paren(paren) => component_param(&paren.expr),
+        Expr::Call(call) => call.args.first().and_then(|arg| component_param(&arg.expr)),
+        _ => None,
+    }
+}
+
+impl Visit for PropsTypeVisitor {
+    fn visit_var_declarator(&mut self, n: &VarDeclarator) {
+        if let Pat::Ident(ref id) = n.name {
+            if id.sym.as_str() == self.component {
+                if let Some(param) = n.init.as_ref().and_then(|init| component_param(init)) {
+                    self.props_type = param_type(&param);
+                    self.param = Some(param);
+                }
+            }
+        }
+    }
+
+    fn visit_fn_decl(&mut self, n: &FnDecl) {
+        if n.ident.sym.as_str() == self.component {
+            if let Some(param) = n.function.params.first() {
+                self.props_type = param_type(&param.pat);
+                self.param = Some(param.pat.to_owned());
+            }
+        }
+    }
+}
+
+/// Resolves the members of a props type against the interfaces and type aliases of the module
+pub fn resolve_props(
+    ty: &TsType,
+    module: &Module,
+    cm: &SourceMap,
+    comments: &SingleThreadedComments,
+) -> Vec<PropDefinition> {
+    match ty {
+        TsType::TsTypeLit(lit) => members_to_props(&lit.members, cm, comments),
+        TsType::TsParenthesizedType(paren) => resolve_props(&paren.type_ann, module, cm, comments),
+        TsType::TsUnionOrIntersectionType(
+            swc_ecma_ast::TsUnionOrIntersectionType::TsIntersectionType(intersection),
+        ) => intersection
+            .types
+            .iter()
+            .flat_map(|t| resolve_props(t, module, cm, comments))
+            .collect(),
+        TsType::TsTypeRef(type_ref) => match type_ref.type_name {
+            TsEntityName::Ident(ref id) => {
+                for item in module.body.iter() {
+                    let decl = match item {
+                        ModuleItem::Stmt(Stmt::Decl(decl)) => decl,
+                        ModuleItem::ModuleDecl(ModuleDecl::ExportDecl(export)) => &export.decl,
+                        _ => continue,
+                    };
+                    match decl {
+                        Decl::TsInterface(interface) if interface.id.sym == id.sym => {
+                            let mut props = members_to_props(&interface.body.body, cm, comments);
+                            // Members of the extended interfaces of the module, the own members win
+                            for parent in interface.extends.iter() {
+                                let parent_type = match *parent.expr {
+                                    Expr::Ident(ref parent_id) => TsType::TsTypeRef(TsTypeRef {
+                                        span: parent.span,
+                                        type_name: TsEntityName::Ident(parent_id.to_owned()),
+                                        type_params: None,
+                                    }),
+                                    _ => continue,
+                                };
+                                for prop in resolve_props(&parent_type, module, cm, comments) {
+                                    if !props.iter().any(|p| p.name == prop.name) {
+                                        props.push(prop);
+                                    }
+                                }
+                            }
+                            return props;
+                        }
+                        Decl::TsTypeAlias(alias) if alias.id.sym == id.sym => {
+                            return resolve_props(&alias.type_ann, module, cm, comments);
+                        }
+                        _ => {}
+                    }
+                }
+                vec![]
+            }
+            _ => vec![],
+        },
+        _ => vec![],
+    }
+}
+
+fn members_to_props(
+    members: &[TsTypeElement],
+    cm: &SourceMap,
+    comments: &SingleThreadedComments,
+) -> Vec<PropDefinition> {
+    members
+        .iter()
+        .filter_map(|member| match member {
+            TsTypeElement::TsPropertySignature(prop) => {
+                let name = match *prop.key {
+                    Expr::Ident(ref id) => id.sym.to_string(),
+                    Expr::Lit(swc_ecma_ast::Lit::Str(ref s)) => s.value.to_string(),
+                    _ => return None,
+                };
+                Some(PropDefinition {
+                    name,
+                    type_text: prop
+                        .type_ann
+                        .as_ref()
+                        .map(|t| snippet(cm, t.type_ann.span()))
+                        .unwrap_or_else(|| String::from("any")),
+                    required: !prop.optional,
+                    description: leading_doc_comment(comments, prop.span_lo()),
+                    default_value: None,
+                })
+            }
+            TsTypeElement::TsMethodSignature(method) => {
+                let name = match *method.key {
+                    Expr::Ident(ref id) => id.sym.to_string(),
+                    _ => return None,
+                };
+                let params = method
+                    .params
+                    .iter()
+                    .map(|p| snippet(cm, p.span()))
+                    .collect::<Vec<String>>()
+                    .join(", ");
+                let ret = method
+                    .type_ann
+                    .as_ref()
+                    .map(|t| snippet(cm, t.type_ann.span()))
+                    .unwrap_or_else(|| String::from("void"));
+                Some(PropDefinition {
+                    name,
+                    type_text: format!("({}) => {}", params, ret),
+                    required: !method.optional,
+                    description: leading_doc_comment(comments, method.span_lo()),
+                    default_value: None,
+                })
+            }
+            _ => None,
+        })
+        .collect()
+}
+
+/// Extracts the props of a component from the type annotation of its props parameter
+pub fn extract_props(
+    component: &str,
+    module: &Module,
+    cm: &SourceMap,
+    comments: &SingleThreadedComments,
+) -> Vec<PropDefinition> {
+    let mut props_type_visitor = PropsTypeVisitor::new(component);
+    module.visit_with(&mut props_type_visitor);
+    match props_type_visitor
+        .props_type
+        .or_else(|| declared_props_type(component, module))
+    {
+        Some(ref ty) => resolve_props(ty, module, cm, comments),
+        None => vec![],
+    }
+}
+
+/// Name of the called function, `forwardRef` for `React.forwardRef(...)`
+fn callee_name(call: &CallExpr) -> Option<String> {
+    match call.callee {
+        Callee::Expr(ref callee) => match **callee {
+            Expr::Ident(ref id) => Some(id.sym.to_string()),
+            Expr::Member(ref member) => member.prop.as_ident().map(|id| id.sym.to_string()),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// The `props` member of a local factory type, `type ButtonFactory = Factory<{ props: ButtonProps }>`
+fn factory_props_type(ty: &TsType, module: &Module) -> Option<TsType> {
+    let name = match ty {
+        TsType::TsTypeRef(TsTypeRef {
+            type_name: TsEntityName::Ident(id),
+            ..
+        }) => &id.sym,
+        _ => return None,
+    };
+    let alias = module.body.iter().find_map(|item| {
+        let decl = match item {
+            ModuleItem::Stmt(Stmt::Decl(decl)) => decl,
+            ModuleItem::ModuleDecl(ModuleDecl::ExportDecl(export)) => &export.decl,
+            _ => return None,
+        };
+        match decl {
+            Decl::TsTypeAlias(alias) if alias.id.sym == *name => Some(alias),
+            _ => None,
+        }
+    })?;
+    let options = match *alias.type_ann {
+        TsType::TsTypeRef(ref factory) => factory.type_params.as_ref()?.params.first()?,
+        _ => return None,
+    };
+    match **options {
+        TsType::TsTypeLit(ref lit) => lit.members.iter().find_map(|member| match member {
+            TsTypeElement::TsPropertySignature(prop) => match *prop.key {
+                Expr::Ident(ref id) if &*id.sym == "props" => {
+                    prop.type_ann.as_ref().map(|t| *t.type_ann.to_owned())
+                }
+                _ => None,
+            },
+            _ => None,
+        }),
+        _ => None,
+    }
+}
+
+/// Props type given as a type argument of the declaration of a component whose props parameter
+/// is not annotated, `FC<ButtonProps>`, `forwardRef<HTMLButtonElement, ButtonProps>` or
+/// `factory<ButtonFactory>`
+fn declared_props_type(component: &str, module: &Module) -> Option<TsType> {
+    let declarator = module.body.iter().find_map(|item| {
+        let var = match item {
+            ModuleItem::Stmt(Stmt::Decl(Decl::Var(var))) => var,
+            ModuleItem::ModuleDecl(ModuleDecl::ExportDecl(export)) => export.decl.as_var()?,
+            _ => return None,
+        };
+        var.decls.iter().find(|d| match d.name {
+            Pat::Ident(ref id) => id.sym.as_str() == component,
+            _ => false,
+        })
+    })?;
+    let annotation = declarator
+        .name
+        .as_ident()
+        .and_then(|id| id.type_ann.as_ref())
+        .and_then(|ann| match *ann.type_ann {
+            TsType::TsTypeRef(ref type_ref) => type_ref.type_params.as_ref(),
+            _ => None,
+        })
+        .and_then(|params| params.params.first());
+    if let Some(ty) = annotation {
+        return Some(*ty.to_owned());
+    }
+    let call = declarator.init.as_deref()?.as_call()?;
+    let type_args = call.type_args.as_ref()?;
+    let ty = if callee_name(call)?.ends_with("forwardRef") {
+        type_args.params.get(1)?
+    } else {
+        type_args.params.first()?
+    };
+    factory_props_type(ty, module).or_else(|| Some(*ty.to_owned()))
+}
+
+/// Finds the JSDoc comment leading the declaration of a component
+pub struct ComponentDocVisitor<'a> {
+    /// Component to look for
+    component: String,
+    /// The description of the component
+    pub description: Option<String>,
+    comments: &'a SingleThreadedComments,
+}
+
+impl<'a> ComponentDocVisitor<'a> {
+    pub fn new(component: &str, comments: &'a SingleThreadedComments) -> Self {
+        ComponentDocVisitor {
+            component: component.to_string(),
+            description: None,
+            comments,
+        }
+    }
+
+    fn declares_component(&self, decl: &Decl) -> bool {
+        match decl {
+            Decl::Fn(func) => func.ident.sym.as_str() == self.component,
+            Decl::Var(var) => var.decls.iter().any(|d| match d.name {
+                Pat::Ident(ref id) => id.sym.as_str() == self.component,
+                _ => false,
+            }),
+            _ => false,
+        }
+    }
+}
+
+impl Visit for ComponentDocVisitor<'_> {
+    fn visit_module_item(&mut self, n: &ModuleItem) {
+        let decl = match n {
+            ModuleItem::Stmt(Stmt::Decl(decl)) => decl,
+            ModuleItem::ModuleDecl(ModuleDecl::ExportDecl(export)) => &export.decl,
+            _ => return,
+        };
+        if self.declares_component(decl) {
+            self.description = leading_doc_comment(self.comments, n.span_lo())
+                .or_else(|| leading_doc_comment(self.comments, decl.span_lo()));
+        }
+    }
+}
@@ -0,0 +1,306 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use glob::Paths;
+use remod_config::Config;
+use serde_json::{json, Value};
+use swc_common::{comments::SingleThreadedComments, SourceMap, Spanned};
+use swc_ecma_ast::{Expr, Module, ObjectPatProp, Pat, PatOrExpr};
+use swc_ecma_visit::VisitWith;
+
+use crate::{
+    default_props::{default_values, module_objects, prop_key, HookDefaultsVisitor},
+    display_name::{module_components, ExpressionStatementVisitor},
+    export_style::{default_export_name, is_story_or_test},
+    props::{extract_props, ComponentDocVisitor, PropDefinition, PropsTypeVisitor},
+    utils::{exported_names, parse_module, should_ignore_entry, snippet},
+};
+
+/// Documentation of a component
+#[derive(Debug, Default, PartialEq)]
+pub struct ComponentDoc {
+    /// Name of the component
+    pub name: String,
+    /// JSDoc description of the component
+    pub description: Option<String>,
+    /// Props of the component with their default values
+    pub props: Vec<PropDefinition>,
+}
+
+/// Default values of the destructuring of the props parameter, `({ size = "md" })`
+fn destructuring_defaults(param: &Pat, cm: &SourceMap) -> Vec<(String, String)> {
+    let object = match param {
+        Pat::Object(object) => object,
+        Pat::Assign(assign) => return destructuring_defaults(&assign.left, cm),
+        _ => return vec![],
+    };
+    object
+        .props
+        .iter()
+        .filter_map(|prop| match prop {
+            ObjectPatProp::Assign(assign) => assign
+                .value
+                .as_ref()
+                .map(|value| (assign.key.sym.to_string(), snippet(cm, value.span()))),
+            ObjectPatProp::KeyValue(kv) => match *kv.value {
+                Pat::Assign(ref assign) => {
+                    Some((prop_key(&kv.key)?, snippet(cm, assign.right.span())))
+                }
+                _ => None,
+            },
+            ObjectPatProp::Rest(..) => None,
+        })
+        .collect()
+}
+
+/// Default values of the props of a component keyed by the prop names. The `defaultProps` and
+/// the default objects passed to its hooks win over the defaults of the destructuring, as React
+/// applies them first
+pub fn prop_defaults(component: &str, module: &Module, cm: &SourceMap) -> Vec<(String, String)> {
+    let mut defaults = vec![];
+    let mut props_type_visitor = PropsTypeVisitor::new(component);
+    module.visit_with(&mut props_type_visitor);
+    if let Some(ref param) = props_type_visitor.param {
+        defaults.extend(destructuring_defaults(param, cm));
+    }
+
+    // Component.defaultProps = {...}
+    let mut expression_stmts_visitor = ExpressionStatementVisitor {
+        property: "defaultProps",
+        assignment_expressions: vec![],
+        expressions: vec![],
+    };
+    module.visit_with(&mut expression_stmts_visitor);
+    let mut objects = expression_stmts_visitor
+        .expressions
+        .iter()
+        .filter_map(|expr| {
+            let assign = expr.as_assign()?;
+            let member = match assign.left {
+                PatOrExpr::Pat(ref pat) => pat.as_expr()?.as_member()?,
+                PatOrExpr::Expr(ref expr) => expr.as_member()?,
+            };
+            match (&*member.obj, &*assign.right) {
+                (Expr::Ident(obj), Expr::Object(object)) if obj.sym.as_str() == component => {
+                    default_values(object, cm)
+                }
+                _ => None,
+            }
+        })
+        .collect::<Vec<Vec<(String, String)>>>();
+
+    // const defaultProps = {...} passed to the hooks of the component
+    let module_objects = module_objects(module);
+    let object_names = module_objects
+        .iter()
+        .map(|o| o.0.clone())
+        .collect::<Vec<String>>();
+    let mut hook_defaults_visitor = HookDefaultsVisitor {
+        component: component.to_owned(),
+        objects: &object_names,
+        arguments: vec![],
+        in_component: false,
+    };
+    module.visit_with(&mut hook_defaults_visitor);
+    for (name, _) in hook_defaults_visitor.arguments {
+        if let Some(values) = module_objects
+            .iter()
+            .find(|o| o.0 == name)
+            .and_then(|o| default_values(&o.1, cm))
+        {
+            objects.push(values);
+        }
+    }
+
+    for (key, value) in objects.into_iter().flatten() {
+        defaults.retain(|(k, _)| *k != key);
+        defaults.push((key, value));
+    }
+    defaults
+}
+
+/// Documentation of the exported components of the module with their props and default values
+pub fn props_docs_module(
+    module: &Module,
+    cm: &SourceMap,
+    comments: &SingleThreadedComments,
+) -> Vec<ComponentDoc> {
+    let exported = exported_names(module);
+    let default = default_export_name(module);
+    module_components(module)
+        .into_iter()
+        .filter(|c| exported.contains(c) || default.as_ref() == Some(c))
+        .map(|component| {
+            let mut docs_visitor = ComponentDocVisitor::new(&component, comments);
+            module.visit_with(&mut docs_visitor);
+            let defaults = prop_defaults(&component, module, cm);
+            let props = extract_props(&component, module, cm, comments)
+                .into_iter()
+                .map(|prop| PropDefinition {
+                    default_value: defaults
+                        .iter()
+                        .find(|(key, _)| *key == prop.name)
+                        .map(|(_, value)| value.to_owned()),
+                    ..prop
+                })
+                .collect();
+            ComponentDoc {
+                name: component,
+                description: docs_visitor.description,
+                props,
+            }
+        })
+        .collect()
+}
+
+/// JSON documentation of the components, in the shape of the react-docgen output
+pub fn props_docs_json(docs: &[(PathBuf, ComponentDoc)]) -> Value {
+    Value::Array(
+        docs.iter()
+            .map(|(path, doc)| {
+                json!({
+                    "displayName": doc.name,
+                    "filePath": path.display().to_string(),
+                    "description": doc.description,
+                    "props": doc.props.iter().map(|prop| json!({
+                        "name": prop.name,
+                        "type": prop.type_text,
+                        "required": prop.required,
+                        "defaultValue": prop.default_value,
+                        "description": prop.description,
+                    })).collect::<Vec<Value>>(),
+                })
+            })
+            .collect(),
+    )
+}
+
+#[derive(Debug, Default)]
+pub struct PropsDocs {
+    /// JSON file the documentation is written to
+    pub output: PathBuf,
+    /// Documented components by file
+    pub docs: Vec<(PathBuf, ComponentDoc)>,
+    /// Count of ignored files
+    pub ignored: i64,
+    /// Count of total files
+    pub total: i64,
+}
+
+impl PropsDocs {
+    pub fn extract_props_docs(&mut self, files: Paths, config: &Config) {
+        for entry in files {
+            match entry {
+                Ok(path) => {
+                    self.total += 1;
+                    if should_ignore_entry(&config.ignore, &path) || is_story_or_test(&path, config)
+                    {
+                        println!("Skipping {}", path.display());
+                        self.ignored += 1;
+                        continue;
+                    }
+                    let (module, cm, comments) = parse_module(&path, config);
+                    for doc in props_docs_module(&module, &cm, &comments) {
+                        println!("{} => {}", path.display(), doc.name);
+                        self.docs.push((path.to_owned(), doc));
+                    }
+                }
+                Err(e) => println!("{:?}", e),
+            }
+        }
+        self.write_json(&self.output);
+    }
+
+    fn write_json(&self, output: &Path) {
+        match serde_json::to_string_pretty(&props_docs_json(&self.docs)) {
+            Ok(json) => match fs::write(output, format!("{}\n", json)) {
+                Ok(_) => println!("=> Props documentation written to {}", output.display()),
+                Err(e) => println!("{:#?}", e),
+            },
+            Err(e) => println!("{:#?}", e),
+        }
+    }
+
+    pub fn display_stats(self) {
+        println!("Total {} files", self.total);
+        println!("Documented {} components", self.docs.len());
+        println!("Ingored {} files", self.ignored);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_props_docs() {
+        let config = Config {
+            typescript: Some(true),
+            ..Config::default()
+        };
+        let path = PathBuf::from("../../tests/props_docs/Slider.tsx");
+        let (module, cm, comments) = parse_module(&path, &config);
+        let docs = props_docs_module(&module, &cm, &comments);
+        assert_eq!(
+            docs.iter().map(|d| d.name.as_str()).collect::<Vec<&str>>(),
+            vec!["Slider", "RangeSlider"]
+        );
+        assert_eq!(
+            docs[0].description.as_deref(),
+            Some("Picks a number in a range")
+        );
+        assert_eq!(
+            docs[0].props,
+            vec![
+                PropDefinition {
+                    name: String::from("value"),
+                    type_text: String::from("number"),
+                    required: true,
+                    description: Some(String::from("Current value")),
+                    default_value: None,
+                },
+                PropDefinition {
+                    name: String::from("step"),
+                    type_text: String::from("number"),
+                    required: false,
+                    description: Some(String::from("Step between the values, `1` by default")),
+                    default_value: Some(String::from("1")),
+                },
+                PropDefinition {
+                    name: String::from("size"),
+                    type_text: String::from("\"sm\" | \"md\" | \"lg\""),
+                    required: false,
+                    description: None,
+                    default_value: Some(String::from("\"md\"")),
+                },
+                PropDefinition {
+                    name: String::from("disabled"),
+                    type_text: String::from("boolean"),
+                    required: false,
+                    description: Some(String::from("Disables the slider")),
+                    default_value: Some(String::from("false")),
+                },
+            ]
+        );
+        assert_eq!(
+            docs[1]
+                .props
+                .iter()
+                .map(|p| (p.name.as_str(), p.default_value.as_deref()))
+                .collect::<Vec<(&str, Option<&str>)>>(),
+            vec![
+                ("min", Some("0")),
+                ("max", Some("100")),
+                ("label", None),
+                ("disabled", Some("true")),
+            ]
+        );
+
+        let json = props_docs_json(&[(path, docs.into_iter().next().unwrap())]);
+        assert_eq!(json[0]["displayName"], "Slider");
+        assert_eq!(json[0]["props"][1]["defaultValue"], "1");
+        assert_eq!(json[0]["props"][0]["defaultValue"], Value::Null);
+    }
+}
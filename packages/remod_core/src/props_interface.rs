@@ -0,0 +1,374 @@
+use std::fs;
+
+use glob::Paths;
+use remod_config::Config;
+use swc_common::{comments::SingleThreadedComments, SourceMap, Span, Spanned};
+use swc_ecma_ast::{
+    Decl, DefaultDecl, Expr, Function, Module, ModuleDecl, ModuleItem, Pat, Stmt, TsEntityName,
+    TsType, TsTypeLit, TsTypeParamDecl, TsUnionOrIntersectionType,
+};
+use swc_ecma_visit::VisitWith;
+
+use crate::{
+    display_name::module_components,
+    utils::{
+        apply_edits, line_start_with_comments, parse_module, reindent, should_ignore_entry,
+        snippet, IdentReferences, SourceEdit,
+    },
+};
+
+/// Props type declared inline by a component
+struct InlineProps<'a> {
+    /// Statement declaring the component
+    item: &'a ModuleItem,
+    /// The annotation to replace with the interface
+    type_ann: &'a TsType,
+    lit: &'a TsTypeLit,
+    /// Types the inline type is intersected with
+    extends: Vec<&'a TsType>,
+    type_params: Option<&'a TsTypeParamDecl>,
+}
+
+/// `{ a: string }` or `{ a: string } & HTMLAttributes<HTMLDivElement>`
+fn inline_type(ty: &TsType) -> Option<(&TsTypeLit, Vec<&TsType>)> {
+    match ty {
+        TsType::TsTypeLit(lit) => Some((lit, vec![])),
+        TsType::TsUnionOrIntersectionType(TsUnionOrIntersectionType::TsIntersectionType(
+            intersection,
+        )) => {
+            let mut lits = intersection.types.iter().filter_map(|t| match **t {
+                TsType::TsTypeLit(ref lit) => Some(lit),
+                _ => None,
+            });
+            let lit = lits.next()?;
+            if lits.next().is_some() {
+                return None;
+            }
+            let extends = intersection
+                .types
+                .iter()
+                .filter(|t| !t.is_ts_type_lit())
+                .map(|t| &**t)
+                .collect::<Vec<&TsType>>();
+            // Interfaces only extend named types
+            if extends.iter().all(|t| t.is_ts_type_ref()) {
+                Some((lit, extends))
+            } else {
+                None
+            }
+        }
+        _ => None,
+    }
+}
+
+fn pat_type(pat: &Pat) -> Option<&TsType> {
+    match pat {
+        Pat::Ident(id) => id.type_ann.as_ref().map(|t| &*t.type_ann),
+        Pat::Object(obj) => obj.type_ann.as_ref().map(|t| &*t.type_ann),
+        Pat::Assign(assign) => pat_type(&assign.left),
+        _ => None,
+    }
+}
+
+fn function_props(function: &Function) -> Option<(&TsType, Option<&TsTypeParamDecl>)> {
+    let param = function.params.first()?;
+    Some((pat_type(&param.pat)?, function.type_params.as_deref()))
+}
+
+/// Props type of a component declared as an arrow or wrapped in a factory call
+fn expr_props(expr: &Expr) -> Option<(&TsType, Option<&TsTypeParamDecl>)> {
+    match expr {
+        Expr::Arrow(arrow) => Some((
+            pat_type(arrow.params.first()?)?,
+            arrow.type_params.as_deref(),
+        )),
+        Expr::Fn(func) => function_props(&func.function),
+        Expr::Paren(paren) => expr_props(&paren.expr),
+        Expr::Call(call) => call.args.first().and_then(|arg| expr_props(&arg.expr)),
+        _ => None,
+    }
+}
+
+/// `React.FC<{ a: string }>` annotation of a component variable
+fn fc_props(ty: &TsType) -> Option<&TsType> {
+    let type_ref = ty.as_ts_type_ref()?;
+    let name = match type_ref.type_name {
+        TsEntityName::Ident(ref id) => &id.sym,
+        TsEntityName::TsQualifiedName(ref qualified) => &qualified.right.sym,
+    };
+    if !matches!(&**name, "FC" | "FunctionComponent" | "VFC") {
+        return None;
+    }
+    type_ref.type_params.as_ref()?.params.first().map(|t| &**t)
+}
+
+fn inline_props<'a>(module: &'a Module, component: &str) -> Option<InlineProps<'a>> {
+    for item in module.body.iter() {
+        let decl = match item {
+            ModuleItem::Stmt(Stmt::Decl(decl)) => decl,
+            ModuleItem::ModuleDecl(ModuleDecl::ExportDecl(export)) => &export.decl,
+            ModuleItem::ModuleDecl(ModuleDecl::ExportDefaultDecl(export)) => match export.decl {
+                DefaultDecl::Fn(ref func)
+                    if func.ident.as_ref().is_some_and(|id| &*id.sym == component) =>
+                {
+                    let (type_ann, type_params) = function_props(&func.function)?;
+                    let (lit, extends) = inline_type(type_ann)?;
+                    return Some(InlineProps {
+                        item,
+                        type_ann,
+                        lit,
+                        extends,
+                        type_params,
+                    });
+                }
+                _ => continue,
+            },
+            _ => continue,
+        };
+        let (type_ann, type_params) = match decl {
+            Decl::Fn(func) if &*func.ident.sym == component => function_props(&func.function)?,
+            Decl::Var(var) => {
+                let declarator = var.decls.iter().find(|d| match d.name {
+                    Pat::Ident(ref id) => &*id.sym == component,
+                    _ => false,
+                });
+                let declarator = match declarator {
+                    Some(declarator) => declarator,
+                    None => continue,
+                };
+                let fc = declarator
+                    .name
+                    .as_ident()
+                    .and_then(|id| id.type_ann.as_ref())
+                    .and_then(|t| fc_props(&t.type_ann));
+                match fc {
+                    Some(type_ann) => (type_ann, None),
+                    None => expr_props(declarator.init.as_deref()?)?,
+                }
+            }
+            _ => continue,
+        };
+        let (lit, extends) = inline_type(type_ann)?;
+        return Some(InlineProps {
+            item,
+            type_ann,
+            lit,
+            extends,
+            type_params,
+        });
+    }
+    None
+}
+
+/// Moves the inline props types of the components into `export interface {Component}Props`
+/// declarations placed before them. Returns `None` when no component declares its props inline
+pub fn extract_props_interface_module(
+    module: &Module,
+    cm: &SourceMap,
+    comments: &SingleThreadedComments,
+) -> Option<String> {
+    let mut edits = vec![];
+    for component in module_components(module) {
+        let props = match inline_props(module, &component) {
+            Some(props) if !props.lit.members.is_empty() => props,
+            _ => continue,
+        };
+        let interface = format!("{}Props", component);
+        let mut references = IdentReferences::new(vec![interface.to_owned()]);
+        module.visit_with(&mut references);
+        if !references.spans.is_empty() {
+            println!(
+                "=> Skipping '{}', '{}' is already declared",
+                component, interface
+            );
+            continue;
+        }
+
+        let lit_text = snippet(cm, props.lit.span);
+        let members = if lit_text.contains('\n') {
+            // Keeps the comments of the members
+            let inner = &lit_text[1..lit_text.len() - 1];
+            reindent(inner, "  ")
+        } else {
+            props
+                .lit
+                .members
+                .iter()
+                .map(|member| {
+                    let text = snippet(cm, member.span());
+                    format!("  {};", text.trim_end_matches([';', ',']))
+                })
+                .collect::<Vec<String>>()
+                .join("\n")
+        };
+        let (params, args) = match props.type_params {
+            Some(type_params) => (
+                format!(
+                    "<{}>",
+                    type_params
+                        .params
+                        .iter()
+                        .map(|p| snippet(cm, p.span))
+                        .collect::<Vec<String>>()
+                        .join(", ")
+                ),
+                format!(
+                    "<{}>",
+                    type_params
+                        .params
+                        .iter()
+                        .map(|p| p.name.sym.to_string())
+                        .collect::<Vec<String>>()
+                        .join(", ")
+                ),
+            ),
+            None => (String::new(), String::new()),
+        };
+        let extends = if props.extends.is_empty() {
+            String::new()
+        } else {
+            format!(
+                " extends {}",
+                props
+                    .extends
+                    .iter()
+                    .map(|t| snippet(cm, t.span()))
+                    .collect::<Vec<String>>()
+                    .join(", ")
+            )
+        };
+        edits.push(SourceEdit::insert(
+            line_start_with_comments(cm, comments, props.item.span_lo()),
+            format!(
+                "export interface {}{}{} {{\n{}\n}}\n\n",
+                interface, params, extends, members
+            ),
+        ));
+        let type_span: Span = props.type_ann.span();
+        edits.push(SourceEdit::replace(
+            type_span,
+            format!("{}{}", interface, args),
+        ));
+    }
+    if edits.is_empty() {
+        return None;
+    }
+    apply_edits(cm, edits)
+}
+
+#[derive(Debug, Default)]
+pub struct PropsInterface {
+    ///  Count of modified files
+    pub modified: i64,
+    /// Count of ignored files
+    pub ignored: i64,
+    /// Count of total files
+    pub total: i64,
+}
+
+impl PropsInterface {
+    pub fn extract_props_interfaces(&mut self, files: Paths, config: &Config) {
+        for entry in files {
+            match entry {
+                Ok(path) => {
+                    self.total += 1;
+                    if should_ignore_entry(&config.ignore, &path) {
+                        println!("Skipping {}", path.display());
+                        self.ignored += 1;
+                        continue;
+                    }
+                    let (module, cm, comments) = parse_module(&path, config);
+                    match extract_props_interface_module(&module, &cm, &comments) {
+                        Some(output) => match fs::write(&path, output) {
+                            Ok(_) => {
+                                println!("{}", path.display());
+                                self.modified += 1;
+                            }
+                            Err(e) => println!("{:#?}", e),
+                        },
+                        None => self.ignored += 1,
+                    }
+                }
+                Err(e) => println!("{:?}", e),
+            }
+        }
+    }
+
+    pub fn display_stats(self) {
+        println!("Total {} files", self.total);
+        println!("Modified {} files", self.modified);
+        println!("Ingored {} files", self.ignored);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::path::PathBuf;
+
+    use super::*;
+
+    #[test]
+    fn test_extract_props_interface() {
+        let config = Config {
+            typescript: Some(true),
+            ..Config::default()
+        };
+        let path = PathBuf::from("../../tests/props_interface/components.tsx");
+        let (module, cm, comments) = parse_module(&path, &config);
+        let output = extract_props_interface_module(&module, &cm, &comments).unwrap();
+        assert_eq!(
+            output,
+            r#"import type { FC, HTMLAttributes, ReactNode } from "react";
+
+export interface UserCardProps {
+  name: string;
+  age?: number;
+}
+
+/** Shows a user */
+export const UserCard = ({ name, age }: UserCardProps) => (
+  <div>
+    {name} {age}
+  </div>
+);
+
+export interface PanelProps extends HTMLAttributes<HTMLDivElement> {
+  /** Heading of the panel */
+  title: string;
+  children: ReactNode;
+}
+
+export function Panel({
+  title,
+  children,
+  ...rest
+}: PanelProps) {
+  return (
+    <section {...rest}>
+      <h2>{title}</h2>
+      {children}
+    </section>
+  );
+}
+
+export interface ListProps<T> {
+  items: T[];
+}
+
+export const List = <T,>({ items }: ListProps<T>) => <ul>{items.length}</ul>;
+
+export interface BadgeProps {
+  label: string;
+}
+
+export const Badge: FC<BadgeProps> = ({ label }) => <span>{label}</span>;
+
+interface ButtonProps {
+  label: string;
+}
+
+export const Button = ({ label }: ButtonProps) => <button>{label}</button>;
+"#
+        );
+    }
+}
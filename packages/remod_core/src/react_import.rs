@@ -0,0 +1,244 @@
+use std::fs;
+
+use glob::Paths;
+use remod_config::Config;
+use swc_common::{SourceMap, Span, Spanned};
+use swc_ecma_ast::{
+    Expr, ImportSpecifier, JSXMemberExpr, JSXObject, MemberExpr, MemberProp, Module, TsEntityName,
+    TsQualifiedName,
+};
+use swc_ecma_visit::{Visit, VisitWith};
+
+use crate::utils::{
+    apply_edits, parse_module, remove_import_specifier, should_ignore_entry, IdentReferences,
+    ReactImports, SourceEdit,
+};
+
+/// Finds the members of the React namespace used by the module, `React.useState`
+struct NamespaceMembers<'a> {
+    /// Local name of the namespace
+    namespace: &'a str,
+    /// Name of the member, the span of its use and whether it is used as a type
+    pub members: Vec<(String, Span, bool)>,
+}
+
+impl Visit for NamespaceMembers<'_> {
+    fn visit_member_expr(&mut self, n: &MemberExpr) {
+        if let (Expr::Ident(ref obj), MemberProp::Ident(ref prop)) = (&*n.obj, &n.prop) {
+            if &*obj.sym == self.namespace {
+                self.members.push((prop.sym.to_string(), n.span, false));
+                return;
+            }
+        }
+        n.visit_children_with(self);
+    }
+
+    fn visit_jsx_member_expr(&mut self, n: &JSXMemberExpr) {
+        if let JSXObject::Ident(ref obj) = n.obj {
+            if &*obj.sym == self.namespace {
+                self.members.push((n.prop.sym.to_string(), n.span(), false));
+                return;
+            }
+        }
+        n.visit_children_with(self);
+    }
+
+    fn visit_ts_qualified_name(&mut self, n: &TsQualifiedName) {
+        if let TsEntityName::Ident(ref left) = n.left {
+            if &*left.sym == self.namespace {
+                self.members.push((n.right.sym.to_string(), n.span(), true));
+                return;
+            }
+        }
+        n.visit_children_with(self);
+    }
+}
+
+/// Removes the default or namespace import of React when the module does not reference it.
+/// With `named_imports`, the uses of `React.*` are rewritten to named imports first.
+/// Returns `None` when the import is still needed
+pub fn remove_react_import_module(
+    module: &Module,
+    cm: &SourceMap,
+    named_imports: bool,
+) -> Option<String> {
+    let file = cm.lookup_byte_offset(module.span.lo).sf;
+    if file.src.contains("@jsx ") {
+        println!("=> The module sets a JSX pragma and needs React in scope");
+        return None;
+    }
+    let mut imports = ReactImports::default();
+    module.visit_with(&mut imports);
+    let namespace = imports.namespace.as_ref()?;
+    let mut edits = vec![];
+
+    let mut references = IdentReferences::new(vec![namespace.to_owned()]);
+    module.visit_with(&mut references);
+    if !references.spans.is_empty() {
+        if !named_imports {
+            return None;
+        }
+        let mut namespace_members = NamespaceMembers {
+            namespace,
+            members: vec![],
+        };
+        module.visit_with(&mut namespace_members);
+        if namespace_members.members.len() != references.spans.len() {
+            println!(
+                "=> '{}' is used as a value and can not be removed",
+                namespace
+            );
+            return None;
+        }
+        let mut names: Vec<String> = vec![];
+        for (name, span, is_type) in namespace_members.members.iter() {
+            let local = match imports.named.get(name) {
+                Some(local) => local.to_owned(),
+                None => {
+                    let mut clashes = IdentReferences::new(vec![name.to_owned()]);
+                    module.visit_with(&mut clashes);
+                    if !clashes.spans.is_empty() {
+                        println!(
+                            "=> '{}' is already declared. Rename it to import it from react",
+                            name
+                        );
+                        return None;
+                    }
+                    // A value import covers the uses as a type as well
+                    let type_only = format!("type {}", name);
+                    if *is_type {
+                        if !names.contains(name) && !names.contains(&type_only) {
+                            names.push(type_only);
+                        }
+                    } else if let Some(index) = names.iter().position(|n| n == &type_only) {
+                        names[index] = name.to_owned();
+                    } else if !names.contains(name) {
+                        names.push(name.to_owned());
+                    }
+                    name.to_owned()
+                }
+            };
+            edits.push(SourceEdit::replace(*span, local));
+        }
+        // Imported alongside the default import, or in place of the namespace import
+        let namespace_only = imports.decls.iter().find(|decl| {
+            matches!(decl.specifiers.as_slice(), [ImportSpecifier::Namespace(..)])
+                || matches!(decl.specifiers.as_slice(), [ImportSpecifier::Default(..)])
+        });
+        let has_named = imports.decls.iter().any(|decl| {
+            decl.specifiers
+                .iter()
+                .any(|s| matches!(s, ImportSpecifier::Named(..)))
+        });
+        match namespace_only {
+            Some(decl) if !has_named && !names.is_empty() => {
+                edits.push(SourceEdit::replace(
+                    decl.specifiers[0].span(),
+                    format!("{{ {} }}", names.join(", ")),
+                ));
+                return apply_edits(cm, edits);
+            }
+            _ => edits.extend(imports.add_named(module, &names)),
+        }
+    }
+
+    for decl in imports.decls.iter() {
+        let index = decl.specifiers.iter().position(|s| {
+            matches!(
+                s,
+                ImportSpecifier::Default(..) | ImportSpecifier::Namespace(..)
+            )
+        });
+        if let Some(index) = index {
+            edits.push(remove_import_specifier(cm, decl, index));
+        }
+    }
+    apply_edits(cm, edits)
+}
+
+#[derive(Debug, Default)]
+pub struct ReactImport {
+    /// Rewrite `React.useState` and the like to named imports
+    pub named_imports: bool,
+    ///  Count of modified files
+    pub modified: i64,
+    /// Count of ignored files
+    pub ignored: i64,
+    /// Count of total files
+    pub total: i64,
+}
+
+impl ReactImport {
+    pub fn remove_react_import(&mut self, files: Paths, config: &Config) {
+        for entry in files {
+            match entry {
+                Ok(path) => {
+                    self.total += 1;
+                    if should_ignore_entry(&config.ignore, &path) {
+                        println!("Skipping {}", path.display());
+                        self.ignored += 1;
+                        continue;
+                    }
+                    let (module, cm, _comments) = parse_module(&path, config);
+                    match remove_react_import_module(&module, &cm, self.named_imports) {
+                        Some(output) => match fs::write(&path, output) {
+                            Ok(_) => {
+                                println!("{}", path.display());
+                                self.modified += 1;
+                            }
+                            Err(e) => println!("{:#?}", e),
+                        },
+                        None => self.ignored += 1,
+                    }
+                }
+                Err(e) => println!("{:?}", e),
+            }
+        }
+    }
+
+    pub fn display_stats(self) {
+        println!("Total {} files", self.total);
+        println!("Modified {} files", self.modified);
+        println!("Ingored {} files", self.ignored);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::path::PathBuf;
+
+    use super::*;
+
+    #[test]
+    fn test_remove_react_import() {
+        let config = Config {
+            typescript: Some(true),
+            ..Config::default()
+        };
+        let path = PathBuf::from("../../tests/react_import/jsx_only.tsx");
+        let (module, cm, _comments) = parse_module(&path, &config);
+        let output = remove_react_import_module(&module, &cm, false).unwrap();
+        assert!(output.starts_with("import { useState } from \"react\";\n"));
+
+        let path = PathBuf::from("../../tests/react_import/apis.tsx");
+        let (module, cm, _comments) = parse_module(&path, &config);
+        assert_eq!(remove_react_import_module(&module, &cm, false), None);
+        let output = remove_react_import_module(&module, &cm, true).unwrap();
+        assert_eq!(
+            output,
+            r#"import { createContext, type FC, useState, Fragment } from "react";
+
+export const Context = createContext<string>("");
+
+export const Provider: FC<{ value: string }> = ({ value, children }) => {
+  const [state] = useState(value);
+  return (
+    <Fragment>
+      <Context.Provider value={state}>{children}</Context.Provider>
+    </Fragment>
+  );
+};
+"#
+        );
+    }
+}
@@ -0,0 +1,656 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use glob::Paths;
+use remod_config::Config;
+use similar::TextDiff;
+use swc_common::SourceMap;
+use swc_ecma_ast::{
+    AssignExpr, Decl, ExportSpecifier, Expr, ImportSpecifier, KeyValueProp, Lit, Module,
+    ModuleDecl, ModuleExportName, ModuleItem, Pat, PatOrExpr, PropName, Str,
+};
+use swc_ecma_visit::{Visit, VisitWith};
+
+use crate::{
+    display_name::module_components,
+    split::resolves_to,
+    utils::{
+        apply_edits, exported_names, parse_module, should_ignore_entry, snippet, IdentReferences,
+        SourceEdit,
+    },
+};
+
+/// Display name value renamed along with the component, `"Old"` or a prefixed `"app_Old"`
+fn renamed_display_name(value: &str, old: &str, new: &str) -> Option<String> {
+    let prefix = value.strip_suffix(old)?;
+    if prefix.chars().last().is_some_and(|c| c.is_alphanumeric()) {
+        return None;
+    }
+    Some(format!("{}{}", prefix, new))
+}
+
+/// Collects the string values of `Old.displayName = "Old"` assignments
+struct DisplayNameValues<'a> {
+    old: &'a str,
+    values: Vec<Str>,
+}
+
+impl Visit for DisplayNameValues<'_> {
+    fn visit_assign_expr(&mut self, n: &AssignExpr) {
+        let member = match n.left {
+            PatOrExpr::Expr(ref expr) => expr.as_member(),
+            PatOrExpr::Pat(ref pat) => match **pat {
+                Pat::Expr(ref expr) => expr.as_member(),
+                _ => None,
+            },
+        };
+        let is_display_name = member.is_some_and(|member| {
+            member.obj.as_ident().is_some_and(|id| &*id.sym == self.old)
+                && member
+                    .prop
+                    .as_ident()
+                    .is_some_and(|id| &*id.sym == "displayName")
+        });
+        if let (true, Expr::Lit(Lit::Str(value))) = (is_display_name, &*n.right) {
+            self.values.push(value.to_owned());
+        }
+        n.visit_children_with(self);
+    }
+}
+
+/// Collects the `title: "Components/Old"` values of story metas
+struct StoryTitles<'a> {
+    old: &'a str,
+    values: Vec<Str>,
+}
+
+impl Visit for StoryTitles<'_> {
+    fn visit_key_value_prop(&mut self, n: &KeyValueProp) {
+        let is_title = match n.key {
+            PropName::Ident(ref id) => &*id.sym == "title",
+            PropName::Str(ref s) => &*s.value == "title",
+            _ => false,
+        };
+        if let (true, Expr::Lit(Lit::Str(value))) = (is_title, &*n.value) {
+            if value.value.rsplit('/').next() == Some(self.old) {
+                self.values.push(value.to_owned());
+            }
+        }
+        n.visit_children_with(self);
+    }
+}
+
+/// Story exports named after the component, `Old_Primary` or `OldDisabled`
+fn story_exports(module: &Module, old: &str) -> Vec<String> {
+    module
+        .body
+        .iter()
+        .filter_map(|item| match item {
+            ModuleItem::ModuleDecl(ModuleDecl::ExportDecl(export)) => match export.decl {
+                Decl::Var(ref var) => Some(var.decls.iter().filter_map(|d| d.name.as_ident())),
+                _ => None,
+            },
+            _ => None,
+        })
+        .flatten()
+        .map(|id| id.sym.to_string())
+        .filter(|name| {
+            name.strip_prefix(old).is_some_and(|rest| {
+                rest.starts_with('_') || rest.starts_with(|c: char| c.is_ascii_uppercase())
+            })
+        })
+        .collect()
+}
+
+/// Import source pointing to the file renamed after the component
+fn renamed_source(source: &str, old: &str, new: &str) -> Option<String> {
+    let (dir, file) = source.rsplit_once('/').unwrap_or(("", source));
+    let rest = file.strip_prefix(old)?;
+    if !rest.is_empty() && !rest.starts_with('.') {
+        return None;
+    }
+    Some(if dir.is_empty() {
+        format!("{}{}", new, rest)
+    } else {
+        format!("{}/{}{}", dir, new, rest)
+    })
+}
+
+/// A component renamed across the project
+#[derive(Debug, Default)]
+pub struct ComponentRename {
+    pub old: String,
+    pub new: String,
+    /// The file declaring the component, followed by the files re-exporting it
+    pub sources: Vec<PathBuf>,
+    /// Whether the declaring file is named after the component and gets renamed too
+    pub rename_file: bool,
+}
+
+impl ComponentRename {
+    /// The component and its props interface
+    fn names(&self) -> [(String, String); 2] {
+        [
+            (self.old.to_owned(), self.new.to_owned()),
+            (format!("{}Props", self.old), format!("{}Props", self.new)),
+        ]
+    }
+
+    fn renamed(&self, name: &str) -> Option<String> {
+        self.names()
+            .into_iter()
+            .find(|(old, _)| old == name)
+            .map(|(_, new)| new)
+    }
+
+    /// Whether the module already uses one of the new names
+    pub fn conflicts(&self, module: &Module) -> bool {
+        let new_names = self
+            .names()
+            .into_iter()
+            .map(|(_, new)| new)
+            .collect::<Vec<String>>();
+        let mut references = IdentReferences::new(new_names.to_owned());
+        module.visit_with(&mut references);
+        let imports_new = module.body.iter().any(|item| match item {
+            ModuleItem::ModuleDecl(ModuleDecl::Import(decl)) => {
+                decl.specifiers.iter().any(|specifier| {
+                    let local = match specifier {
+                        ImportSpecifier::Named(named) => &named.local,
+                        ImportSpecifier::Default(default) => &default.local,
+                        ImportSpecifier::Namespace(namespace) => &namespace.local,
+                    };
+                    new_names.iter().any(|name| name.as_str() == &*local.sym)
+                })
+            }
+            _ => false,
+        });
+        !references.spans.is_empty() || imports_new
+    }
+
+    /// Whether the module re-exports the component from one of the sources under its own name
+    pub fn re_exports(&self, module: &Module, path: &Path) -> bool {
+        module.body.iter().any(|item| match item {
+            ModuleItem::ModuleDecl(ModuleDecl::ExportAll(export)) => self
+                .sources
+                .iter()
+                .any(|source| resolves_to(path, &export.src.value, source).is_some()),
+            ModuleItem::ModuleDecl(ModuleDecl::ExportNamed(export)) => {
+                export.src.as_ref().is_some_and(|src| {
+                    self.sources
+                        .iter()
+                        .any(|source| resolves_to(path, &src.value, source).is_some())
+                }) && export.specifiers.iter().any(|specifier| match specifier {
+                    ExportSpecifier::Named(named) => {
+                        named.exported.is_none()
+                            && matches!(named.orig, ModuleExportName::Ident(ref id) if *id.sym == *self.old)
+                    }
+                    _ => false,
+                })
+            }
+            _ => false,
+        })
+    }
+
+    /// Renames the component, its props interface and its `displayName` in the module declaring them
+    pub fn rename_declaring_module(&self, module: &Module, cm: &SourceMap) -> Option<String> {
+        let mut edits = vec![];
+        let mut references =
+            IdentReferences::new(self.names().into_iter().map(|(old, _)| old).collect());
+        module.visit_with(&mut references);
+        for span in references.spans {
+            if let Some(new) = self.renamed(&snippet(cm, span)) {
+                edits.push(SourceEdit::replace(span, new));
+            }
+        }
+        let mut display_names = DisplayNameValues {
+            old: &self.old,
+            values: vec![],
+        };
+        module.visit_with(&mut display_names);
+        for value in display_names.values {
+            if let Some(renamed) = renamed_display_name(&value.value, &self.old, &self.new) {
+                let quote = snippet(cm, value.span).chars().next().unwrap_or('"');
+                edits.push(SourceEdit::replace(
+                    value.span,
+                    format!("{q}{}{q}", renamed, q = quote),
+                ));
+            }
+        }
+        if edits.is_empty() {
+            return None;
+        }
+        apply_edits(cm, edits)
+    }
+
+    /// Renames the imports and re-exports of the component, the JSX using it and, in its story file,
+    /// the story exports and the title. Returns `None` when the module does not import the component
+    pub fn rename_importing_module(
+        &self,
+        module: &Module,
+        cm: &SourceMap,
+        path: &Path,
+        story: bool,
+    ) -> Option<String> {
+        let mut edits = vec![];
+        // Local names renamed along with the imports
+        let mut locals = vec![];
+        for item in module.body.iter() {
+            let src = match item {
+                ModuleItem::ModuleDecl(ModuleDecl::Import(decl)) => &decl.src,
+                ModuleItem::ModuleDecl(ModuleDecl::ExportNamed(export)) => match export.src {
+                    Some(ref src) => src,
+                    None => continue,
+                },
+                ModuleItem::ModuleDecl(ModuleDecl::ExportAll(export)) => &export.src,
+                _ => continue,
+            };
+            let (index, via_index) = match self
+                .sources
+                .iter()
+                .enumerate()
+                .find_map(|(i, source)| Some((i, resolves_to(path, &src.value, source)?)))
+            {
+                Some(found) => found,
+                None => continue,
+            };
+            if index == 0 && !via_index && self.rename_file {
+                if let Some(source) = renamed_source(&src.value, &self.old, &self.new) {
+                    let quote = snippet(cm, src.span).chars().next().unwrap_or('"');
+                    edits.push(SourceEdit::replace(
+                        src.span,
+                        format!("{q}{}{q}", source, q = quote),
+                    ));
+                }
+            }
+            match item {
+                ModuleItem::ModuleDecl(ModuleDecl::Import(decl)) => {
+                    for specifier in decl.specifiers.iter() {
+                        match specifier {
+                            ImportSpecifier::Named(named) => match named.imported {
+                                Some(ModuleExportName::Ident(ref imported)) => {
+                                    if let Some(new) = self.renamed(&imported.sym) {
+                                        edits.push(SourceEdit::replace(imported.span, new));
+                                    }
+                                }
+                                Some(ModuleExportName::Str(..)) => {}
+                                None => {
+                                    if let Some(new) = self.renamed(&named.local.sym) {
+                                        edits.push(SourceEdit::replace(named.local.span, new));
+                                        locals.push(named.local.sym.to_string());
+                                    }
+                                }
+                            },
+                            ImportSpecifier::Default(default)
+                                if index == 0 && *default.local.sym == *self.old =>
+                            {
+                                edits.push(SourceEdit::replace(
+                                    default.local.span,
+                                    self.new.to_owned(),
+                                ));
+                                locals.push(self.old.to_owned());
+                            }
+                            _ => {}
+                        }
+                    }
+                }
+                ModuleItem::ModuleDecl(ModuleDecl::ExportNamed(export)) => {
+                    for specifier in export.specifiers.iter() {
+                        if let ExportSpecifier::Named(named) = specifier {
+                            if let ModuleExportName::Ident(ref orig) = named.orig {
+                                if let Some(new) = self.renamed(&orig.sym) {
+                                    edits.push(SourceEdit::replace(orig.span, new));
+                                }
+                            }
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+        if edits.is_empty() {
+            return None;
+        }
+
+        let mut stories = vec![];
+        if story {
+            stories = story_exports(module, &self.old);
+            let mut titles = StoryTitles {
+                old: &self.old,
+                values: vec![],
+            };
+            module.visit_with(&mut titles);
+            for value in titles.values {
+                let title = match value.value.rsplit_once('/') {
+                    Some((dir, _)) => format!("{}/{}", dir, self.new),
+                    None => self.new.to_owned(),
+                };
+                let quote = snippet(cm, value.span).chars().next().unwrap_or('"');
+                edits.push(SourceEdit::replace(
+                    value.span,
+                    format!("{q}{}{q}", title, q = quote),
+                ));
+            }
+        }
+        let mut references = IdentReferences::new(
+            locals
+                .iter()
+                .chain(stories.iter())
+                .map(|name| name.to_owned())
+                .collect(),
+        );
+        module.visit_with(&mut references);
+        for span in references.spans {
+            let name = snippet(cm, span);
+            let new = match self.renamed(&name) {
+                Some(new) => new,
+                None => format!("{}{}", self.new, &name[self.old.len()..]),
+            };
+            edits.push(SourceEdit::replace(span, new));
+        }
+        apply_edits(cm, edits)
+    }
+}
+
+/// Renamed file with its contents before and after the rename
+struct FileChange {
+    path: PathBuf,
+    renamed_to: PathBuf,
+    before: String,
+    after: String,
+}
+
+#[derive(Debug, Default)]
+pub struct RenameComponent {
+    /// Current name of the component
+    pub old: String,
+    /// New name of the component
+    pub new: String,
+    /// Only show the diff without writing the files
+    pub dry_run: bool,
+    ///  Count of modified files
+    pub modified: i64,
+    /// Count of ignored files
+    pub ignored: i64,
+    /// Count of total files
+    pub total: i64,
+}
+
+impl RenameComponent {
+    pub fn rename_component(&mut self, files: Paths, config: &Config) {
+        let mut paths = vec![];
+        for entry in files {
+            match entry {
+                Ok(path) => {
+                    self.total += 1;
+                    if should_ignore_entry(&config.ignore, &path) {
+                        println!("Skipping {}", path.display());
+                        self.ignored += 1;
+                        continue;
+                    }
+                    paths.push(path);
+                }
+                Err(e) => println!("{:?}", e),
+            }
+        }
+
+        let mut declaring = vec![];
+        for path in paths.iter() {
+            let (module, _cm, _comments) = parse_module(path, config);
+            if module_components(&module).contains(&self.old) {
+                declaring.push((exported_names(&module).contains(&self.old), path.to_owned()));
+            }
+        }
+        if declaring.iter().any(|(exported, _)| *exported) {
+            declaring.retain(|(exported, _)| *exported);
+        }
+        let declaring = match declaring.as_slice() {
+            [(_, path)] => path.to_owned(),
+            [] => {
+                println!("=> No component named '{}' found", self.old);
+                return;
+            }
+            _ => {
+                println!("=> Several files declare '{}':", self.old);
+                for (_, path) in declaring.iter() {
+                    println!("   {}", path.display());
+                }
+                return;
+            }
+        };
+
+        let stem = declaring
+            .file_stem()
+            .map_or(String::new(), |s| s.to_string_lossy().to_string());
+        let rename_file = stem == self.old;
+        let mut moves = vec![];
+        if rename_file {
+            let extension = declaring
+                .extension()
+                .map_or(String::new(), |e| format!(".{}", e.to_string_lossy()));
+            moves.push((
+                declaring.to_owned(),
+                declaring.with_file_name(format!("{}{}", self.new, extension)),
+            ));
+        }
+        let story_ext = config
+            .story_file_ext
+            .to_owned()
+            .unwrap_or(String::from(".stories.tsx"));
+        let story = declaring.with_file_name(format!("{}{}", stem, story_ext));
+        if story.exists() {
+            if rename_file {
+                moves.push((
+                    story.to_owned(),
+                    story.with_file_name(format!("{}{}", self.new, story_ext)),
+                ));
+            }
+            if !paths.contains(&story) {
+                paths.push(story.to_owned());
+            }
+        }
+        if let Some((_, to)) = moves.iter().find(|(_, to)| to.exists()) {
+            println!("=> Cannot rename, {} already exists", to.display());
+            return;
+        }
+
+        let mut rename = ComponentRename {
+            old: self.old.to_owned(),
+            new: self.new.to_owned(),
+            sources: vec![declaring.to_owned()],
+            rename_file,
+        };
+        // Barrel files re-exporting the component, and the ones re-exporting those
+        loop {
+            let barrels = paths
+                .iter()
+                .filter(|path| !rename.sources.contains(path))
+                .filter(|path| {
+                    let (module, _cm, _comments) = parse_module(path, config);
+                    rename.re_exports(&module, path)
+                })
+                .cloned()
+                .collect::<Vec<PathBuf>>();
+            if barrels.is_empty() {
+                break;
+            }
+            rename.sources.extend(barrels);
+        }
+
+        let mut changes = vec![];
+        for path in paths.iter() {
+            let (module, cm, _comments) = parse_module(path, config);
+            let output = if *path == declaring {
+                rename.rename_declaring_module(&module, &cm)
+            } else {
+                rename.rename_importing_module(&module, &cm, path, *path == story)
+            };
+            let renamed_to = moves
+                .iter()
+                .find(|(from, _)| from == path)
+                .map_or(path.to_owned(), |(_, to)| to.to_owned());
+            if output.is_none() && renamed_to == *path {
+                continue;
+            }
+            if rename.conflicts(&module) {
+                println!(
+                    "=> Cannot rename, '{}' is already used in {}",
+                    self.new,
+                    path.display()
+                );
+                return;
+            }
+            match fs::read_to_string(path) {
+                Ok(before) => changes.push(FileChange {
+                    path: path.to_owned(),
+                    renamed_to,
+                    after: output.unwrap_or(before.to_owned()),
+                    before,
+                }),
+                Err(e) => println!("{:#?}", e),
+            }
+        }
+
+        for change in changes.iter() {
+            let diff = TextDiff::from_lines(&change.before, &change.after);
+            print!(
+                "{}",
+                diff.unified_diff().header(
+                    &change.path.display().to_string(),
+                    &change.renamed_to.display().to_string()
+                )
+            );
+        }
+        if self.dry_run {
+            return;
+        }
+        for change in changes {
+            if let Err(e) = fs::write(&change.renamed_to, &change.after) {
+                println!("{:#?}", e);
+                continue;
+            }
+            if change.renamed_to != change.path {
+                match fs::remove_file(&change.path) {
+                    Ok(_) => println!(
+                        "=> Renamed {} to {}",
+                        change.path.display(),
+                        change.renamed_to.display()
+                    ),
+                    Err(e) => println!("{:#?}", e),
+                }
+            }
+            self.modified += 1;
+        }
+    }
+
+    pub fn display_stats(self) {
+        println!("Total {} files", self.total);
+        println!("Modified {} files", self.modified);
+        println!("Ingored {} files", self.ignored);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_rename_component() {
+        let config = Config {
+            typescript: Some(true),
+            ..Config::default()
+        };
+        let rename = ComponentRename {
+            old: String::from("Button"),
+            new: String::from("ActionButton"),
+            sources: vec![
+                PathBuf::from("../../tests/rename_component/Button.tsx"),
+                PathBuf::from("../../tests/rename_component/index.ts"),
+            ],
+            rename_file: true,
+        };
+
+        let path = PathBuf::from("../../tests/rename_component/Button.tsx");
+        let (module, cm, _comments) = parse_module(&path, &config);
+        assert_eq!(
+            rename.rename_declaring_module(&module, &cm).unwrap(),
+            r#"import type { ReactNode } from "react";
+
+export interface ActionButtonProps {
+  children: ReactNode;
+  onClick?: () => void;
+}
+
+/** Primary action of a form */
+export const ActionButton = ({ children, onClick }: ActionButtonProps) => (
+  <button onClick={onClick}>{children}</button>
+);
+
+ActionButton.displayName = "ActionButton";
+
+export const IconButton = (props: ActionButtonProps) => <ActionButton {...props}>★</ActionButton>;
+"#
+        );
+
+        let path = PathBuf::from("../../tests/rename_component/Button.stories.tsx");
+        let (module, cm, _comments) = parse_module(&path, &config);
+        assert_eq!(
+            rename
+                .rename_importing_module(&module, &cm, &path, true)
+                .unwrap(),
+            r#"import type { Meta, StoryObj } from "@storybook/react";
+import { ActionButton } from "./ActionButton";
+
+const meta: Meta<typeof ActionButton> = {
+  title: "Components/ActionButton",
+  component: ActionButton,
+};
+
+export default meta;
+type Story = StoryObj<typeof ActionButton>;
+
+export const ActionButton_Primary: Story = {
+  render: (args) => <ActionButton {...args} />,
+};
+"#
+        );
+
+        let path = PathBuf::from("../../tests/rename_component/Toolbar.tsx");
+        let (module, cm, _comments) = parse_module(&path, &config);
+        assert_eq!(
+            rename
+                .rename_importing_module(&module, &cm, &path, false)
+                .unwrap(),
+            r#"import { ActionButton, IconButton, type ActionButtonProps } from "./ActionButton";
+import { Button as LegacyButton } from "../legacy";
+
+export const Toolbar = (props: ActionButtonProps) => (
+  <div>
+    <ActionButton {...props} />
+    <IconButton {...props} />
+    <LegacyButton>Back</LegacyButton>
+  </div>
+);
+"#
+        );
+
+        let path = PathBuf::from("../../tests/rename_component/Page.tsx");
+        let (module, cm, _comments) = parse_module(&path, &config);
+        let output = rename
+            .rename_importing_module(&module, &cm, &path, false)
+            .unwrap();
+        assert!(output.starts_with("import { ActionButton as Action, Toolbar } from \".\";\n"));
+
+        let path = PathBuf::from("../../tests/rename_component/index.ts");
+        let (module, cm, _comments) = parse_module(&path, &config);
+        assert!(rename.re_exports(&module, &path));
+        assert_eq!(
+            rename
+                .rename_importing_module(&module, &cm, &path, false)
+                .unwrap(),
+            "export * from \"./ActionButton\";\nexport { Toolbar } from \"./Toolbar\";\n"
+        );
+    }
+}
@@ -0,0 +1,235 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use remod_config::Config;
+use swc_atoms::Atom;
+
+use crate::{
+    barrels::{barrel_entry, barrel_source},
+    declaration_style::ComponentStyle,
+    display_name::DisplayName,
+    export_style::ExportStyle,
+    storybook::Storybook,
+    testing_library::TestingLibrary,
+    utils::parse_module,
+};
+
+/// Source of a new component following the configured declaration style, export style and
+/// display name prefix
+pub fn component_source(component: &str, config: &Config) -> String {
+    let typescript = config.typescript.unwrap_or(false);
+    let style = config
+        .component_style
+        .as_deref()
+        .and_then(ComponentStyle::from_name)
+        .unwrap_or(ComponentStyle::Arrow);
+    let export_style = config
+        .export_style
+        .as_deref()
+        .and_then(ExportStyle::from_name)
+        .unwrap_or(ExportStyle::Named);
+    let export = match export_style {
+        ExportStyle::Named => "export ",
+        ExportStyle::Default => "",
+    };
+    let props = format!("{}Props", component);
+
+    let mut parts = vec![];
+    if typescript {
+        parts.push(String::from("import type { ReactNode } from \"react\";"));
+        parts.push(format!(
+            "export interface {} {{\n  children?: ReactNode;\n}}",
+            props
+        ));
+    }
+    let params = if typescript {
+        format!("{{ children }}: {}", props)
+    } else {
+        String::from("{ children }")
+    };
+    parts.push(match style {
+        ComponentStyle::Arrow => format!(
+            "{}const {} = ({}) => <div>{{children}}</div>;",
+            export, component, params
+        ),
+        ComponentStyle::Function => format!(
+            "{}function {}({}) {{\n  return <div>{{children}}</div>;\n}}",
+            export, component, params
+        ),
+    });
+    let mut display_name = DisplayName {
+        prefix: config.display_name_prefix.to_owned().unwrap_or_default(),
+        ..Default::default()
+    };
+    parts.push(format!(
+        "{};",
+        display_name.create_stmts(&Atom::from(component))
+    ));
+    if export_style == ExportStyle::Default {
+        parts.push(format!("export default {};", component));
+    }
+    format!("{}\n", parts.join("\n\n"))
+}
+
+#[derive(Debug, Default)]
+pub struct NewComponent {
+    /// Path of the component under `root_dir`, `Forms/Input`
+    pub path: String,
+    /// Count of created files
+    pub created: i64,
+    /// Count of modified files
+    pub modified: i64,
+}
+
+impl NewComponent {
+    pub fn create_component(&mut self, config: &Config) {
+        let path = self.path.trim_matches('/').to_owned();
+        let component = path.rsplit('/').next().unwrap_or(&path);
+        if !component.starts_with(|c: char| c.is_ascii_uppercase())
+            || !component.chars().all(|c| c.is_ascii_alphanumeric())
+        {
+            println!(
+                "=> Cannot create '{}', component names are PascalCase identifiers",
+                component
+            );
+            return;
+        }
+        let directory = Path::new(&config.root_dir).join(&path);
+        if directory.exists() {
+            println!(
+                "=> Cannot create {}, it already exists",
+                directory.display()
+            );
+            return;
+        }
+        if let Err(e) = fs::create_dir_all(&directory) {
+            println!("{:#?}", e);
+            return;
+        }
+
+        let typescript = config.typescript.unwrap_or(false);
+        let file = directory.join(format!(
+            "{}.{}",
+            component,
+            if typescript { "tsx" } else { "jsx" }
+        ));
+        match fs::write(&file, component_source(component, config)) {
+            Ok(_) => {
+                println!("=> Created {}", file.display());
+                self.created += 1;
+            }
+            Err(e) => {
+                println!("{:#?}", e);
+                return;
+            }
+        }
+
+        let mut storybook = Storybook {
+            docs: config.story_docs.unwrap_or(false),
+            ..Default::default()
+        };
+        storybook.emit_story_file(&file, config);
+        let mut testing_library = TestingLibrary::default();
+        testing_library.emit_test_file(&file, config);
+        self.created += storybook.created + testing_library.created;
+
+        let (module, _cm, _comments) = parse_module(&file, config);
+        if let Some(entry) = barrel_entry(&module, &file) {
+            let barrel = directory.join(if typescript { "index.ts" } else { "index.js" });
+            match fs::write(&barrel, barrel_source(&[entry], None, false)) {
+                Ok(_) => {
+                    println!("=> Created {}", barrel.display());
+                    self.created += 1;
+                }
+                Err(e) => println!("{:#?}", e),
+            }
+        }
+        self.add_to_parent_barrel(&directory, component);
+    }
+
+    /// Re-exports the component folder from the barrel of the directory containing it
+    fn add_to_parent_barrel(&mut self, directory: &Path, component: &str) {
+        let parent_barrel = directory.parent().and_then(|parent| {
+            ["ts", "tsx", "js", "jsx"]
+                .iter()
+                .map(|ext| parent.join(format!("index.{}", ext)))
+                .find(|path| path.is_file())
+        });
+        let parent_barrel: PathBuf = match parent_barrel {
+            Some(path) => path,
+            None => return,
+        };
+        let contents = match fs::read_to_string(&parent_barrel) {
+            Ok(contents) => contents,
+            Err(e) => {
+                println!("{:#?}", e);
+                return;
+            }
+        };
+        let export = format!("export * from \"./{}\";", component);
+        if contents.lines().any(|line| line.trim() == export) {
+            return;
+        }
+        let contents = format!("{}\n{}\n", contents.trim_end(), export)
+            .trim_start()
+            .to_owned();
+        match fs::write(&parent_barrel, contents) {
+            Ok(_) => {
+                println!("{}", parent_barrel.display());
+                self.modified += 1;
+            }
+            Err(e) => println!("{:#?}", e),
+        }
+    }
+
+    pub fn display_stats(self) {
+        println!("Created {} files", self.created);
+        println!("Modified {} files", self.modified);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_component_source() {
+        let config = Config {
+            typescript: Some(true),
+            display_name_prefix: Some(String::from("app")),
+            ..Config::default()
+        };
+        assert_eq!(
+            component_source("Input", &config),
+            r#"import type { ReactNode } from "react";
+
+export interface InputProps {
+  children?: ReactNode;
+}
+
+export const Input = ({ children }: InputProps) => <div>{children}</div>;
+
+Input.displayName = "app_Input";
+"#
+        );
+
+        let config = Config {
+            component_style: Some(String::from("function")),
+            export_style: Some(String::from("default")),
+            ..Config::default()
+        };
+        assert_eq!(
+            component_source("Input", &config),
+            r#"function Input({ children }) {
+  return <div>{children}</div>;
+}
+
+Input.displayName = "_Input";
+
+export default Input;
+"#
+        );
+    }
+}
@@ -0,0 +1,891 @@
+use std::{
+    collections::HashMap,
+    fs,
+    path::{Component, Path, PathBuf},
+};
+
+use glob::Paths;
+use remod_config::Config;
+use swc_common::{comments::SingleThreadedComments, SourceMap, Span, Spanned};
+use swc_ecma_ast::{
+    Decl, DefaultDecl, ExportSpecifier, Expr, ImportSpecifier, JSXElement, JSXFragment, Lit,
+    Module, ModuleDecl, ModuleExportName, ModuleItem, Pat, PatOrExpr, Stmt,
+};
+use swc_ecma_visit::{Visit, VisitWith};
+
+use crate::{
+    display_name::module_components,
+    utils::{
+        apply_edits, exported_names, import_text, imported_name, line_start_with_comments,
+        parse_module, should_ignore_entry, snippet, IdentReferences, SourceEdit,
+    },
+};
+
+pub(crate) const SCRIPT_EXTENSIONS: [&str; 5] = ["js", "jsx", "ts", "tsx", "mjs"];
+
+/// Top level bindings declared by the module item
+fn declared_names(item: &ModuleItem) -> Vec<String> {
+    let decl = match item {
+        ModuleItem::Stmt(Stmt::Decl(decl)) => decl,
+        ModuleItem::ModuleDecl(ModuleDecl::ExportDecl(export)) => &export.decl,
+        ModuleItem::ModuleDecl(ModuleDecl::ExportDefaultDecl(export)) => {
+            return match export.decl {
+                DefaultDecl::Fn(ref func) => {
+                    func.ident.iter().map(|id| id.sym.to_string()).collect()
+                }
+                DefaultDecl::Class(ref class) => {
+                    class.ident.iter().map(|id| id.sym.to_string()).collect()
+                }
+                _ => vec![],
+            }
+        }
+        _ => return vec![],
+    };
+    match decl {
+        Decl::Fn(func) => vec![func.ident.sym.to_string()],
+        Decl::Class(class) => vec![class.ident.sym.to_string()],
+        Decl::Var(var) => var
+            .decls
+            .iter()
+            .filter_map(|d| d.name.as_ident().map(|id| id.sym.to_string()))
+            .collect(),
+        Decl::TsInterface(interface) => vec![interface.id.sym.to_string()],
+        Decl::TsTypeAlias(alias) => vec![alias.id.sym.to_string()],
+        Decl::TsEnum(ts_enum) => vec![ts_enum.id.sym.to_string()],
+        _ => vec![],
+    }
+}
+
+/// Component of `Component.displayName = "..."` and other static assignments
+fn assigned_component(item: &ModuleItem) -> Option<String> {
+    let assign = item.as_stmt()?.as_expr()?.expr.as_assign()?;
+    let member = match assign.left {
+        PatOrExpr::Expr(ref expr) => expr.as_member(),
+        PatOrExpr::Pat(ref pat) => match **pat {
+            Pat::Expr(ref expr) => expr.as_member(),
+            _ => None,
+        },
+    }?;
+    member.obj.as_ident().map(|id| id.sym.to_string())
+}
+
+/// `"use client"` and other directives opening the module
+fn is_directive(item: &ModuleItem) -> bool {
+    matches!(
+        item.as_stmt().and_then(|s| s.as_expr()).map(|e| &*e.expr),
+        Some(Expr::Lit(Lit::Str(..)))
+    )
+}
+
+/// Names exported as they are by a local export list, `export { Card, CardHeader }`
+fn listed_exports(item: &ModuleItem) -> Vec<String> {
+    let export = match item {
+        ModuleItem::ModuleDecl(ModuleDecl::ExportNamed(export)) if export.src.is_none() => export,
+        _ => return vec![],
+    };
+    export
+        .specifiers
+        .iter()
+        .filter_map(|specifier| match specifier {
+            ExportSpecifier::Named(named) if named.exported.is_none() => match named.orig {
+                ModuleExportName::Ident(ref id) => Some(id.sym.to_string()),
+                ModuleExportName::Str(..) => None,
+            },
+            _ => None,
+        })
+        .collect()
+}
+
+fn references(item: &ModuleItem, name: &str) -> bool {
+    let mut references = IdentReferences::new(vec![name.to_owned()]);
+    item.visit_with(&mut references);
+    !references.spans.is_empty()
+}
+
+#[derive(Default)]
+struct HasJsx {
+    pub found: bool,
+}
+
+impl Visit for HasJsx {
+    fn visit_jsx_element(&mut self, _: &JSXElement) {
+        self.found = true;
+    }
+    fn visit_jsx_fragment(&mut self, _: &JSXFragment) {
+        self.found = true;
+    }
+}
+
+/// Source of the item along with its leading comments
+fn item_text(cm: &SourceMap, comments: &SingleThreadedComments, item: &ModuleItem) -> String {
+    let start = line_start_with_comments(cm, comments, item.span_lo());
+    snippet(cm, Span::new(start, item.span_hi(), Default::default()))
+}
+
+/// Module split into files
+#[derive(Debug, Default)]
+pub struct SplitModule {
+    /// Source of the module the components were moved out of
+    pub source: String,
+    /// Path and source of the files created for the components
+    pub files: Vec<(PathBuf, String)>,
+    /// Exported names moved out of the module with the file they moved to
+    pub moved: Vec<(String, PathBuf)>,
+}
+
+/// Moves the exported components of the module other than its primary component into files of
+/// their own, along with their display names, types and the declarations only they use.
+/// The primary component is the one named after the file, else the default export, else the first.
+/// Returns `None` when the module exports a single component
+pub fn split_module(
+    module: &Module,
+    cm: &SourceMap,
+    comments: &SingleThreadedComments,
+    path: &Path,
+    re_export: bool,
+) -> Option<SplitModule> {
+    let exported = exported_names(module);
+    let components = module_components(module);
+    let items = module
+        .body
+        .iter()
+        .filter(|item| !matches!(item, ModuleItem::ModuleDecl(ModuleDecl::Import(..))))
+        .collect::<Vec<&ModuleItem>>();
+    let names = items
+        .iter()
+        .map(|item| declared_names(item))
+        .collect::<Vec<Vec<String>>>();
+    // Exported components in the order they are declared
+    let split = names
+        .iter()
+        .flatten()
+        .filter(|name| components.contains(name) && exported.contains(name))
+        .cloned()
+        .collect::<Vec<String>>();
+    if split.len() < 2 {
+        return None;
+    }
+    let stem = path.file_stem()?.to_string_lossy().to_string();
+    let default_export = module.body.iter().find_map(|item| match item {
+        ModuleItem::ModuleDecl(ModuleDecl::ExportDefaultExpr(export)) => {
+            export.expr.as_ident().map(|id| id.sym.to_string())
+        }
+        ModuleItem::ModuleDecl(ModuleDecl::ExportDefaultDecl(export)) => match export.decl {
+            DefaultDecl::Fn(ref func) => func.ident.as_ref().map(|id| id.sym.to_string()),
+            _ => None,
+        },
+        _ => None,
+    });
+    let primary = split
+        .iter()
+        .find(|name| **name == stem)
+        .or_else(|| {
+            split
+                .iter()
+                .find(|name| Some(*name) == default_export.as_ref())
+        })
+        .unwrap_or(&split[0])
+        .to_owned();
+    let extension = path
+        .extension()
+        .map_or(String::from("tsx"), |e| e.to_string_lossy().to_string());
+    let directives = items
+        .iter()
+        .take_while(|item| is_directive(item))
+        .map(|item| snippet(cm, item.span()))
+        .collect::<Vec<String>>();
+
+    let listed = items
+        .iter()
+        .flat_map(|item| listed_exports(item))
+        .collect::<Vec<String>>();
+
+    let mut moved_items: Vec<usize> = vec![];
+    let mut result = SplitModule::default();
+    // Moved components by the index of the items they moved with
+    let mut moved_components: Vec<(String, Vec<usize>)> = vec![];
+    for component in split.iter().filter(|name| **name != primary) {
+        let file = path.with_file_name(format!("{}.{}", component, extension));
+        if file.exists() {
+            println!(
+                "=> Skipping '{}', {} already exists",
+                component,
+                file.display()
+            );
+            continue;
+        }
+        let mut set = items
+            .iter()
+            .enumerate()
+            .filter(|(i, item)| {
+                names[*i].contains(component)
+                    || assigned_component(item).as_ref() == Some(component)
+            })
+            .map(|(i, _)| i)
+            .collect::<Vec<usize>>();
+        // Declarations the component uses, moved along when nothing else uses them
+        let mut shared = None;
+        let mut imported_components = vec![];
+        let mut index = 0;
+        while index < set.len() && shared.is_none() {
+            let item = items[set[index]];
+            for (j, declared) in names.iter().enumerate() {
+                if set.contains(&j) || declared.is_empty() {
+                    continue;
+                }
+                let used = declared.iter().find(|name| references(item, name));
+                let used = match used {
+                    Some(used) => used,
+                    None => continue,
+                };
+                if moved_items.contains(&j) {
+                    if !imported_components.contains(used) {
+                        imported_components.push(used.to_owned());
+                    }
+                    continue;
+                }
+                if components.contains(used) {
+                    shared = Some(used.to_owned());
+                    break;
+                }
+                set.push(j);
+            }
+            index += 1;
+        }
+        let users_outside = set.iter().find_map(|i| {
+            names[*i].iter().find(|name| {
+                *name != component
+                    && items.iter().enumerate().any(|(j, item)| {
+                        !set.contains(&j)
+                            && !moved_items.contains(&j)
+                            && !names[j].contains(name)
+                            && !listed_exports(item).contains(name)
+                            && references(item, name)
+                    })
+            })
+        });
+        if let Some(name) = shared.as_ref().or(users_outside) {
+            println!(
+                "=> Skipping '{}', it shares '{}' with the rest of the module",
+                component, name
+            );
+            continue;
+        }
+        set.sort();
+
+        // Imports of the original module the moved declarations use
+        let mut has_jsx = HasJsx::default();
+        for i in set.iter() {
+            items[*i].visit_with(&mut has_jsx);
+        }
+        let mut imports = vec![];
+        for item in module.body.iter() {
+            let decl = match item {
+                ModuleItem::ModuleDecl(ModuleDecl::Import(decl)) => decl,
+                _ => continue,
+            };
+            let keep = decl
+                .specifiers
+                .iter()
+                .enumerate()
+                .filter(|(_, specifier)| {
+                    let local = specifier_local(specifier);
+                    // The classic JSX transform needs React in scope
+                    (has_jsx.found
+                        && &*decl.src.value == "react"
+                        && !matches!(specifier, ImportSpecifier::Named(..)))
+                        || set.iter().any(|i| references(items[*i], &local))
+                })
+                .map(|(i, _)| i)
+                .collect::<Vec<usize>>();
+            if !keep.is_empty() {
+                imports.push(import_text(cm, decl, &keep));
+            }
+        }
+        for name in imported_components.iter() {
+            if let Some((_, file)) = result.moved.iter().find(|(moved, _)| moved == name) {
+                imports.push(format!(
+                    "import {{ {} }} from \"./{}\";",
+                    name,
+                    file.file_stem()?.to_string_lossy()
+                ));
+            }
+        }
+        let mut source = String::new();
+        if !directives.is_empty() {
+            source.push_str(&format!("{}\n\n", directives.join("\n")));
+        }
+        if !imports.is_empty() {
+            source.push_str(&format!("{}\n\n", imports.join("\n")));
+        }
+        source.push_str(
+            &set.iter()
+                .map(|i| {
+                    let text = item_text(cm, comments, items[*i]);
+                    // Declarations exported by an export list of the module export themselves
+                    if matches!(items[*i], ModuleItem::Stmt(Stmt::Decl(..)))
+                        && names[*i].iter().any(|name| listed.contains(name))
+                    {
+                        let declaration = snippet(cm, items[*i].span());
+                        format!(
+                            "{}export {}",
+                            &text[..text.len() - declaration.len()],
+                            declaration
+                        )
+                    } else {
+                        text
+                    }
+                })
+                .collect::<Vec<String>>()
+                .join("\n\n"),
+        );
+        source.push('\n');
+        result.files.push((file.to_owned(), source));
+        for i in set.iter() {
+            let exported_item = matches!(
+                items[*i],
+                ModuleItem::ModuleDecl(
+                    ModuleDecl::ExportDecl(..) | ModuleDecl::ExportDefaultDecl(..)
+                )
+            );
+            for name in names[*i]
+                .iter()
+                .filter(|name| exported_item || exported.contains(name))
+            {
+                result.moved.push((name.to_owned(), file.to_owned()));
+            }
+        }
+        moved_items.extend(set.iter());
+        moved_components.push((component.to_owned(), set));
+    }
+    if moved_items.is_empty() {
+        return None;
+    }
+
+    let mut edits = vec![];
+    for i in moved_items.iter() {
+        let start = line_start_with_comments(cm, comments, items[*i].span_lo());
+        edits.push(SourceEdit::remove_lines(
+            cm,
+            Span::new(start, items[*i].span_hi(), Default::default()),
+        ));
+    }
+    let remaining = items
+        .iter()
+        .enumerate()
+        .filter(|(i, _)| !moved_items.contains(i))
+        .map(|(_, item)| *item)
+        .collect::<Vec<&ModuleItem>>();
+    // The moved names leave the export lists, their files export them
+    let moved_names = moved_items
+        .iter()
+        .flat_map(|i| names[*i].iter())
+        .collect::<Vec<&String>>();
+    for item in remaining.iter() {
+        let export = match item {
+            ModuleItem::ModuleDecl(ModuleDecl::ExportNamed(export)) if export.src.is_none() => {
+                export
+            }
+            _ => continue,
+        };
+        let dropped = listed_exports(item)
+            .into_iter()
+            .filter(|name| moved_names.contains(&name))
+            .collect::<Vec<String>>();
+        if dropped.is_empty() {
+            continue;
+        }
+        let kept = export
+            .specifiers
+            .iter()
+            .filter(|specifier| match specifier {
+                ExportSpecifier::Named(named) if named.exported.is_none() => match named.orig {
+                    ModuleExportName::Ident(ref id) => !dropped.contains(&id.sym.to_string()),
+                    ModuleExportName::Str(..) => true,
+                },
+                _ => true,
+            })
+            .map(|specifier| snippet(cm, specifier.span()))
+            .collect::<Vec<String>>();
+        edits.push(if kept.is_empty() {
+            SourceEdit::remove_lines(cm, export.span)
+        } else {
+            SourceEdit::replace(
+                export.span,
+                format!(
+                    "export {}{{ {} }};",
+                    if export.type_only { "type " } else { "" },
+                    kept.join(", ")
+                ),
+            )
+        });
+    }
+    let mut kept_import = None;
+    let mut first_import = None;
+    for item in module.body.iter() {
+        let decl = match item {
+            ModuleItem::ModuleDecl(ModuleDecl::Import(decl)) => decl,
+            _ => continue,
+        };
+        first_import.get_or_insert(decl.span);
+        let keep = decl
+            .specifiers
+            .iter()
+            .enumerate()
+            .filter(|(_, specifier)| {
+                (&*decl.src.value == "react" && !matches!(specifier, ImportSpecifier::Named(..)))
+                    || remaining
+                        .iter()
+                        .any(|item| references(item, &specifier_local(specifier)))
+            })
+            .map(|(i, _)| i)
+            .collect::<Vec<usize>>();
+        if keep.len() == decl.specifiers.len() {
+            kept_import = Some(decl.span);
+        } else if keep.is_empty() {
+            edits.push(SourceEdit::remove_lines(cm, decl.span));
+        } else {
+            edits.push(SourceEdit::replace(decl.span, import_text(cm, decl, &keep)));
+            kept_import = Some(decl.span);
+        }
+    }
+    let mut imports = vec![];
+    for (component, set) in moved_components.iter() {
+        let used = set.iter().flat_map(|i| names[*i].iter()).filter(|name| {
+            remaining
+                .iter()
+                .any(|item| references(item, name) && !listed_exports(item).contains(name))
+        });
+        let used = used.cloned().collect::<Vec<String>>();
+        if !used.is_empty() {
+            imports.push(format!(
+                "import {{ {} }} from \"./{}\";",
+                used.join(", "),
+                component
+            ));
+        }
+        if re_export {
+            let exported = result
+                .moved
+                .iter()
+                .filter(|(_, file)| file.file_stem().is_some_and(|stem| stem == &**component))
+                .map(|(name, _)| name.to_owned())
+                .collect::<Vec<String>>();
+            imports.push(format!(
+                "export {{ {} }} from \"./{}\";",
+                exported.join(", "),
+                component
+            ));
+        }
+    }
+    if !imports.is_empty() {
+        let imports = imports.join("\n");
+        edits.push(match (kept_import, first_import) {
+            (Some(span), _) => SourceEdit::insert(span.hi, format!("\n{}", imports)),
+            (None, Some(span)) => SourceEdit::insert(span.lo, format!("{}\n", imports)),
+            (None, None) => {
+                let first = remaining
+                    .iter()
+                    .find(|item| !is_directive(item))
+                    .map_or(module.span.lo, |item| {
+                        line_start_with_comments(cm, comments, item.span_lo())
+                    });
+                SourceEdit::insert(first, format!("{}\n\n", imports))
+            }
+        });
+    }
+    result.source = format!("{}\n", apply_edits(cm, edits)?.trim_end());
+    Some(result)
+}
+
+fn specifier_local(specifier: &ImportSpecifier) -> String {
+    match specifier {
+        ImportSpecifier::Named(named) => named.local.sym.to_string(),
+        ImportSpecifier::Default(default) => default.local.sym.to_string(),
+        ImportSpecifier::Namespace(namespace) => namespace.local.sym.to_string(),
+    }
+}
+
+/// Lexically resolves `.` and `..` of the path
+pub(crate) fn normalize(path: &Path) -> PathBuf {
+    let mut normalized = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                if matches!(
+                    normalized.components().next_back(),
+                    Some(Component::Normal(..))
+                ) {
+                    normalized.pop();
+                } else {
+                    normalized.push("..");
+                }
+            }
+            component => normalized.push(component),
+        }
+    }
+    normalized
+}
+
+/// Whether the relative import source of the importer refers to the module. `Some(true)` when it
+/// refers to the directory of an `index` module
+pub(crate) fn resolves_to(importer: &Path, source: &str, module: &Path) -> Option<bool> {
+    if !source.starts_with('.') {
+        return None;
+    }
+    let joined = normalize(&importer.parent()?.join(source));
+    let module = normalize(module);
+    let has_extension = joined
+        .extension()
+        .is_some_and(|e| SCRIPT_EXTENSIONS.contains(&&*e.to_string_lossy()));
+    let joined_stem = if has_extension {
+        joined.with_extension("")
+    } else {
+        joined.to_owned()
+    };
+    if joined_stem == module.with_extension("") {
+        return Some(false);
+    }
+    if module.file_stem().is_some_and(|stem| stem == "index")
+        && Some(joined.as_path()) == module.parent()
+    {
+        return Some(true);
+    }
+    None
+}
+
+/// Import source of the moved file, next to the module the source refers to
+fn moved_source(source: &str, via_index: bool, file: &Path) -> String {
+    let stem = file
+        .file_stem()
+        .map_or(String::new(), |s| s.to_string_lossy().to_string());
+    if via_index {
+        return format!("{}/{}", source.trim_end_matches('/'), stem);
+    }
+    match source.rsplit_once('/') {
+        Some((dir, _)) => format!("{}/{}", dir, stem),
+        None => format!("./{}", stem),
+    }
+}
+
+/// Points the imports and re-exports of the moved names to the files they moved to.
+/// Returns `None` when the module imports none of them
+pub fn update_importers_module(
+    module: &Module,
+    cm: &SourceMap,
+    path: &Path,
+    splits: &[(PathBuf, Vec<(String, PathBuf)>)],
+) -> Option<String> {
+    let mut edits = vec![];
+    for item in module.body.iter() {
+        let (src, span) = match item {
+            ModuleItem::ModuleDecl(ModuleDecl::Import(decl)) => (&decl.src, decl.span),
+            ModuleItem::ModuleDecl(ModuleDecl::ExportNamed(export)) => match export.src {
+                Some(ref src) => (src, export.span),
+                None => continue,
+            },
+            ModuleItem::ModuleDecl(ModuleDecl::ExportAll(export)) => (&export.src, export.span),
+            _ => continue,
+        };
+        let (via_index, moved) = match splits
+            .iter()
+            .find_map(|(original, moved)| Some((resolves_to(path, &src.value, original)?, moved)))
+        {
+            Some(found) => found,
+            None => continue,
+        };
+        let quote = snippet(cm, src.span).chars().next().unwrap_or('"');
+        // Moved specifiers grouped by the file they moved to
+        let mut moved_specifiers: HashMap<PathBuf, Vec<String>> = HashMap::new();
+        let mut files = vec![];
+        let mut push = |file: &PathBuf, specifier: String| {
+            if !files.contains(file) {
+                files.push(file.to_owned());
+            }
+            moved_specifiers
+                .entry(file.to_owned())
+                .or_default()
+                .push(specifier);
+        };
+        let moved_file = |name: &str| moved.iter().find(|(n, _)| n == name).map(|(_, f)| f);
+        match item {
+            ModuleItem::ModuleDecl(ModuleDecl::Import(decl)) => {
+                let mut keep = vec![];
+                for (i, specifier) in decl.specifiers.iter().enumerate() {
+                    match imported_name(specifier).and_then(|name| moved_file(&name)) {
+                        Some(file) => push(file, snippet(cm, specifier.span())),
+                        None => keep.push(i),
+                    }
+                }
+                if files.is_empty() {
+                    continue;
+                }
+                let imports = files
+                    .iter()
+                    .map(|file| {
+                        format!(
+                            "import {}{{ {} }} from {q}{}{q};",
+                            if decl.type_only { "type " } else { "" },
+                            moved_specifiers[file].join(", "),
+                            moved_source(&src.value, via_index, file),
+                            q = quote
+                        )
+                    })
+                    .collect::<Vec<String>>();
+                if keep.is_empty() {
+                    edits.push(SourceEdit::replace(span, imports.join("\n")));
+                } else {
+                    edits.push(SourceEdit::replace(
+                        span,
+                        format!("{}\n{}", import_text(cm, decl, &keep), imports.join("\n")),
+                    ));
+                }
+            }
+            ModuleItem::ModuleDecl(ModuleDecl::ExportNamed(export)) => {
+                let mut keep = vec![];
+                for specifier in export.specifiers.iter() {
+                    let orig = match specifier {
+                        ExportSpecifier::Named(named) => match named.orig {
+                            ModuleExportName::Ident(ref id) => Some(id.sym.to_string()),
+                            ModuleExportName::Str(ref s) => Some(s.value.to_string()),
+                        },
+                        _ => None,
+                    };
+                    match orig.and_then(|name| moved_file(&name)) {
+                        Some(file) => push(file, snippet(cm, specifier.span())),
+                        None => keep.push(snippet(cm, specifier.span())),
+                    }
+                }
+                if files.is_empty() {
+                    continue;
+                }
+                let mut exports = vec![];
+                if !keep.is_empty() {
+                    exports.push(format!(
+                        "export {}{{ {} }} from {};",
+                        if export.type_only { "type " } else { "" },
+                        keep.join(", "),
+                        snippet(cm, src.span)
+                    ));
+                }
+                for file in files.iter() {
+                    exports.push(format!(
+                        "export {}{{ {} }} from {q}{}{q};",
+                        if export.type_only { "type " } else { "" },
+                        moved_specifiers[file].join(", "),
+                        moved_source(&src.value, via_index, file),
+                        q = quote
+                    ));
+                }
+                edits.push(SourceEdit::replace(span, exports.join("\n")));
+            }
+            ModuleItem::ModuleDecl(ModuleDecl::ExportAll(..)) => {
+                let mut files = moved
+                    .iter()
+                    .map(|(_, file)| file)
+                    .collect::<Vec<&PathBuf>>();
+                files.dedup();
+                let exports = files
+                    .iter()
+                    .map(|file| {
+                        format!(
+                            "\nexport * from {q}{}{q};",
+                            moved_source(&src.value, via_index, file),
+                            q = quote
+                        )
+                    })
+                    .collect::<String>();
+                edits.push(SourceEdit::insert(span.hi, exports));
+            }
+            _ => {}
+        }
+    }
+    if edits.is_empty() {
+        return None;
+    }
+    apply_edits(cm, edits)
+}
+
+#[derive(Debug, Default)]
+pub struct Split {
+    /// Re-export the moved components from the module they were split from
+    pub re_export: bool,
+    ///  Count of modified files
+    pub modified: i64,
+    /// Count of created files
+    pub created: i64,
+    /// Count of ignored files
+    pub ignored: i64,
+    /// Count of total files
+    pub total: i64,
+}
+
+impl Split {
+    pub fn split_files(&mut self, files: Paths, config: &Config) {
+        let paths = files
+            .filter_map(|entry| match entry {
+                Ok(path) => Some(path),
+                Err(e) => {
+                    println!("{:?}", e);
+                    None
+                }
+            })
+            .collect::<Vec<PathBuf>>();
+        let mut splits = vec![];
+        for path in paths.iter() {
+            self.total += 1;
+            if should_ignore_entry(&config.ignore, path) {
+                println!("Skipping {}", path.display());
+                self.ignored += 1;
+                continue;
+            }
+            let (module, cm, comments) = parse_module(path, config);
+            let split = match split_module(&module, &cm, &comments, path, self.re_export) {
+                Some(split) => split,
+                None => continue,
+            };
+            for (file, source) in split.files.iter() {
+                match fs::write(file, source) {
+                    Ok(_) => {
+                        println!("=> Created {}", file.display());
+                        self.created += 1;
+                    }
+                    Err(e) => println!("{:#?}", e),
+                }
+            }
+            match fs::write(path, split.source) {
+                Ok(_) => {
+                    println!("{}", path.display());
+                    self.modified += 1;
+                }
+                Err(e) => println!("{:#?}", e),
+            }
+            splits.push((path.to_owned(), split.moved));
+        }
+        if splits.is_empty() {
+            return;
+        }
+
+        for path in paths.iter() {
+            if should_ignore_entry(&config.ignore, path)
+                || splits.iter().any(|(original, _)| original == path)
+            {
+                continue;
+            }
+            let (module, cm, _comments) = parse_module(path, config);
+            if let Some(output) = update_importers_module(&module, &cm, path, &splits) {
+                match fs::write(path, output) {
+                    Ok(_) => {
+                        println!("{}", path.display());
+                        self.modified += 1;
+                    }
+                    Err(e) => println!("{:#?}", e),
+                }
+            }
+        }
+    }
+
+    pub fn display_stats(self) {
+        println!("Total {} files", self.total);
+        println!("Modified {} files", self.modified);
+        println!("Created {} files", self.created);
+        println!("Ingored {} files", self.ignored);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_split() {
+        let config = Config {
+            typescript: Some(true),
+            ..Config::default()
+        };
+        let path = PathBuf::from("../../tests/split/Card.tsx");
+        let (module, cm, comments) = parse_module(&path, &config);
+        let split = split_module(&module, &cm, &comments, &path, false).unwrap();
+        assert!(split.source.starts_with(
+            r#""use client";
+
+import { useState, type ReactNode } from "react";
+import { CardHeader } from "./CardHeader";
+
+export interface CardProps {"#
+        ));
+        assert!(split
+            .source
+            .ends_with("};\n\nCard.displayName = \"Card\";\n"));
+        let (file, source) = &split.files[0];
+        assert_eq!(file, &PathBuf::from("../../tests/split/CardHeader.tsx"));
+        assert!(source.starts_with(
+            r#""use client";
+
+import { Icon } from "./Icon";
+
+const toggleLabel = (open: boolean) => (open ? "Collapse" : "Expand");
+
+export interface CardHeaderProps {"#
+        ));
+        assert!(source.contains("}\n\n/** Heading of a card */\nexport const CardHeader"));
+        assert!(source.ends_with(");\n\nCardHeader.displayName = \"CardHeader\";\n"));
+        let (file, source) = &split.files[1];
+        assert_eq!(file, &PathBuf::from("../../tests/split/CardFooter.tsx"));
+        assert!(source.contains("import { type ReactNode } from \"react\";\n\nexport function"));
+
+        let splits = vec![(path.to_owned(), split.moved)];
+        let importer = PathBuf::from("../../tests/split/Page.tsx");
+        let (module, cm, _comments) = parse_module(&importer, &config);
+        let output = update_importers_module(&module, &cm, &importer, &splits).unwrap();
+        assert!(output.starts_with(
+            r#"import { Card } from "./Card";
+import { CardFooter as Footer } from "./CardFooter";
+import { type CardHeaderProps } from "./CardHeader";
+"#
+        ));
+        let importer = PathBuf::from("../../tests/split/index.ts");
+        let (module, cm, _comments) = parse_module(&importer, &config);
+        let output = update_importers_module(&module, &cm, &importer, &splits).unwrap();
+        assert_eq!(
+            output,
+            r#"export * from "./Card";
+export * from "./CardHeader";
+export * from "./CardFooter";
+export { Card as default } from "./Card";
+"#
+        );
+
+        // Components exported by an export list export themselves from their files
+        let path = PathBuf::from("../../tests/split/Tabs.tsx");
+        let (module, cm, comments) = parse_module(&path, &config);
+        let split = split_module(&module, &cm, &comments, &path, false).unwrap();
+        assert_eq!(
+            split.source,
+            r#"import { type ReactNode } from "react";
+
+function Tabs({ children }: { children: ReactNode }) {
+  return <div role="tablist">{children}</div>;
+}
+
+export { Tabs };
+"#
+        );
+        assert_eq!(
+            split.files[0].1,
+            r#"/** A tab of the list */
+export const Tab = ({ label }: { label: string }) => <button role="tab">{label}</button>;
+
+Tab.displayName = "Tab";
+"#
+        );
+        assert_eq!(
+            split.moved,
+            vec![(
+                String::from("Tab"),
+                PathBuf::from("../../tests/split/Tab.tsx")
+            )]
+        );
+    }
+}
@@ -6,38 +6,126 @@ use std::{
 
 use crate::{
     display_name::{FunctionDelarationWalker, VariableDeclarationWalker},
+    export_style::default_export_name,
+    props::{extract_props, ComponentDocVisitor, PropDefinition},
+    testing_library::prop_value,
     utils::{parse_module, parse_raw_string_as_module, should_ignore_entry},
 };
 use glob::Paths;
 use remod_config::Config;
-use swc_common::Spanned;
-use swc_common::{chain, util::take::Take, Loc};
-use swc_ecma_ast::{BindingIdent, Ident, Program};
+use swc_common::comments::SingleThreadedComments;
+use swc_common::{chain, Loc};
+use swc_common::{SourceMap, Spanned};
+use swc_ecma_ast::{
+    BindingIdent, CallExpr, Callee, Expr, FnDecl, Ident, KeyValueProp, MemberProp, Module, Pat,
+    Program, PropName, VarDeclarator,
+};
 use swc_ecma_visit::{Visit, VisitWith};
 
+#[derive(Default)]
 pub struct StoryNameExpr {
-    pub expr: Ident,
+    pub expr: Option<Ident>,
     /// Stories to filter from
     _filter: String,
 }
 
-impl Default for StoryNameExpr {
-    fn default() -> Self {
-        Self {
-            expr: Ident::dummy(),
-            _filter: Default::default(),
+impl Visit for StoryNameExpr {
+    fn visit_binding_ident(&mut self, n: &BindingIdent) {
+        if &*n.id.sym == &self._filter {
+            self.expr = Some(n.id.clone());
         }
     }
+
+    // Cosmos fixtures are the keys of the default export, `{ Button_Primary: <Button /> }`
+    fn visit_key_value_prop(&mut self, n: &KeyValueProp) {
+        if let PropName::Ident(ref id) = n.key {
+            if *id.sym == self._filter {
+                self.expr = Some(id.clone());
+            }
+        }
+        n.visit_children_with(self);
+    }
 }
 
-impl Visit for StoryNameExpr {
-    fn visit_binding_ident(&mut self, n: &BindingIdent) {
-        if &*n.id.sym == &self._filter {
-            self.expr = n.id.clone();
+/// Collects the hooks and contexts used in the body of a component
+#[derive(Default)]
+pub struct HookUsageVisitor {
+    /// Component to look for
+    component: String,
+    /// Hooks (`useProps`) and contexts (`useContext(ThemeContext)`) used by the component
+    pub usages: Vec<String>,
+    in_component: bool,
+}
+
+impl HookUsageVisitor {
+    pub fn new(component: &str) -> Self {
+        HookUsageVisitor {
+            component: component.to_string(),
+            ..Default::default()
+        }
+    }
+    fn push_usage(&mut self, usage: String) {
+        if !self.usages.contains(&usage) {
+            self.usages.push(usage);
         }
     }
 }
 
+impl Visit for HookUsageVisitor {
+    fn visit_var_declarator(&mut self, n: &VarDeclarator) {
+        if let Pat::Ident(ref id) = n.name {
+            if id.sym.as_str() == self.component {
+                self.in_component = true;
+                n.visit_children_with(self);
+                self.in_component = false;
+                return;
+            }
+        }
+        n.visit_children_with(self);
+    }
+
+    fn visit_fn_decl(&mut self, n: &FnDecl) {
+        if n.ident.sym.as_str() == self.component {
+            self.in_component = true;
+            n.visit_children_with(self);
+            self.in_component = false;
+        } else {
+            n.visit_children_with(self);
+        }
+    }
+
+    fn visit_call_expr(&mut self, n: &CallExpr) {
+        if self.in_component {
+            if let Callee::Expr(ref callee) = n.callee {
+                let hook = match **callee {
+                    Expr::Ident(ref id) => Some(id.sym.to_string()),
+                    Expr::Member(ref mem) => match mem.prop {
+                        MemberProp::Ident(ref id) => Some(id.sym.to_string()),
+                        _ => None,
+                    },
+                    _ => None,
+                };
+                if let Some(hook) = hook.filter(|h| is_hook_name(h)) {
+                    if hook == "useContext" {
+                        if let Some(Expr::Ident(context)) = n.args.first().map(|a| &*a.expr) {
+                            self.push_usage(context.sym.to_string());
+                        }
+                    }
+                    self.push_usage(hook);
+                }
+            }
+        }
+        n.visit_children_with(self);
+    }
+}
+
+/// Hooks follow the `use` + capitalised name convention (`useState`, `useProps`)
+pub fn is_hook_name(name: &str) -> bool {
+    name.strip_prefix("use")
+        .and_then(|rest| rest.chars().next())
+        .is_some_and(|c| c.is_ascii_uppercase())
+}
+
 #[derive(Debug, Default)]
 struct Story {
     /// Name of the story
@@ -56,13 +144,18 @@ impl Story {
             value: Default::default(),
         }
     }
+    fn export_name(&self) -> String {
+        format!("{}_{}", self.name, "Primary")
+    }
     fn print_story(&mut self) {
         self.value = format!(
-            "export const {}_{}: {} = {{\n
+            "export const {}: {} = {{\n
                 render: (args)=><{} {{...args}} />
             \n}}
             ",
-            self.name, "Primary", self.type_annotation, self.name
+            self.export_name(),
+            self.type_annotation,
+            self.name
         );
     }
 }
@@ -74,6 +167,14 @@ pub struct StoryFile {
     import_default: String,
     /// The component import statement
     import_component: String,
+    /// Whether the component is the default export of its module
+    default_export: bool,
+    /// Import statements required by the decorators
+    import_decorators: String,
+    /// Decorators wrapping the stories with the providers the component needs
+    decorators: Vec<String>,
+    /// Attributes of the required props, for the targets rendering the component themselves
+    required_props: String,
     /// Meta declaration
     meta_decl: String,
     /// Story type declaration
@@ -88,6 +189,10 @@ impl Default for StoryFile {
             component: Default::default(),
             import_default: Default::default(),
             import_component: Default::default(),
+            default_export: Default::default(),
+            import_decorators: Default::default(),
+            decorators: Default::default(),
+            required_props: Default::default(),
             meta_decl: Default::default(),
             story_type: Default::default(),
             stories: Default::default(),
@@ -106,16 +211,51 @@ impl StoryFile {
         self.import_default = format!("import type {{ Meta, StoryObj }} from '@storybook/react';");
     }
     fn print_import_component(&mut self, file_name: &str) {
-        self.import_component = format!("import {{ {} }} from \'./{}\'", self.component, file_name);
+        self.import_component = if self.default_export {
+            format!("import {} from \'./{}\'", self.component, file_name)
+        } else {
+            format!("import {{ {} }} from \'./{}\'", self.component, file_name)
+        };
+    }
+    fn print_decorators(&mut self, usages: &[String], config: &Config) {
+        let mut imports: Vec<String> = vec![];
+        for usage in usages {
+            if let Some(decorator) = config.story_decorators.get(usage) {
+                if !self.decorators.contains(&decorator.decorator) {
+                    self.decorators.push(decorator.decorator.to_owned());
+                }
+                for import in decorator.imports.iter() {
+                    if !imports.contains(import) {
+                        imports.push(import.to_owned());
+                    }
+                }
+            }
+        }
+        self.import_decorators = imports.join("\n");
+    }
+    fn print_required_props(&mut self, props: &[PropDefinition], typescript: bool) {
+        self.required_props = props
+            .iter()
+            .filter(|p| p.required)
+            .map(|p| format!(" {}={}", p.name, prop_value(p, "() => {}", typescript)))
+            .collect();
     }
     fn print_meta_decl(&mut self) {
+        let decorators = if self.decorators.is_empty() {
+            String::new()
+        } else {
+            format!(
+                "\n                decorators: [{}],",
+                self.decorators.join(", ")
+            )
+        };
         self.meta_decl = format!(
             "const meta: Meta<typeof {}> = {{\n
-                component: {},
+                component: {},{}
             \n}};\n
             export default meta;
             ",
-            self.component, self.component
+            self.component, self.component, decorators
         );
     }
     fn print_story_type(&mut self) {
@@ -123,33 +263,300 @@ impl StoryFile {
     }
 
     pub fn emit_story_file(&self) -> String {
-        format!(
-            "{}\n{}\n{}\n{}\n{}",
-            self.import_default,
-            self.import_component,
-            self.meta_decl,
-            self.story_type,
-            self.stories
-                .iter()
-                .map(|s| s.value.to_owned())
-                .collect::<Vec<String>>()
-                .join("\n")
-        )
-    }
-
-    pub fn find_story_ident_loc(&self, config: &Config) -> (Loc, Loc) {
+        let stories = self
+            .stories
+            .iter()
+            .map(|s| s.value.to_owned())
+            .collect::<Vec<String>>()
+            .join("\n");
+        [
+            &self.import_default,
+            &self.import_component,
+            &self.import_decorators,
+            &self.meta_decl,
+            &self.story_type,
+            &stories,
+        ]
+        .iter()
+        .filter(|section| !section.is_empty())
+        .map(|section| section.as_str())
+        .collect::<Vec<&str>>()
+        .join("\n")
+    }
+
+    /// Start and end of the name of the primary story. `None` when the story file has none
+    pub fn find_story_ident_loc(&self, config: &Config) -> Option<(Loc, Loc)> {
         let (program, cm) = parse_raw_string_as_module(&self.emit_story_file(), config);
         let mut story_expr = StoryNameExpr {
             _filter: format!("{}_Primary", self.component.to_owned()),
             ..Default::default()
         };
         program.visit_with(&mut story_expr);
-        let start_loc = cm.lookup_char_pos(story_expr.expr.span_lo());
-        let end_loc = cm.lookup_char_pos(story_expr.expr.span_hi());
-        (start_loc, end_loc)
+        let expr = story_expr.expr?;
+        let start_loc = cm.lookup_char_pos(expr.span_lo());
+        let end_loc = cm.lookup_char_pos(expr.span_hi());
+        Some((start_loc, end_loc))
+    }
+}
+
+/// Files the language server creates for a component, with their paths
+pub struct StoryFiles {
+    /// The story file, `None` when the component has stories already
+    pub story: Option<(StoryFile, String)>,
+    /// Contents of the missing MDX docs page
+    pub docs: Option<(String, String)>,
+}
+
+/// A tool rendering the stories of the components
+pub trait StoryTarget {
+    /// Extension of the story files created when no `story_file_ext` of the target is configured
+    fn file_ext(&self) -> &'static str;
+    /// Whether a configured `story_file_ext` names the story files of the target
+    fn accepts_file_ext(&self, _ext: &str) -> bool {
+        true
+    }
+    /// Extension of the story files created for the config
+    fn story_file_ext(&self, config: &Config) -> String {
+        config
+            .story_file_ext
+            .as_deref()
+            .filter(|ext| self.accepts_file_ext(ext))
+            .unwrap_or(self.file_ext())
+            .to_owned()
+    }
+    /// Extensions of the files that already hold stories for a component
+    fn existing_file_exts(&self) -> &'static [&'static str];
+    /// Prints the sections of the story file for the component imported from `file_name`
+    fn print_story_file(&self, story_file: &mut StoryFile, file_name: &str);
+    /// Whether MDX docs pages can be emitted alongside the stories
+    fn supports_docs(&self) -> bool {
+        false
+    }
+}
+
+/// [Storybook](https://storybook.js.org/) Component Story Format
+pub struct StorybookTarget;
+
+impl StoryTarget for StorybookTarget {
+    fn file_ext(&self) -> &'static str {
+        ".stories.tsx"
+    }
+    fn existing_file_exts(&self) -> &'static [&'static str] {
+        &[".stories.tsx", ".story.tsx"]
+    }
+    fn print_story_file(&self, story_file: &mut StoryFile, file_name: &str) {
+        for story in story_file.stories.iter_mut() {
+            story.print_story();
+        }
+        story_file.print_import_default();
+        story_file.print_import_component(file_name);
+        story_file.print_meta_decl();
+        story_file.print_story_type();
+    }
+    fn supports_docs(&self) -> bool {
+        true
+    }
+}
+
+/// [Ladle](https://ladle.dev/) stories
+pub struct LadleTarget;
+
+impl StoryTarget for LadleTarget {
+    fn file_ext(&self) -> &'static str {
+        ".stories.tsx"
+    }
+    fn existing_file_exts(&self) -> &'static [&'static str] {
+        &[".stories.tsx", ".story.tsx"]
+    }
+    fn print_story_file(&self, story_file: &mut StoryFile, file_name: &str) {
+        let component = story_file.component.to_owned();
+        for story in story_file.stories.iter_mut() {
+            story.value = format!(
+                "export const {}: {}<React.ComponentProps<typeof {}>> = (args) => <{} {{...args}} />;",
+                story.export_name(),
+                story.type_annotation,
+                component,
+                component
+            );
+        }
+        story_file.import_default = String::from(
+            "import type * as React from 'react';\nimport type { Story } from '@ladle/react';",
+        );
+        story_file.print_import_component(file_name);
+        if !story_file.decorators.is_empty() {
+            story_file.meta_decl = format!(
+                "export default {{\n  decorators: [{}],\n}};",
+                story_file.decorators.join(", ")
+            );
+        }
+    }
+}
+
+/// [React Cosmos](https://reactcosmos.org/) fixtures
+pub struct CosmosTarget;
+
+impl StoryTarget for CosmosTarget {
+    fn file_ext(&self) -> &'static str {
+        ".fixture.tsx"
+    }
+    fn existing_file_exts(&self) -> &'static [&'static str] {
+        &[".fixture.tsx", ".fixtures.tsx"]
+    }
+    // The default `.stories.tsx` names Storybook stories, Cosmos only finds fixture files
+    fn accepts_file_ext(&self, ext: &str) -> bool {
+        ext.contains(".fixture")
+    }
+    fn print_story_file(&self, story_file: &mut StoryFile, file_name: &str) {
+        let component = story_file.component.to_owned();
+        // Cosmos has no per fixture decorators, so the decorators are applied to the element
+        let element = story_file.decorators.iter().fold(
+            format!("<{}{} />", component, story_file.required_props),
+            |element, decorator| format!("({})(() => {})", decorator, element),
+        );
+        let fixtures = story_file
+            .stories
+            .iter()
+            .map(|story| format!("  {}: {},", story.export_name(), element))
+            .collect::<Vec<String>>()
+            .join("\n");
+        // All the fixtures live in the default export
+        for story in story_file.stories.iter_mut() {
+            story.value = Default::default();
+        }
+        story_file.print_import_component(file_name);
+        story_file.meta_decl = format!("export default {{\n{}\n}};", fixtures);
+    }
+}
+
+/// The story target selected by `story_target` in the config. `Err` for an unknown target
+fn find_story_target(config: &Config) -> Result<Box<dyn StoryTarget>, String> {
+    match config.story_target.as_deref() {
+        None | Some("storybook") => Ok(Box::new(StorybookTarget)),
+        Some("ladle") => Ok(Box::new(LadleTarget)),
+        Some("cosmos") => Ok(Box::new(CosmosTarget)),
+        Some(target) => Err(format!("Unknown story target '{}'", target)),
+    }
+}
+
+/// The story target selected by `story_target` in the config. Defaults to Storybook
+pub fn story_target(config: &Config) -> Box<dyn StoryTarget> {
+    find_story_target(config).unwrap_or_else(|e| {
+        println!("{}. Falling back to storybook", e);
+        Box::new(StorybookTarget)
+    })
+}
+
+/// The MDX docs page of the component of the story file, importing the stories at the path
+fn docs_page(
+    story_file: &StoryFile,
+    story_path: &str,
+    module: &Module,
+    cm: &SourceMap,
+    comments: &SingleThreadedComments,
+) -> String {
+    let component = &story_file.component;
+    let mut docs_visitor = ComponentDocVisitor::new(component, comments);
+    module.visit_with(&mut docs_visitor);
+    let props = extract_props(component, module, cm, comments);
+    let mut docs_file = DocsFile::new(component.to_owned());
+    docs_file.print_import_blocks();
+    docs_file.print_import_stories(Path::new(story_path).file_stem().unwrap().to_str().unwrap());
+    docs_file.print_description(docs_visitor.description);
+    docs_file.print_props_table(&props);
+    docs_file.print_canvases(&story_file.stories);
+    docs_file.emit_docs_file()
+}
+
+#[derive(Default)]
+pub struct DocsFile {
+    /// component in Process
+    component: String,
+    /// The storybook doc blocks import statement
+    import_blocks: String,
+    /// The stories import statement
+    import_stories: String,
+    /// Meta reference to the stories
+    meta: String,
+    /// Title and description of the component
+    description: String,
+    /// Table of the props of the component
+    props_table: String,
+    /// Canvas blocks for the stories
+    canvases: Vec<String>,
+}
+
+impl DocsFile {
+    fn new(comp: String) -> Self {
+        DocsFile {
+            component: comp,
+            ..Default::default()
+        }
+    }
+    fn print_import_blocks(&mut self) {
+        self.import_blocks =
+            String::from("import { Meta, Canvas, Controls } from '@storybook/blocks';");
+    }
+    fn print_import_stories(&mut self, story_file_name: &str) {
+        self.import_stories = format!("import * as Stories from './{}';", story_file_name);
+        self.meta = String::from("<Meta of={Stories} />");
+    }
+    fn print_description(&mut self, description: Option<String>) {
+        self.description = match description {
+            Some(d) => format!("# {}\n\n{}", self.component, d),
+            None => format!("# {}", self.component),
+        };
+    }
+    fn print_props_table(&mut self, props: &[PropDefinition]) {
+        if props.is_empty() {
+            return;
+        }
+        let rows = props
+            .iter()
+            .map(|p| {
+                format!(
+                    "| {} | `{}` | {} | {} |",
+                    p.name,
+                    escape_table_cell(&p.type_text),
+                    if p.required { "Yes" } else { "No" },
+                    escape_table_cell(p.description.as_deref().unwrap_or_default())
+                )
+            })
+            .collect::<Vec<String>>()
+            .join("\n");
+        self.props_table = format!(
+            "## Props\n\n| Name | Type | Required | Description |\n| --- | --- | --- | --- |\n{}",
+            rows
+        );
+    }
+    fn print_canvases(&mut self, stories: &[Story]) {
+        self.canvases = stories
+            .iter()
+            .map(|s| format!("<Canvas of={{Stories.{}}} />", s.export_name()))
+            .collect();
+    }
+
+    pub fn emit_docs_file(&self) -> String {
+        let mut sections = vec![
+            format!("{}\n{}", self.import_blocks, self.import_stories),
+            self.meta.to_owned(),
+            self.description.to_owned(),
+        ];
+        if !self.props_table.is_empty() {
+            sections.push(self.props_table.to_owned());
+        }
+        sections.extend(self.canvases.iter().cloned());
+        sections.push(String::from("<Controls />"));
+        sections.join("\n\n") + "\n"
     }
 }
 
+fn escape_table_cell(text: &str) -> String {
+    text.split_whitespace()
+        .collect::<Vec<&str>>()
+        .join(" ")
+        .replace('|', "\\|")
+}
+
 #[derive(Debug, Default)]
 pub struct Storybook {
     ///  Count of modified files
@@ -158,6 +565,8 @@ pub struct Storybook {
     pub ignored: i64,
     /// Count of total files
     pub total: i64,
+    /// Emit a MDX docs page alongside the created stories
+    pub docs: bool,
 }
 
 impl Storybook {
@@ -165,12 +574,13 @@ impl Storybook {
         if should_ignore_entry(&config.ignore, path) {
             self.ignored += 1;
         } else {
-            let (module, _cm, _comments) = parse_module(path, config);
+            let (module, cm, comments) = parse_module(path, config);
             let mut variable_decl_visitor = VariableDeclarationWalker { variables: vec![] };
             let mut function_decl_visitor = FunctionDelarationWalker {
                 function_decls: vec![],
             };
             let mut visitor = chain!(&mut variable_decl_visitor, &mut function_decl_visitor);
+            let default_export = default_export_name(&module);
             let program = Program::Module(module);
             program.visit_with(&mut visitor);
             let mut story_name: Option<String> = Some(String::from(""));
@@ -186,27 +596,32 @@ impl Storybook {
                 }
             }
             if let Some(component) = story_name {
-                let mut stories: Vec<Story> = vec![];
-                let mut story = Story::new(component.clone());
-                story.print_story();
-                stories.push(story);
+                let target = story_target(config);
+                let stories: Vec<Story> = vec![Story::new(component.clone())];
                 let mut story_file = StoryFile::new(component.to_owned(), stories);
+                story_file.default_export = default_export.as_ref() == Some(&component);
                 let file_name = path.file_stem().unwrap().to_str().unwrap();
-                story_file.print_import_default();
-                story_file.print_import_component(file_name);
-                story_file.print_meta_decl();
-                story_file.print_story_type();
+                let mut hook_usage_visitor = HookUsageVisitor::new(&component);
+                program.visit_with(&mut hook_usage_visitor);
+                story_file.print_decorators(&hook_usage_visitor.usages, config);
+                if let Program::Module(ref module) = program {
+                    let props = extract_props(&component, module, &cm, &comments);
+                    story_file.print_required_props(&props, config.typescript.is_some());
+                }
+                target.print_story_file(&mut story_file, file_name);
                 let final_output = story_file.emit_story_file();
                 let may_be_dir = path.parent();
                 match may_be_dir {
                     Some(dir_path) => {
                         let directory = dir_path.display();
                         let final_path = format!("{}/{}", directory, file_name);
-                        let pattern_matches = vec![
-                            String::from(final_path.to_owned() + ".stories.tsx"),
-                            String::from(final_path.to_owned() + ".story.tsx"),
-                        ];
-                        if pattern_matches.iter().any(|p| {
+                        let pattern_matches = target
+                            .existing_file_exts()
+                            .iter()
+                            .map(|ext| format!("{}{}", final_path, ext))
+                            .collect::<Vec<String>>();
+                        let mut story_path: Option<String> = None;
+                        if let Some(existing) = pattern_matches.iter().find(|p| {
                             let exists_already = File::open(p);
                             match exists_already {
                                 Ok(_) => true,
@@ -215,11 +630,9 @@ impl Storybook {
                         }) {
                             println!("Story already exists for {}", path.display());
                             self.ignored += 0;
+                            story_path = Some(existing.to_owned());
                         } else {
-                            let ext = config
-                                .story_file_ext
-                                .to_owned()
-                                .unwrap_or(String::from(".stories.tsx"));
+                            let ext = target.story_file_ext(config);
                             let file_name = format!("{}{}", final_path, ext);
                             let new_path = Path::new(file_name.as_str());
                             let exists_already = File::open(new_path);
@@ -227,6 +640,7 @@ impl Storybook {
                                 Ok(_) => {
                                     println!("Story alredy exists for {}", path.display());
                                     self.ignored += 1;
+                                    story_path = Some(file_name.to_owned());
                                 }
                                 Err(_) => {
                                     let may_be_file =
@@ -240,6 +654,7 @@ impl Storybook {
                                             );
                                             let _ = file.write(final_output.as_bytes());
                                             self.created += 1;
+                                            story_path = Some(file_name.to_owned());
                                         }
                                         Err(e) => {
                                             println!("{:#?}", e);
@@ -248,6 +663,19 @@ impl Storybook {
                                 }
                             }
                         }
+                        // Docs are written for existing stories too, when their page is missing
+                        if let Some(story_path) = story_path {
+                            if self.docs && target.supports_docs() {
+                                self.emit_docs_file(
+                                    &story_file,
+                                    &story_path,
+                                    &format!("{}.mdx", final_path),
+                                    &program.expect_module(),
+                                    &cm,
+                                    &comments,
+                                );
+                            }
+                        }
                     }
                     None => {
                         println!("Not a recognisable directory");
@@ -257,52 +685,97 @@ impl Storybook {
         }
     }
 
-    pub fn pre_process_story_module(
+    /// Writes the MDX docs page of the component of the story file next to its stories
+    fn emit_docs_file(
         &mut self,
+        story_file: &StoryFile,
+        story_path: &str,
+        docs_path: &str,
+        module: &Module,
+        cm: &SourceMap,
+        comments: &SingleThreadedComments,
+    ) {
+        let contents = docs_page(story_file, story_path, module, cm, comments);
+        self.write_docs_file(docs_path, contents);
+    }
+
+    fn write_docs_file(&mut self, docs_path: &str, contents: String) {
+        let docs_path = Path::new(docs_path);
+        if docs_path.exists() {
+            println!("Docs already exist at {}", docs_path.display());
+            return;
+        }
+        let may_be_file = OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .open(docs_path);
+        match may_be_file {
+            Ok(mut file) => {
+                println!("=> {}", docs_path.display());
+                let _ = file.write(contents.as_bytes());
+            }
+            Err(e) => {
+                println!("{:#?}", e);
+            }
+        }
+    }
+
+    /// The story file and the docs page of the component which are missing, with their paths.
+    /// Nothing is written, the language server creates the files
+    pub fn pre_process_story_module(
+        &self,
         story_name: Option<String>,
         path: &PathBuf,
         config: &Config,
-    ) -> Result<(StoryFile, String), String> {
+    ) -> Result<StoryFiles, String> {
         if let Some(component) = story_name {
-            let mut stories: Vec<Story> = vec![];
-            let mut story = Story::new(component.clone());
-            story.print_story();
-            stories.push(story);
+            let target = find_story_target(config)?;
+            let stories: Vec<Story> = vec![Story::new(component.clone())];
             let mut story_file = StoryFile::new(component.to_owned(), stories);
             let file_name = path.file_stem().unwrap().to_str().unwrap();
-            story_file.print_import_default();
-            story_file.print_import_component(file_name);
-            story_file.print_meta_decl();
-            story_file.print_story_type();
+            let (module, cm, comments) = parse_module(path, config);
+            let mut hook_usage_visitor = HookUsageVisitor::new(&component);
+            module.visit_with(&mut hook_usage_visitor);
+            story_file.print_decorators(&hook_usage_visitor.usages, config);
+            let props = extract_props(&component, &module, &cm, &comments);
+            story_file.print_required_props(&props, config.typescript.is_some());
+            target.print_story_file(&mut story_file, file_name);
             let may_be_dir = path.parent();
             let result = match may_be_dir {
                 Some(dir_path) => {
                     let directory = dir_path.display();
                     let final_path = format!("{}/{}", directory, file_name);
-                    let pattern_matches = vec![
-                        String::from(final_path.to_owned() + ".stories.tsx"),
-                        String::from(final_path.to_owned() + ".story.tsx"),
-                    ];
-                    if pattern_matches.iter().any(|p| {
-                        let exists_already = File::open(p);
-                        match exists_already {
-                            Ok(_) => true,
-                            Err(_) => false,
-                        }
-                    }) {
-                        Err(format!("Story already exists for {}", path.display()))
-                    } else {
-                        let ext = config
-                            .story_file_ext
-                            .to_owned()
-                            .unwrap_or(String::from(".stories.tsx"));
-                        let file_name = format!("{}{}", final_path, ext);
-                        let new_path = Path::new(&file_name);
-                        let exists_already = File::open(new_path);
-                        match exists_already {
-                            Ok(_) => Err(format!("Story already exists for {}", path.display())),
-                            Err(_) => Ok((story_file, file_name)),
+                    let pattern_matches = target
+                        .existing_file_exts()
+                        .iter()
+                        .map(|ext| format!("{}{}", final_path, ext))
+                        .collect::<Vec<String>>();
+                    let ext = target.story_file_ext(config);
+                    let new_file_name = format!("{}{}", final_path, ext);
+                    let existing = pattern_matches
+                        .iter()
+                        .chain([&new_file_name])
+                        .find(|p| File::open(p).is_ok())
+                        .cloned();
+                    // The docs page is created when it is missing, for existing stories too
+                    let docs_path = format!("{}.mdx", final_path);
+                    let docs = (config.story_docs.unwrap_or(false)
+                        && target.supports_docs()
+                        && !Path::new(&docs_path).exists())
+                    .then(|| {
+                        let story_path = existing.as_ref().unwrap_or(&new_file_name);
+                        let contents = docs_page(&story_file, story_path, &module, &cm, &comments);
+                        (docs_path, contents)
+                    });
+                    match (existing, docs) {
+                        (Some(_), None) => {
+                            Err(format!("Story already exists for {}", path.display()))
                         }
+                        (Some(_), docs) => Ok(StoryFiles { story: None, docs }),
+                        (None, docs) => Ok(StoryFiles {
+                            story: Some((story_file, new_file_name)),
+                            docs,
+                        }),
                     }
                 }
                 None => Err("Not a recognisable directory".to_string()),
@@ -352,4 +825,185 @@ mod test {
             }
         }
     }
+
+    #[test]
+    fn test_story_decorators() {
+        let config = Config {
+            typescript: Some(true),
+            story_decorators: [(
+                String::from("ThemeContext"),
+                remod_config::StoryDecorator {
+                    decorator: String::from("(Story) => <ThemeProvider><Story /></ThemeProvider>"),
+                    imports: vec![String::from("import { ThemeProvider } from './theme';")],
+                },
+            )]
+            .into_iter()
+            .collect(),
+            ..Config::default()
+        };
+        let (program, _cm) = parse_raw_string_as_module(
+            "const Button = () => {
+                const theme = useContext(ThemeContext);
+                return <button>{theme.name}</button>;
+            };",
+            &config,
+        );
+        let mut hook_usage_visitor = HookUsageVisitor::new("Button");
+        program.visit_with(&mut hook_usage_visitor);
+        assert_eq!(
+            hook_usage_visitor.usages,
+            vec!["ThemeContext", "useContext"]
+        );
+
+        let mut story_file = StoryFile::new(String::from("Button"), vec![]);
+        story_file.print_import_component("Button");
+        story_file.print_decorators(&hook_usage_visitor.usages, &config);
+        story_file.print_meta_decl();
+        let output = story_file.emit_story_file();
+        assert!(output.contains("import { ThemeProvider } from './theme';"));
+        assert!(
+            output.contains("decorators: [(Story) => <ThemeProvider><Story /></ThemeProvider>],")
+        );
+    }
+
+    #[test]
+    fn test_import_component() {
+        let mut story_file = StoryFile::new(String::from("Button"), vec![]);
+        story_file.print_import_component("Button");
+        assert_eq!(
+            story_file.import_component,
+            "import { Button } from './Button'"
+        );
+
+        story_file.default_export = true;
+        story_file.print_import_component("Button");
+        assert_eq!(story_file.import_component, "import Button from './Button'");
+    }
+
+    #[test]
+    fn test_docs_file() {
+        let config = Config {
+            typescript: Some(true),
+            ..Config::default()
+        };
+        let path = PathBuf::from("../../tests/storybook/documented.tsx");
+        let (module, cm, comments) = parse_module(&path, &config);
+        let mut docs_visitor = ComponentDocVisitor::new("Button", &comments);
+        module.visit_with(&mut docs_visitor);
+        let props = extract_props("Button", &module, &cm, &comments);
+
+        let mut story = Story::new(String::from("Button"));
+        story.print_story();
+        let mut docs_file = DocsFile::new(String::from("Button"));
+        docs_file.print_import_blocks();
+        docs_file.print_import_stories("documented.stories");
+        docs_file.print_description(docs_visitor.description);
+        docs_file.print_props_table(&props);
+        docs_file.print_canvases(&[story]);
+        let output = docs_file.emit_docs_file();
+        assert!(output.contains("import * as Stories from './documented.stories';"));
+        assert!(output.contains("# Button\n\nPrimary UI component for user interaction"));
+        assert!(output.contains("| label | `string` | Yes | Text rendered inside the button |"));
+        assert!(output.contains(
+            "| variant | `\"primary\" \\| \"secondary\"` | No | Visual style of the button |"
+        ));
+        assert!(output.contains("| onClick | `() => void` | No |  |"));
+        assert!(output.contains("<Canvas of={Stories.Button_Primary} />"));
+    }
+
+    #[test]
+    fn test_docs_of_existing_story() {
+        let config = Config {
+            typescript: Some(true),
+            story_docs: Some(true),
+            ..Config::default()
+        };
+        let dir = std::env::temp_dir().join("remod_docs_of_existing_story");
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("documented.tsx");
+        std::fs::copy("../../tests/storybook/documented.tsx", &path).unwrap();
+        std::fs::write(dir.join("documented.stories.tsx"), "").unwrap();
+
+        let mut storybook = Storybook {
+            docs: true,
+            ..Default::default()
+        };
+        // The language server gets the docs page to create, nothing is written
+        let files = storybook
+            .pre_process_story_module(Some(String::from("Button")), &path, &config)
+            .unwrap();
+        assert!(files.story.is_none());
+        let (docs_path, docs) = files.docs.unwrap();
+        assert_eq!(docs_path, format!("{}/documented.mdx", dir.display()));
+        assert!(docs.contains("import * as Stories from './documented.stories';"));
+        assert!(!dir.join("documented.mdx").exists());
+
+        storybook.emit_story_file(&path, &config);
+        assert_eq!(storybook.created, 0);
+        let docs = std::fs::read_to_string(dir.join("documented.mdx")).unwrap();
+        assert!(docs.contains("import * as Stories from './documented.stories';"));
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_story_targets() {
+        let config = Config {
+            typescript: Some(true),
+            story_target: Some(String::from("cosmos")),
+            ..Config::default()
+        };
+        let mut story_file = StoryFile::new(
+            String::from("Button"),
+            vec![Story::new(String::from("Button"))],
+        );
+        story_file.decorators = vec![String::from("(Story) => <Theme><Story /></Theme>")];
+        story_file.print_required_props(
+            &[PropDefinition {
+                name: String::from("label"),
+                type_text: String::from("string"),
+                required: true,
+                ..Default::default()
+            }],
+            true,
+        );
+        story_target(&config).print_story_file(&mut story_file, "Button");
+        assert_eq!(
+            story_file.emit_story_file(),
+            "import { Button } from './Button'\nexport default {\n  Button_Primary: ((Story) => <Theme><Story /></Theme>)(() => <Button label=\"\" />),\n};"
+        );
+        assert_eq!(
+            story_target(&config).story_file_ext(&config),
+            ".fixture.tsx"
+        );
+        let config = Config {
+            story_file_ext: Some(String::from(".fixtures.tsx")),
+            ..config
+        };
+        assert_eq!(
+            story_target(&config).story_file_ext(&config),
+            ".fixtures.tsx"
+        );
+        let loc = story_file.find_story_ident_loc(&config);
+        assert_eq!(
+            loc.map(|(start, end)| (start.line, start.col_display, end.col_display)),
+            Some((3, 2, 16))
+        );
+
+        let config = Config {
+            story_target: Some(String::from("ladle")),
+            ..Config::default()
+        };
+        let mut story_file = StoryFile::new(
+            String::from("Button"),
+            vec![Story::new(String::from("Button"))],
+        );
+        story_target(&config).print_story_file(&mut story_file, "Button");
+        let output = story_file.emit_story_file();
+        assert!(output.contains("import type { Story } from '@ladle/react';"));
+        assert!(output.contains(
+            "export const Button_Primary: Story<React.ComponentProps<typeof Button>> = (args) => <Button {...args} />;"
+        ));
+        assert!(!output.contains("export default"));
+    }
 }
@@ -0,0 +1,342 @@
+use std::fs;
+
+use glob::Paths;
+use remod_config::Config;
+use swc_common::{BytePos, SourceMap, Spanned};
+use swc_ecma_ast::{
+    BinaryOp, BlockStmt, BlockStmtOrExpr, Decl, DefaultDecl, Expr, JSXAttr, JSXAttrName,
+    JSXAttrOrSpread, JSXAttrValue, JSXElement, JSXElementName, Lit, Module, ModuleDecl, ModuleItem,
+    ReturnStmt, Stmt,
+};
+
+use crate::{
+    display_name::module_components,
+    utils::{apply_edits, parse_module, should_ignore_entry, SourceEdit},
+};
+
+const TEST_ID: &str = "data-testid";
+
+/// `ListItem` as `list-item`
+pub fn kebab_case(name: &str) -> String {
+    let chars = name.chars().collect::<Vec<char>>();
+    let mut kebab = String::new();
+    for (i, c) in chars.iter().enumerate() {
+        if c.is_uppercase() && i > 0 {
+            let previous = chars[i - 1];
+            let next_is_lower = chars.get(i + 1).is_some_and(|n| n.is_lowercase());
+            if previous.is_lowercase()
+                || previous.is_ascii_digit()
+                || (previous.is_uppercase() && next_is_lower)
+            {
+                kebab.push('-');
+            }
+        }
+        kebab.extend(c.to_lowercase());
+    }
+    kebab
+}
+
+/// The test id of the component rendered from the template
+fn test_id(template: &str, component: &str) -> String {
+    template
+        .replace("{component-kebab}", &kebab_case(component))
+        .replace("{component}", component)
+}
+
+/// Body of the function declaring the component
+pub(crate) enum ComponentBody<'a> {
+    Block(&'a BlockStmt),
+    Expr(&'a Expr),
+}
+
+fn function_body(expr: &Expr) -> Option<ComponentBody<'_>> {
+    match expr {
+        Expr::Arrow(arrow) => Some(match *arrow.body {
+            BlockStmtOrExpr::BlockStmt(ref block) => ComponentBody::Block(block),
+            BlockStmtOrExpr::Expr(ref expr) => ComponentBody::Expr(expr),
+        }),
+        Expr::Fn(func) => func.function.body.as_ref().map(ComponentBody::Block),
+        Expr::Paren(paren) => function_body(&paren.expr),
+        // memo(forwardRef(...))
+        Expr::Call(call) => call.args.first().and_then(|arg| function_body(&arg.expr)),
+        _ => None,
+    }
+}
+
+pub(crate) fn component_body<'a>(module: &'a Module, component: &str) -> Option<ComponentBody<'a>> {
+    module.body.iter().find_map(|item| {
+        let decl = match item {
+            ModuleItem::Stmt(Stmt::Decl(decl)) => decl,
+            ModuleItem::ModuleDecl(ModuleDecl::ExportDecl(export)) => &export.decl,
+            ModuleItem::ModuleDecl(ModuleDecl::ExportDefaultDecl(export)) => {
+                return match export.decl {
+                    DefaultDecl::Fn(ref func)
+                        if func.ident.as_ref().is_some_and(|id| &*id.sym == component) =>
+                    {
+                        func.function.body.as_ref().map(ComponentBody::Block)
+                    }
+                    _ => None,
+                }
+            }
+            _ => return None,
+        };
+        match decl {
+            Decl::Fn(func) if &*func.ident.sym == component => {
+                func.function.body.as_ref().map(ComponentBody::Block)
+            }
+            Decl::Var(var) => var.decls.iter().find_map(|d| match d.name.as_ident() {
+                Some(id) if &*id.sym == component => d.init.as_deref().and_then(function_body),
+                _ => None,
+            }),
+            _ => None,
+        }
+    })
+}
+
+/// Collects the expressions returned by a function body, without looking into nested functions
+#[derive(Default)]
+struct ReturnVisitor<'a> {
+    pub returned: Vec<&'a Expr>,
+}
+
+impl<'a> ReturnVisitor<'a> {
+    fn visit_block(&mut self, block: &'a BlockStmt) {
+        for stmt in block.stmts.iter() {
+            self.visit_returns(stmt);
+        }
+    }
+
+    fn visit_returns(&mut self, stmt: &'a Stmt) {
+        match stmt {
+            Stmt::Return(ReturnStmt { arg: Some(arg), .. }) => self.returned.push(arg),
+            Stmt::Block(block) => self.visit_block(block),
+            Stmt::If(if_stmt) => {
+                self.visit_returns(&if_stmt.cons);
+                if let Some(ref alt) = if_stmt.alt {
+                    self.visit_returns(alt);
+                }
+            }
+            Stmt::Switch(switch) => {
+                for case in switch.cases.iter() {
+                    for stmt in case.cons.iter() {
+                        self.visit_returns(stmt);
+                    }
+                }
+            }
+            Stmt::Try(try_stmt) => {
+                self.visit_block(&try_stmt.block);
+                if let Some(ref handler) = try_stmt.handler {
+                    self.visit_block(&handler.body);
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Root element of a returned expression. `Err` with the reason it can not hold a test id
+fn returned_roots<'a>(expr: &'a Expr, roots: &mut Vec<Result<&'a JSXElement, String>>) {
+    match expr {
+        Expr::Paren(paren) => returned_roots(&paren.expr, roots),
+        Expr::Cond(cond) => {
+            returned_roots(&cond.cons, roots);
+            returned_roots(&cond.alt, roots);
+        }
+        Expr::Bin(bin) if bin.op == BinaryOp::LogicalAnd || bin.op == BinaryOp::LogicalOr => {
+            returned_roots(&bin.right, roots)
+        }
+        Expr::JSXElement(element) => match element.opening.name {
+            JSXElementName::Ident(ref id) if id.sym.starts_with(char::is_lowercase) => {
+                roots.push(Ok(element))
+            }
+            _ => roots.push(Err(String::from(
+                "renders another component which may not pass the test id on",
+            ))),
+        },
+        Expr::JSXFragment(..) => roots.push(Err(String::from("renders a fragment"))),
+        _ => {}
+    }
+}
+
+fn has_test_id(element: &JSXElement) -> bool {
+    element.opening.attrs.iter().any(|attr| match attr {
+        JSXAttrOrSpread::JSXAttr(attr) => is_test_id(attr),
+        JSXAttrOrSpread::SpreadElement(..) => false,
+    })
+}
+
+fn is_test_id(attr: &JSXAttr) -> bool {
+    matches!(attr.name, JSXAttrName::Ident(ref id) if &*id.sym == TEST_ID)
+}
+
+/// Root elements returned by the component. `Err` with the reason a root can not hold a test id
+fn component_roots<'a>(module: &'a Module, component: &str) -> Vec<Result<&'a JSXElement, String>> {
+    let mut roots = vec![];
+    match component_body(module, component) {
+        Some(ComponentBody::Expr(expr)) => returned_roots(expr, &mut roots),
+        Some(ComponentBody::Block(block)) => {
+            let mut return_visitor = ReturnVisitor::default();
+            return_visitor.visit_block(block);
+            for expr in return_visitor.returned {
+                returned_roots(expr, &mut roots);
+            }
+        }
+        None => {}
+    }
+    roots
+}
+
+/// Adds a `data-testid` to the root element returned by every component of the module.
+/// Returns `None` when nothing was added
+pub fn add_test_ids_module(module: &Module, cm: &SourceMap, template: &str) -> Option<String> {
+    let mut edits = vec![];
+    for component in module_components(module) {
+        for root in component_roots(module, &component) {
+            match root {
+                Ok(element) if has_test_id(element) => {}
+                Ok(element) => edits.push(SourceEdit::insert(
+                    element.opening.name.span_hi(),
+                    format!(" {}=\"{}\"", TEST_ID, test_id(template, &component)),
+                )),
+                Err(reason) => println!("=> Skipping a root of '{}', it {}", component, reason),
+            }
+        }
+    }
+    if edits.is_empty() {
+        return None;
+    }
+    apply_edits(cm, edits)
+}
+
+/// Removes the `data-testid` attributes the template added to the root elements of the
+/// components. Hand-written test ids are kept. Returns `None` when there were none
+pub fn remove_test_ids_module(module: &Module, cm: &SourceMap, template: &str) -> Option<String> {
+    let mut attrs = vec![];
+    for component in module_components(module) {
+        let value = test_id(template, &component);
+        for element in component_roots(module, &component).into_iter().flatten() {
+            attrs.extend(element.opening.attrs.iter().filter_map(|attr| match attr {
+                JSXAttrOrSpread::JSXAttr(attr)
+                    if is_test_id(attr)
+                        && matches!(
+                            attr.value,
+                            Some(JSXAttrValue::Lit(Lit::Str(ref s))) if *s.value == *value
+                        ) =>
+                {
+                    Some(attr)
+                }
+                _ => None,
+            }));
+        }
+    }
+    let edits = attrs
+        .iter()
+        .map(|attr| {
+            // The whitespace separating the attribute goes along
+            let file = cm.lookup_byte_offset(attr.span.lo).sf;
+            let offset = (attr.span.lo - file.start_pos).0 as usize;
+            let start = file.src[..offset].trim_end().len();
+            SourceEdit {
+                start: file.start_pos + BytePos(start as u32),
+                end: attr.span.hi,
+                text: String::new(),
+            }
+        })
+        .collect::<Vec<SourceEdit>>();
+    if edits.is_empty() {
+        return None;
+    }
+    apply_edits(cm, edits)
+}
+
+#[derive(Debug, Default)]
+pub struct TestIds {
+    /// Strip the `data-testid` attributes instead of adding them
+    pub remove: bool,
+    ///  Count of modified files
+    pub modified: i64,
+    /// Count of ignored files
+    pub ignored: i64,
+    /// Count of total files
+    pub total: i64,
+}
+
+impl TestIds {
+    pub fn update_test_ids(&mut self, files: Paths, config: &Config) {
+        let template = config
+            .test_id_template
+            .to_owned()
+            .unwrap_or_else(|| String::from("{component-kebab}"));
+        for entry in files {
+            match entry {
+                Ok(path) => {
+                    self.total += 1;
+                    if should_ignore_entry(&config.ignore, &path) {
+                        println!("Skipping {}", path.display());
+                        self.ignored += 1;
+                        continue;
+                    }
+                    let (module, cm, _comments) = parse_module(&path, config);
+                    let output = if self.remove {
+                        remove_test_ids_module(&module, &cm, &template)
+                    } else {
+                        add_test_ids_module(&module, &cm, &template)
+                    };
+                    match output {
+                        Some(output) => match fs::write(&path, output) {
+                            Ok(_) => {
+                                println!("{}", path.display());
+                                self.modified += 1;
+                            }
+                            Err(e) => println!("{:#?}", e),
+                        },
+                        None => self.ignored += 1,
+                    }
+                }
+                Err(e) => println!("{:?}", e),
+            }
+        }
+    }
+
+    pub fn display_stats(self) {
+        println!("Total {} files", self.total);
+        println!("Modified {} files", self.modified);
+        println!("Ingored {} files", self.ignored);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::path::PathBuf;
+
+    use super::*;
+    use crate::utils::parse_raw_string_as_module;
+
+    #[test]
+    fn test_test_ids() {
+        assert_eq!(kebab_case("HTMLPreview"), "html-preview");
+        assert_eq!(kebab_case("UserCard2Go"), "user-card2-go");
+        let config = Config {
+            typescript: Some(true),
+            ..Config::default()
+        };
+        let path = PathBuf::from("../../tests/test_ids/card.tsx");
+        let (module, cm, _comments) = parse_module(&path, &config);
+        let output = add_test_ids_module(&module, &cm, "{component-kebab}").unwrap();
+        assert!(output.contains(r#"<div data-testid="user-card" className="card">"#));
+        assert!(output.contains(r#"<span data-testid="html-preview" className="spinner" />"#));
+        assert!(output.contains("<p>{item}</p>"));
+        assert!(output.contains(r#"data-testid="preview" />"#));
+        assert!(output.contains("<>\n    <li />"));
+        assert!(output.contains(r#"<UserCard name="wrapped" />"#));
+
+        // Only the added test ids are removed
+        assert_eq!(
+            remove_test_ids_module(&module, &cm, "{component-kebab}"),
+            None
+        );
+        let (program, cm) = parse_raw_string_as_module(&output, &config);
+        let output = remove_test_ids_module(&program.expect_module(), &cm, "{component-kebab}");
+        assert_eq!(output, Some(fs::read_to_string(&path).unwrap()));
+    }
+}
@@ -0,0 +1,334 @@
+use std::{
+    fs::OpenOptions,
+    io::Write,
+    path::{Path, PathBuf},
+};
+
+use glob::Paths;
+use remod_config::Config;
+use swc_ecma_ast::Module;
+
+use crate::{
+    display_name::module_components,
+    export_style::default_export_name,
+    props::{extract_props, PropDefinition},
+    utils::{exported_names, parse_module, should_ignore_entry},
+};
+
+/// Test runners the test files can be written for
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TestRunner {
+    Jest,
+    Vitest,
+}
+
+impl TestRunner {
+    /// The test runner selected by `test_runner` in the config. Defaults to Jest
+    pub fn from_config(config: &Config) -> Self {
+        match config.test_runner.as_deref() {
+            None | Some("jest") => TestRunner::Jest,
+            Some("vitest") => TestRunner::Vitest,
+            Some(runner) => {
+                println!("Unknown test runner '{}'. Falling back to jest", runner);
+                TestRunner::Jest
+            }
+        }
+    }
+    fn mock_fn(&self) -> &'static str {
+        match self {
+            TestRunner::Jest => "jest.fn()",
+            TestRunner::Vitest => "vi.fn()",
+        }
+    }
+}
+
+/// A JSX attribute value satisfying the prop type, functions are `mock_fn`
+pub(crate) fn prop_value(prop: &PropDefinition, mock_fn: &str, typescript: bool) -> String {
+    let type_text = prop.type_text.trim();
+    let first_variant = type_text
+        .split('|')
+        .map(|t| t.trim())
+        .find(|t| !t.is_empty())
+        .unwrap_or_default();
+    if type_text.contains("=>") {
+        format!("{{{}}}", mock_fn)
+    } else if first_variant.starts_with('"') || first_variant.starts_with('\'') {
+        format!(
+            "\"{}\"",
+            first_variant.trim_matches(|c| c == '"' || c == '\'')
+        )
+    } else if first_variant == "string" {
+        String::from("\"\"")
+    } else if first_variant == "number" || first_variant.parse::<f64>().is_ok() {
+        format!("{{{}}}", first_variant.parse::<f64>().unwrap_or(0.0))
+    } else if first_variant == "boolean" || first_variant == "true" || first_variant == "false" {
+        format!("{{{}}}", first_variant == "true")
+    } else if first_variant.ends_with("[]") || first_variant.starts_with("Array<") {
+        String::from("{[]}")
+    } else if first_variant.ends_with("ReactNode") || first_variant.ends_with("ReactElement") {
+        String::from("{null}")
+    } else if typescript {
+        String::from("{{} as any}")
+    } else {
+        String::from("{{}}")
+    }
+}
+
+/// The components of the module a test file can import, the exported ones and the default export
+fn tested_components(module: &Module) -> Vec<String> {
+    let exported = exported_names(module);
+    let default_export = default_export_name(module);
+    module_components(module)
+        .into_iter()
+        .filter(|c| exported.contains(c) || default_export.as_ref() == Some(c))
+        .collect()
+}
+
+#[derive(Debug, Default)]
+pub struct TestFile {
+    /// Whether the test file is written in TypeScript
+    typescript: bool,
+    /// The testing library and test runner import statements
+    import_default: String,
+    /// The component import statement
+    import_components: String,
+    /// Smoke tests of the components
+    tests: Vec<String>,
+}
+
+impl TestFile {
+    fn print_import_default(&mut self, runner: TestRunner) {
+        self.import_default = match runner {
+            TestRunner::Jest => String::from("import { render } from '@testing-library/react';"),
+            TestRunner::Vitest => String::from(
+                "import { describe, expect, it, vi } from 'vitest';\nimport { render } from '@testing-library/react';",
+            ),
+        };
+    }
+    fn print_import_components(
+        &mut self,
+        components: &[String],
+        default: Option<&str>,
+        file_name: &str,
+    ) {
+        let named = components
+            .iter()
+            .filter(|c| Some(c.as_str()) != default)
+            .map(|c| c.to_owned())
+            .collect::<Vec<String>>();
+        let specifiers = match default {
+            Some(default) if named.is_empty() => default.to_owned(),
+            Some(default) => format!("{}, {{ {} }}", default, named.join(", ")),
+            None => format!("{{ {} }}", named.join(", ")),
+        };
+        self.import_components = format!("import {} from './{}';", specifiers, file_name);
+    }
+    fn print_test(&mut self, component: &str, props: &[PropDefinition], runner: TestRunner) {
+        let attributes = props
+            .iter()
+            .filter(|p| p.required)
+            .map(|p| {
+                format!(
+                    " {}={}",
+                    p.name,
+                    prop_value(p, runner.mock_fn(), self.typescript)
+                )
+            })
+            .collect::<String>();
+        self.tests.push(format!(
+            "describe('{}', () => {{
+  it('renders without crashing', () => {{
+    const {{ container }} = render(<{}{} />);
+    expect(container).toBeTruthy();
+  }});
+}});",
+            component, component, attributes
+        ));
+    }
+
+    pub fn emit_test_file(&self) -> String {
+        format!(
+            "{}\n{}\n\n{}\n",
+            self.import_default,
+            self.import_components,
+            self.tests.join("\n\n")
+        )
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct TestingLibrary {
+    ///  Count of created files
+    pub created: i64,
+    /// Count of ignored files
+    pub ignored: i64,
+    /// Count of total files
+    pub total: i64,
+}
+
+impl TestingLibrary {
+    pub fn emit_test_file(&mut self, path: &PathBuf, config: &Config) {
+        if should_ignore_entry(&config.ignore, path) {
+            self.ignored += 1;
+            return;
+        }
+        let (module, cm, comments) = parse_module(path, config);
+        let default_export = default_export_name(&module);
+        let components = tested_components(&module);
+        if components.is_empty() {
+            self.ignored += 1;
+            return;
+        }
+        let file_name = path.file_stem().unwrap().to_str().unwrap();
+        let directory = path.parent().unwrap_or(Path::new("."));
+        let ext = config
+            .test_file_ext
+            .to_owned()
+            .unwrap_or(String::from(".test.tsx"));
+        let existing_exts = [
+            ext.as_str(),
+            ".test.tsx",
+            ".test.jsx",
+            ".spec.tsx",
+            ".spec.jsx",
+        ];
+        if existing_exts
+            .iter()
+            .any(|e| directory.join(format!("{}{}", file_name, e)).exists())
+        {
+            println!("Tests already exist for {}", path.display());
+            self.ignored += 1;
+            return;
+        }
+
+        let runner = TestRunner::from_config(config);
+        let mut test_file = TestFile {
+            typescript: config.typescript.unwrap_or(false)
+                && (ext.ends_with(".ts") || ext.ends_with(".tsx")),
+            ..Default::default()
+        };
+        test_file.print_import_default(runner);
+        test_file.print_import_components(&components, default_export.as_deref(), file_name);
+        for component in components.iter() {
+            let props = extract_props(component, &module, &cm, &comments);
+            test_file.print_test(component, &props, runner);
+        }
+        let new_path = directory.join(format!("{}{}", file_name, ext));
+        let may_be_file = OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .open(&new_path);
+        match may_be_file {
+            Ok(mut file) => {
+                println!("{} => {}", path.display(), new_path.display());
+                let _ = file.write(test_file.emit_test_file().as_bytes());
+                self.created += 1;
+            }
+            Err(e) => {
+                println!("{:#?}", e);
+            }
+        }
+    }
+
+    pub fn emit_test_files(&mut self, files: Paths, config: &Config) {
+        for entry in files {
+            self.total += 1;
+            match entry {
+                Ok(path) => self.emit_test_file(&path, config),
+                Err(e) => println!("{:?}", e),
+            }
+        }
+    }
+
+    pub fn display_stats(self) {
+        println!("Total {} files", self.total);
+        println!("Created {} files", self.created);
+        println!("Ingored {} files", self.ignored);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_required_props() {
+        let config = Config {
+            typescript: Some(true),
+            ..Config::default()
+        };
+        let path = PathBuf::from("../../tests/storybook/documented.tsx");
+        let (module, cm, comments) = parse_module(&path, &config);
+        let props = extract_props("Button", &module, &cm, &comments);
+        let mut test_file = TestFile::default();
+        test_file.print_import_default(TestRunner::Vitest);
+        test_file.print_import_components(&[String::from("Button")], None, "documented");
+        test_file.print_test("Button", &props, TestRunner::Vitest);
+        let output = test_file.emit_test_file();
+        assert!(output.contains("import { describe, expect, it, vi } from 'vitest';"));
+        assert!(output.contains("import { Button } from './documented';"));
+        assert!(output.contains("const { container } = render(<Button label=\"\" />);"));
+        assert_eq!(tested_components(&module), vec![String::from("Button")]);
+
+        test_file.print_import_components(
+            &[String::from("Button"), String::from("Icon")],
+            Some("Button"),
+            "documented",
+        );
+        assert_eq!(
+            test_file.import_components,
+            "import Button, { Icon } from './documented';"
+        );
+
+        // Components which are not exported can not be imported by the test file
+        let path = PathBuf::from("../../tests/testing_library/menu.tsx");
+        let (module, _cm, _comments) = parse_module(&path, &config);
+        assert_eq!(
+            tested_components(&module),
+            vec![String::from("Menu"), String::from("MenuPage")]
+        );
+    }
+
+    #[test]
+    fn test_prop_values() {
+        let prop = |type_text: &str| PropDefinition {
+            name: String::from("prop"),
+            type_text: type_text.to_string(),
+            required: true,
+            description: None,
+            default_value: None,
+        };
+        assert_eq!(
+            prop_value(&prop("\"sm\" | \"lg\""), TestRunner::Jest.mock_fn(), true),
+            "\"sm\""
+        );
+        assert_eq!(
+            prop_value(&prop("number"), TestRunner::Jest.mock_fn(), true),
+            "{0}"
+        );
+        assert_eq!(
+            prop_value(&prop("boolean"), TestRunner::Jest.mock_fn(), true),
+            "{false}"
+        );
+        assert_eq!(
+            prop_value(
+                &prop("(index: number) => void"),
+                TestRunner::Jest.mock_fn(),
+                true
+            ),
+            "{jest.fn()}"
+        );
+        assert_eq!(
+            prop_value(&prop("string[]"), TestRunner::Vitest.mock_fn(), true),
+            "{[]}"
+        );
+        assert_eq!(
+            prop_value(&prop("CarouselProps"), TestRunner::Vitest.mock_fn(), true),
+            "{{} as any}"
+        );
+        assert_eq!(
+            prop_value(&prop("CarouselProps"), TestRunner::Jest.mock_fn(), false),
+            "{{}}"
+        );
+    }
+}
@@ -0,0 +1,386 @@
+use std::{
+    collections::HashSet,
+    path::{Path, PathBuf},
+};
+
+use glob::Paths;
+use remod_config::Config;
+use swc_ecma_ast::{
+    CallExpr, Callee, ExportSpecifier, Expr, ImportSpecifier, JSXElementName, JSXObject,
+    JSXOpeningElement, Lit, Module, ModuleDecl, ModuleExportName, ModuleItem,
+};
+use swc_ecma_visit::{Visit, VisitWith};
+
+use crate::{
+    display_name::module_components,
+    export_style::default_export_name,
+    move_component::{resolve, PathAliases, Resolution},
+    split::normalize,
+    utils::{exported_names, parse_module, should_ignore_entry},
+};
+
+/// Any export of a module, for namespace imports, `import()`, `require` and `export *`
+const ALL_EXPORTS: &str = "*";
+
+/// What a module exports and what it takes from the other modules of the project
+#[derive(Debug, Default, PartialEq)]
+pub struct ModuleUsage {
+    /// Exported components with the name they are exported as, `default` for the default export
+    pub components: Vec<(String, String)>,
+    /// Names imported from the other modules, `*` when any export can be used
+    pub imports: Vec<(PathBuf, String)>,
+    /// Names re-exported from the other modules with the name they are re-exported as, `None`
+    /// for `export * from`
+    pub re_exports: Vec<(Option<String>, PathBuf, String)>,
+    /// Components rendered in the module itself
+    pub rendered: Vec<String>,
+}
+
+fn export_name(name: &ModuleExportName) -> String {
+    match name {
+        ModuleExportName::Ident(id) => id.sym.to_string(),
+        ModuleExportName::Str(s) => s.value.to_string(),
+    }
+}
+
+/// Name of the component a JSX member expression starts from, `Carousel` of `Carousel.Slide`
+fn jsx_object_name(object: &JSXObject) -> String {
+    match object {
+        JSXObject::Ident(id) => id.sym.to_string(),
+        JSXObject::JSXMemberExpr(member) => jsx_object_name(&member.obj),
+    }
+}
+
+/// Collects the components rendered as JSX elements, `Carousel` of `<Carousel.Slide />` included
+#[derive(Default)]
+struct RenderedComponents {
+    names: Vec<String>,
+}
+
+impl Visit for RenderedComponents {
+    fn visit_jsx_opening_element(&mut self, n: &JSXOpeningElement) {
+        match n.name {
+            JSXElementName::Ident(ref id) => self.names.push(id.sym.to_string()),
+            JSXElementName::JSXMemberExpr(ref member) => {
+                self.names.push(jsx_object_name(&member.obj))
+            }
+            JSXElementName::JSXNamespacedName(..) => {}
+        }
+        n.visit_children_with(self);
+    }
+}
+
+/// Collects the specifiers of `import()` and `require` calls
+#[derive(Default)]
+struct DynamicImports {
+    specifiers: Vec<String>,
+}
+
+impl Visit for DynamicImports {
+    fn visit_call_expr(&mut self, n: &CallExpr) {
+        let loads_module = match n.callee {
+            Callee::Import(..) => true,
+            Callee::Expr(ref callee) => callee.as_ident().is_some_and(|id| &*id.sym == "require"),
+            Callee::Super(..) => false,
+        };
+        if let (true, Some(Expr::Lit(Lit::Str(src)))) =
+            (loads_module, n.args.first().map(|arg| &*arg.expr))
+        {
+            self.specifiers.push(src.value.to_string());
+        }
+        n.visit_children_with(self);
+    }
+}
+
+/// Exported components, imports and re-exports of the module at `path`. The specifiers are
+/// resolved to the files of the project, packages are left out
+pub fn module_usage(module: &Module, path: &Path, aliases: Option<&PathAliases>) -> ModuleUsage {
+    let resolve_file = |specifier: &str| match resolve(path, specifier, aliases) {
+        Resolution::File(file) => Some(normalize(&file)),
+        _ => None,
+    };
+    let mut usage = ModuleUsage::default();
+
+    let components = module_components(module);
+    let default = default_export_name(module).filter(|name| components.contains(name));
+    let exported = exported_names(module);
+    for item in module.body.iter() {
+        // export { Button as PrimaryButton }
+        if let ModuleItem::ModuleDecl(ModuleDecl::ExportNamed(export)) = item {
+            if export.src.is_none() {
+                for specifier in export.specifiers.iter() {
+                    if let ExportSpecifier::Named(named) = specifier {
+                        let orig = export_name(&named.orig);
+                        if components.contains(&orig) {
+                            let exported = named.exported.as_ref().unwrap_or(&named.orig);
+                            usage.components.push((export_name(exported), orig));
+                        }
+                    }
+                }
+            }
+        }
+    }
+    for component in components.iter() {
+        if exported.contains(component) && !usage.components.iter().any(|(_, c)| c == component) {
+            usage
+                .components
+                .push((component.to_owned(), component.to_owned()));
+        }
+    }
+    if let Some(default) = default {
+        usage.components.push((String::from("default"), default));
+    }
+
+    for item in module.body.iter() {
+        match item {
+            ModuleItem::ModuleDecl(ModuleDecl::Import(import)) if !import.type_only => {
+                let file = match resolve_file(&import.src.value) {
+                    Some(file) => file,
+                    None => continue,
+                };
+                for specifier in import.specifiers.iter() {
+                    let name = match specifier {
+                        ImportSpecifier::Named(named) => match named.imported {
+                            Some(ref imported) => export_name(imported),
+                            None => named.local.sym.to_string(),
+                        },
+                        ImportSpecifier::Default(..) => String::from("default"),
+                        ImportSpecifier::Namespace(..) => String::from(ALL_EXPORTS),
+                    };
+                    usage.imports.push((file.to_owned(), name));
+                }
+            }
+            ModuleItem::ModuleDecl(ModuleDecl::ExportNamed(export)) if !export.type_only => {
+                let file = match export.src.as_ref().and_then(|src| resolve_file(&src.value)) {
+                    Some(file) => file,
+                    None => continue,
+                };
+                for specifier in export.specifiers.iter() {
+                    let (exported, orig) = match specifier {
+                        ExportSpecifier::Named(named) => (
+                            export_name(named.exported.as_ref().unwrap_or(&named.orig)),
+                            export_name(&named.orig),
+                        ),
+                        ExportSpecifier::Namespace(namespace) => {
+                            (export_name(&namespace.name), String::from(ALL_EXPORTS))
+                        }
+                        ExportSpecifier::Default(default) => {
+                            (default.exported.sym.to_string(), String::from("default"))
+                        }
+                    };
+                    usage
+                        .re_exports
+                        .push((Some(exported), file.to_owned(), orig));
+                }
+            }
+            ModuleItem::ModuleDecl(ModuleDecl::ExportAll(export)) if !export.type_only => {
+                if let Some(file) = resolve_file(&export.src.value) {
+                    usage
+                        .re_exports
+                        .push((None, file, String::from(ALL_EXPORTS)));
+                }
+            }
+            _ => {}
+        }
+    }
+
+    let mut dynamic_imports = DynamicImports::default();
+    module.visit_with(&mut dynamic_imports);
+    for specifier in dynamic_imports.specifiers {
+        if let Some(file) = resolve_file(&specifier) {
+            usage.imports.push((file, String::from(ALL_EXPORTS)));
+        }
+    }
+
+    let mut rendered = RenderedComponents::default();
+    module.visit_with(&mut rendered);
+    usage.rendered = rendered
+        .names
+        .into_iter()
+        .filter(|name| components.contains(name))
+        .collect();
+    usage
+}
+
+/// Exported components of the modules never imported by another module nor rendered in their own
+/// module. Every export of the entry points is used, and the names taken from a barrel are
+/// followed to the modules it re-exports
+pub fn unused_components(
+    modules: &[(PathBuf, ModuleUsage)],
+    entry_points: &[PathBuf],
+) -> Vec<(PathBuf, String)> {
+    let mut used: HashSet<(PathBuf, String)> = HashSet::new();
+    let mut pending: Vec<(PathBuf, String)> = entry_points
+        .iter()
+        .map(|entry| (entry.to_owned(), String::from(ALL_EXPORTS)))
+        .chain(
+            modules
+                .iter()
+                .flat_map(|(_, usage)| usage.imports.to_owned()),
+        )
+        .collect();
+    while let Some((file, name)) = pending.pop() {
+        if !used.insert((file.to_owned(), name.to_owned())) {
+            continue;
+        }
+        let usage = match modules.iter().find(|(path, _)| *path == file) {
+            Some((_, usage)) => usage,
+            None => continue,
+        };
+        for (exported, source, orig) in usage.re_exports.iter() {
+            match exported {
+                Some(exported) if name == ALL_EXPORTS || *exported == name => {
+                    pending.push((source.to_owned(), orig.to_owned()))
+                }
+                // export * never re-exports the default export
+                None if name != "default" => pending.push((source.to_owned(), name.to_owned())),
+                _ => {}
+            }
+        }
+    }
+
+    let mut unused = vec![];
+    for (path, usage) in modules.iter() {
+        if used.contains(&(path.to_owned(), String::from(ALL_EXPORTS))) {
+            continue;
+        }
+        for (_, component) in usage.components.iter() {
+            // A component exported under several names is used through any of them
+            let is_used = usage.rendered.contains(component)
+                || usage.components.iter().any(|(exported, c)| {
+                    c == component && used.contains(&(path.to_owned(), exported.to_owned()))
+                });
+            let unused_component = (path.to_owned(), component.to_owned());
+            if !is_used && !unused.contains(&unused_component) {
+                unused.push(unused_component);
+            }
+        }
+    }
+    unused
+}
+
+#[derive(Debug, Default)]
+pub struct Unused {
+    /// Exported components never imported nor rendered by file
+    pub unused: Vec<(PathBuf, String)>,
+    /// Count of ignored files
+    pub ignored: i64,
+    /// Count of total files
+    pub total: i64,
+}
+
+impl Unused {
+    pub fn find_unused_components(&mut self, files: Paths, config: &Config) {
+        let aliases = PathAliases::load(Path::new("tsconfig.json"))
+            .or_else(|| PathAliases::load(Path::new("jsconfig.json")));
+        let mut modules = vec![];
+        let mut entry_points = vec![];
+        for entry in files {
+            match entry {
+                Ok(path) => {
+                    self.total += 1;
+                    if should_ignore_entry(&config.ignore, &path) {
+                        println!("Skipping {}", path.display());
+                        self.ignored += 1;
+                        continue;
+                    }
+                    let file = normalize(&path);
+                    if should_ignore_entry(&config.entry_points, &path)
+                        || should_ignore_entry(&config.entry_points, &file)
+                    {
+                        entry_points.push(file.to_owned());
+                    }
+                    let (module, _cm, _comments) = parse_module(&path, config);
+                    modules.push((file, module_usage(&module, &path, aliases.as_ref())));
+                }
+                Err(e) => println!("{:?}", e),
+            }
+        }
+        self.unused = unused_components(&modules, &entry_points);
+        for (path, component) in self.unused.iter() {
+            println!(
+                "=> '{}' of {} is never imported or rendered",
+                component,
+                path.display()
+            );
+        }
+    }
+
+    pub fn display_stats(self) {
+        println!("Total {} files", self.total);
+        println!("Found {} unused components", self.unused.len());
+        println!("Ingored {} files", self.ignored);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_unused_components() {
+        let config = Config {
+            typescript: Some(true),
+            ..Config::default()
+        };
+        let root = PathBuf::from("../../tests/unused");
+        let aliases = PathAliases::load(&root.join("tsconfig.json"));
+        let modules = [
+            "src/App.tsx",
+            "src/pages/Settings.tsx",
+            "src/components/index.ts",
+            "src/components/Button.tsx",
+            "src/components/Card.tsx",
+            "src/components/Modal.tsx",
+            "src/components/Tooltip.tsx",
+        ]
+        .iter()
+        .map(|file| {
+            let path = root.join(file);
+            let (module, _cm, _comments) = parse_module(&path, &config);
+            let usage = module_usage(&module, &path, aliases.as_ref());
+            (normalize(&path), usage)
+        })
+        .collect::<Vec<(PathBuf, ModuleUsage)>>();
+        assert_eq!(
+            modules[4].1.components,
+            vec![
+                (String::from("CardHeader"), String::from("CardHeader")),
+                (String::from("CardFooter"), String::from("CardFooter")),
+                (String::from("Card"), String::from("Card")),
+            ]
+        );
+        assert_eq!(
+            modules[0].1.imports,
+            vec![
+                (
+                    normalize(&root.join("src/components/index.ts")),
+                    String::from("Button")
+                ),
+                (
+                    normalize(&root.join("src/components/index.ts")),
+                    String::from("Card")
+                ),
+                (
+                    normalize(&root.join("src/pages/Settings.tsx")),
+                    String::from("*")
+                ),
+            ]
+        );
+
+        let entry_points = vec![normalize(&root.join("src/App.tsx"))];
+        assert_eq!(
+            unused_components(&modules, &entry_points),
+            vec![
+                (
+                    normalize(&root.join("src/components/Card.tsx")),
+                    String::from("CardFooter")
+                ),
+                (
+                    normalize(&root.join("src/components/Tooltip.tsx")),
+                    String::from("Tooltip")
+                ),
+            ]
+        );
+    }
+}
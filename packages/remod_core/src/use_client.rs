@@ -0,0 +1,228 @@
+use std::fs;
+
+use glob::Paths;
+use remod_config::Config;
+use swc_common::{SourceMap, Spanned};
+use swc_ecma_ast::{
+    CallExpr, Callee, Class, Expr, JSXAttr, JSXAttrName, Lit, MemberProp, Module, ModuleItem, Stmt,
+};
+use swc_ecma_visit::{Visit, VisitWith};
+
+use crate::{
+    display_name::module_components,
+    storybook::is_hook_name,
+    utils::{apply_edits, parse_module, should_ignore_entry, IdentReferences, SourceEdit},
+};
+
+/// Globals only available in the browser
+const BROWSER_GLOBALS: [&str; 6] = [
+    "window",
+    "document",
+    "localStorage",
+    "sessionStorage",
+    "navigator",
+    "location",
+];
+
+/// Collects the reasons a module can only run on the client
+#[derive(Debug, Default)]
+pub struct ClientUsageVisitor {
+    pub reasons: Vec<String>,
+}
+
+impl ClientUsageVisitor {
+    fn add(&mut self, reason: String) {
+        if !self.reasons.contains(&reason) {
+            self.reasons.push(reason);
+        }
+    }
+}
+
+impl Visit for ClientUsageVisitor {
+    fn visit_call_expr(&mut self, n: &CallExpr) {
+        if let Callee::Expr(ref callee) = n.callee {
+            let name = match **callee {
+                Expr::Ident(ref id) => Some(id.sym.to_string()),
+                // React.useState
+                Expr::Member(ref member) => match member.prop {
+                    MemberProp::Ident(ref prop) => Some(prop.sym.to_string()),
+                    _ => None,
+                },
+                _ => None,
+            };
+            if let Some(name) = name {
+                if is_hook_name(&name) || name == "createContext" {
+                    self.add(format!("calls {}", name));
+                }
+            }
+        }
+        n.visit_children_with(self);
+    }
+
+    fn visit_jsx_attr(&mut self, n: &JSXAttr) {
+        if let JSXAttrName::Ident(ref name) = n.name {
+            let is_handler = name
+                .sym
+                .strip_prefix("on")
+                .and_then(|rest| rest.chars().next())
+                .is_some_and(|c| c.is_ascii_uppercase());
+            if is_handler {
+                self.add(format!("passes the event handler {}", name.sym));
+            }
+        }
+        n.visit_children_with(self);
+    }
+
+    fn visit_class(&mut self, n: &Class) {
+        if n.super_class.is_some() {
+            self.add(String::from("declares a class component"));
+        }
+        n.visit_children_with(self);
+    }
+}
+
+/// The reasons the module can only run on the client
+pub fn client_usage(module: &Module) -> Vec<String> {
+    let mut visitor = ClientUsageVisitor::default();
+    module.visit_with(&mut visitor);
+    for global in BROWSER_GLOBALS {
+        let mut references = IdentReferences::new(vec![global.to_string()]);
+        module.visit_with(&mut references);
+        if !references.spans.is_empty() {
+            visitor.add(format!("uses {}", global));
+        }
+    }
+    visitor.reasons
+}
+
+/// The `"use client"` directive statement of the module, among the directives opening it
+fn client_directive(module: &Module) -> Option<&Stmt> {
+    module
+        .body
+        .iter()
+        .map_while(|item| match item {
+            ModuleItem::Stmt(stmt @ Stmt::Expr(expr)) => match *expr.expr {
+                Expr::Lit(Lit::Str(ref s)) => Some((stmt, s)),
+                _ => None,
+            },
+            _ => None,
+        })
+        .find_map(|(stmt, s)| (&*s.value == "use client").then_some(stmt))
+}
+
+/// Adds the `"use client"` directive to component modules using hooks, event handlers or
+/// browser globals. With `remove`, the directive is dropped from modules which do not need it
+/// instead. Returns `None` when the module is left as it is
+pub fn use_client_module(module: &Module, cm: &SourceMap, remove: bool) -> Option<String> {
+    if module_components(module).is_empty() {
+        return None;
+    }
+    let reasons = client_usage(module);
+    match (client_directive(module), reasons.is_empty(), remove) {
+        (None, false, false) => {
+            println!("=> The module {}", reasons.join(", "));
+            let first = module.body.first()?;
+            apply_edits(
+                cm,
+                vec![SourceEdit::insert(
+                    first.span_lo(),
+                    String::from("\"use client\";\n\n"),
+                )],
+            )
+        }
+        (Some(directive), true, true) => {
+            apply_edits(cm, vec![SourceEdit::remove_lines(cm, directive.span())])
+        }
+        _ => None,
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct UseClient {
+    /// Remove the directive from modules which do not need it instead of adding it
+    pub remove: bool,
+    ///  Count of modified files
+    pub modified: i64,
+    /// Count of ignored files
+    pub ignored: i64,
+    /// Count of total files
+    pub total: i64,
+}
+
+impl UseClient {
+    pub fn use_client(&mut self, files: Paths, config: &Config) {
+        for entry in files {
+            match entry {
+                Ok(path) => {
+                    self.total += 1;
+                    if should_ignore_entry(&config.ignore, &path) {
+                        println!("Skipping {}", path.display());
+                        self.ignored += 1;
+                        continue;
+                    }
+                    let (module, cm, _comments) = parse_module(&path, config);
+                    match use_client_module(&module, &cm, self.remove) {
+                        Some(output) => match fs::write(&path, output) {
+                            Ok(_) => {
+                                println!("{}", path.display());
+                                self.modified += 1;
+                            }
+                            Err(e) => println!("{:#?}", e),
+                        },
+                        None => self.ignored += 1,
+                    }
+                }
+                Err(e) => println!("{:?}", e),
+            }
+        }
+    }
+
+    pub fn display_stats(self) {
+        println!("Total {} files", self.total);
+        println!("Modified {} files", self.modified);
+        println!("Ingored {} files", self.ignored);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::path::PathBuf;
+
+    use super::*;
+
+    #[test]
+    fn test_use_client() {
+        let config = Config {
+            typescript: Some(true),
+            ..Config::default()
+        };
+        let path = PathBuf::from("../../tests/use_client/counter.tsx");
+        let (module, cm, _comments) = parse_module(&path, &config);
+        assert_eq!(
+            client_usage(&module),
+            vec![
+                "calls useState",
+                "passes the event handler onClick",
+                "uses window"
+            ]
+        );
+        assert_eq!(use_client_module(&module, &cm, true), None);
+        let output = use_client_module(&module, &cm, false).unwrap();
+        assert!(output.starts_with(
+            "// Counter shown in the header\n\"use client\";\n\nimport { useState } from \"react\";"
+        ));
+
+        let path = PathBuf::from("../../tests/use_client/heading.tsx");
+        let (module, cm, _comments) = parse_module(&path, &config);
+        assert_eq!(use_client_module(&module, &cm, false), None);
+        let output = use_client_module(&module, &cm, true).unwrap();
+        assert!(output.starts_with("import { Title } from \"./title\";"));
+
+        // A string statement after the imports is no directive
+        let path = PathBuf::from("../../tests/use_client/banner.tsx");
+        let (module, cm, _comments) = parse_module(&path, &config);
+        assert_eq!(use_client_module(&module, &cm, true), None);
+        let output = use_client_module(&module, &cm, false).unwrap();
+        assert!(output.starts_with("\"use client\";\n\nimport { useState } from \"react\";"));
+    }
+}
@@ -1,17 +1,23 @@
+use std::collections::HashMap;
 use std::path::PathBuf;
 
 use glob::Pattern;
 use remod_config::Config;
-use swc_common::comments::SingleThreadedComments;
+use swc_common::comments::{CommentKind, Comments, SingleThreadedComments};
 use swc_common::sync::Lrc;
-use swc_common::FileName;
 use swc_common::{
     errors::{ColorConfig, Handler},
     SourceMap,
 };
-use swc_ecma_ast::{Expr, Module, Program};
+use swc_common::{BytePos, FileName, SourceMapper, Span, Spanned};
+use swc_ecma_ast::{
+    Decl, DefaultDecl, Expr, Ident, ImportDecl, ImportSpecifier, JSXAttrName, JSXMemberExpr,
+    MemberProp, Module, ModuleDecl, ModuleExportName, ModuleItem, Pat, Program, PropName,
+    TsQualifiedName,
+};
 use swc_ecma_parser::{lexer::Lexer, Parser, StringInput, Syntax};
 use swc_ecma_parser::{Capturing, EsConfig, TsConfig};
+use swc_ecma_visit::{Visit, VisitWith};
 
 pub fn should_ignore_entry(ignore: &Vec<String>, path: &PathBuf) -> bool {
     ignore.iter().any(|it| {
@@ -165,3 +171,474 @@ pub fn is_jsx_like(expr: &Box<Expr>) -> bool {
         _ => false,
     }
 }
+
+/// End of a parameter including its type annotation
+pub fn pat_end(pat: &Pat) -> BytePos {
+    match pat {
+        Pat::Ident(id) => id.type_ann.as_ref().map_or(id.id.span.hi, |t| t.span.hi),
+        Pat::Object(obj) => obj.type_ann.as_ref().map_or(obj.span.hi, |t| t.span.hi),
+        Pat::Array(array) => array.type_ann.as_ref().map_or(array.span.hi, |t| t.span.hi),
+        _ => pat.span_hi(),
+    }
+}
+
+/// Names of the exported declarations of the module
+pub fn exported_names(module: &Module) -> Vec<String> {
+    let mut names = vec![];
+    for item in module.body.iter() {
+        match item {
+            ModuleItem::ModuleDecl(ModuleDecl::ExportDecl(export)) => match export.decl {
+                Decl::Fn(ref func) => names.push(func.ident.sym.to_string()),
+                Decl::Var(ref var) => names.extend(
+                    var.decls
+                        .iter()
+                        .filter_map(|d| d.name.as_ident().map(|id| id.sym.to_string())),
+                ),
+                _ => {}
+            },
+            ModuleItem::ModuleDecl(ModuleDecl::ExportDefaultDecl(export)) => {
+                if let DefaultDecl::Fn(ref func) = export.decl {
+                    names.extend(func.ident.as_ref().map(|id| id.sym.to_string()));
+                }
+            }
+            ModuleItem::ModuleDecl(ModuleDecl::ExportDefaultExpr(export)) => {
+                if let Expr::Ident(ref id) = *export.expr {
+                    names.push(id.sym.to_string());
+                }
+            }
+            // export { Foo }
+            ModuleItem::ModuleDecl(ModuleDecl::ExportNamed(export)) if export.src.is_none() => {
+                names.extend(
+                    export
+                        .specifiers
+                        .iter()
+                        .filter_map(|s| match s.as_named()?.orig {
+                            ModuleExportName::Ident(ref id) => Some(id.sym.to_string()),
+                            ModuleExportName::Str(..) => None,
+                        }),
+                )
+            }
+            _ => {}
+        }
+    }
+    names
+}
+
+/// Source text covered by the span
+pub fn snippet(cm: &SourceMap, span: Span) -> String {
+    cm.span_to_snippet(span).unwrap_or_default()
+}
+
+/// Text of the JSDoc (`/** ... */`) comment leading the position, without the `*` gutters
+pub fn leading_doc_comment(comments: &SingleThreadedComments, pos: BytePos) -> Option<String> {
+    let leading = comments.get_leading(pos)?;
+    let doc = leading
+        .iter()
+        .rev()
+        .find(|c| c.kind == CommentKind::Block && c.text.starts_with('*'))?;
+    let text = doc
+        .text
+        .lines()
+        .map(|line| line.trim().trim_start_matches('*').trim())
+        .filter(|line| !line.is_empty())
+        .collect::<Vec<&str>>()
+        .join("\n");
+    if text.is_empty() {
+        None
+    } else {
+        Some(text)
+    }
+}
+
+/// Replacement of the source text between two positions of a parsed file
+#[derive(Debug, Clone, PartialEq)]
+pub struct SourceEdit {
+    pub start: BytePos,
+    pub end: BytePos,
+    pub text: String,
+}
+
+impl SourceEdit {
+    pub fn insert(pos: BytePos, text: String) -> Self {
+        SourceEdit {
+            start: pos,
+            end: pos,
+            text,
+        }
+    }
+    pub fn replace(span: Span, text: String) -> Self {
+        SourceEdit {
+            start: span.lo,
+            end: span.hi,
+            text,
+        }
+    }
+    /// Removes the span along with the lines it leaves empty
+    pub fn remove_lines(cm: &SourceMap, span: Span) -> Self {
+        let file = cm.lookup_byte_offset(span.lo).sf;
+        let src = file.src.as_str();
+        let mut start = (span.lo - file.start_pos).0 as usize;
+        let mut end = (span.hi - file.start_pos).0 as usize;
+        if src[end..].starts_with(';') {
+            end += 1;
+        }
+        let line_start = src[..start].rfind('\n').map_or(0, |i| i + 1);
+        if src[line_start..start].trim().is_empty() {
+            start = line_start;
+        }
+        let line_end = src[end..].find('\n').map_or(src.len(), |i| end + i + 1);
+        if src[end..line_end].trim().is_empty() {
+            end = line_end;
+            // Avoid leaving two blank lines or a blank line opening a block behind
+            let previous = src[..start].trim_end_matches([' ', '\t']);
+            let previous_line = previous[..previous.len().saturating_sub(1)]
+                .rsplit('\n')
+                .next()
+                .unwrap_or_default()
+                .trim();
+            let next_end = src[end..].find('\n').map_or(src.len(), |i| end + i + 1);
+            if start == line_start
+                && src[end..next_end].trim().is_empty()
+                && (previous_line.is_empty() || previous_line.ends_with('{'))
+            {
+                end = next_end;
+            }
+        }
+        SourceEdit {
+            start: file.start_pos + BytePos(start as u32),
+            end: file.start_pos + BytePos(end as u32),
+            text: String::new(),
+        }
+    }
+}
+
+/// Start of the line holding the position, including the comments leading it
+pub fn line_start_with_comments(
+    cm: &SourceMap,
+    comments: &SingleThreadedComments,
+    pos: BytePos,
+) -> BytePos {
+    let pos = comments
+        .get_leading(pos)
+        .and_then(|leading| leading.iter().map(|c| c.span.lo).min())
+        .unwrap_or(pos);
+    let file = cm.lookup_byte_offset(pos).sf;
+    let offset = (pos - file.start_pos).0 as usize;
+    let line_start = file.src[..offset].rfind('\n').map_or(0, |i| i + 1);
+    file.start_pos + BytePos(line_start as u32)
+}
+
+/// Applies the edits to the source of the file they were computed for.
+/// Edits overlapping an already applied edit are dropped
+pub fn apply_edits(cm: &SourceMap, edits: Vec<SourceEdit>) -> Option<String> {
+    let first = edits.first()?;
+    let file = cm.lookup_byte_offset(first.start).sf;
+    Some(apply_edits_to(&file.src, file.start_pos, edits))
+}
+
+/// Source text covered by the span with the edits falling inside of it applied
+pub fn edit_snippet(cm: &SourceMap, span: Span, edits: &[SourceEdit]) -> String {
+    let edits = edits
+        .iter()
+        .filter(|e| e.start >= span.lo && e.end <= span.hi)
+        .cloned()
+        .collect();
+    apply_edits_to(&snippet(cm, span), span.lo, edits)
+}
+
+fn apply_edits_to(src: &str, start_pos: BytePos, edits: Vec<SourceEdit>) -> String {
+    let mut output = src.to_string();
+    // Applied back to front so the earlier positions stay valid. Insertions at the same
+    // position keep the order they were pushed in
+    let mut edits = edits
+        .into_iter()
+        .enumerate()
+        .collect::<Vec<(usize, SourceEdit)>>();
+    edits.sort_by(|(i, a), (j, b)| b.start.cmp(&a.start).then(b.end.cmp(&a.end)).then(j.cmp(i)));
+    let mut applied_start = usize::MAX;
+    for (_, edit) in edits {
+        let start = (edit.start - start_pos).0 as usize;
+        let end = (edit.end - start_pos).0 as usize;
+        if end > applied_start {
+            continue;
+        }
+        output.replace_range(start..end, &edit.text);
+        applied_start = start;
+    }
+    output
+}
+
+/// Indents the lines of the text by the indent, keeping their relative indentation
+pub fn reindent(text: &str, indent: &str) -> String {
+    let lines = text
+        .trim_matches('\n')
+        .trim_end()
+        .lines()
+        .collect::<Vec<&str>>();
+    let common = lines
+        .iter()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| line.len() - line.trim_start().len())
+        .min()
+        .unwrap_or(0);
+    lines
+        .iter()
+        .map(|line| {
+            if line.trim().is_empty() {
+                String::new()
+            } else {
+                format!("{}{}", indent, &line[common..])
+            }
+        })
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+/// Collects the identifiers referring to the names. Property names and import declarations are skipped
+pub struct IdentReferences {
+    names: Vec<String>,
+    /// Spans of the references, including the bindings declaring the names
+    pub spans: Vec<Span>,
+}
+
+impl IdentReferences {
+    pub fn new(names: Vec<String>) -> Self {
+        IdentReferences {
+            names,
+            spans: vec![],
+        }
+    }
+}
+
+impl Visit for IdentReferences {
+    fn visit_import_decl(&mut self, _: &ImportDecl) {}
+    fn visit_member_prop(&mut self, n: &MemberProp) {
+        if let MemberProp::Computed(ref computed) = n {
+            computed.visit_with(self);
+        }
+    }
+    fn visit_prop_name(&mut self, n: &PropName) {
+        if let PropName::Computed(ref computed) = n {
+            computed.visit_with(self);
+        }
+    }
+    fn visit_jsx_attr_name(&mut self, _: &JSXAttrName) {}
+    fn visit_jsx_member_expr(&mut self, n: &JSXMemberExpr) {
+        n.obj.visit_with(self);
+    }
+    fn visit_ts_qualified_name(&mut self, n: &TsQualifiedName) {
+        n.left.visit_with(self);
+    }
+    fn visit_ident(&mut self, n: &Ident) {
+        if self.names.iter().any(|name| name.as_str() == &*n.sym) {
+            self.spans.push(n.span);
+        }
+    }
+}
+
+/// Name a named import specifier imports, `forwardRef` of `import { forwardRef as fr } from "react"`
+pub fn imported_name(specifier: &ImportSpecifier) -> Option<String> {
+    match specifier {
+        ImportSpecifier::Named(named) => Some(match named.imported {
+            Some(ModuleExportName::Ident(ref id)) => id.sym.to_string(),
+            Some(ModuleExportName::Str(ref s)) => s.value.to_string(),
+            None => named.local.sym.to_string(),
+        }),
+        _ => None,
+    }
+}
+
+/// Removes the specifier at the index from the import declaration along with its separator.
+/// The whole declaration is removed when it is the only specifier
+pub fn remove_import_specifier(cm: &SourceMap, decl: &ImportDecl, index: usize) -> SourceEdit {
+    let specifiers = &decl.specifiers;
+    let specifier = &specifiers[index];
+    if specifiers.len() == 1 {
+        return SourceEdit::remove_lines(cm, decl.span);
+    }
+    let named = specifiers
+        .iter()
+        .filter(|s| matches!(s, ImportSpecifier::Named(..)))
+        .count();
+    if named == 1 && matches!(specifier, ImportSpecifier::Named(..)) {
+        // `import React, { forwardRef } from "react"`, the braces go along
+        let file = cm.lookup_byte_offset(specifier.span().hi).sf;
+        let offset = (specifier.span().hi - file.start_pos).0 as usize;
+        let close = file.src[offset..]
+            .find('}')
+            .map_or(offset, |i| offset + i + 1);
+        return SourceEdit {
+            start: specifiers[index - 1].span().hi,
+            end: file.start_pos + BytePos(close as u32),
+            text: String::new(),
+        };
+    }
+    match specifiers.get(index + 1) {
+        // `import React, { useState } from "react"`, the braces stay
+        Some(_) if matches!(specifier, ImportSpecifier::Default(..)) => {
+            let file = cm.lookup_byte_offset(specifier.span().hi).sf;
+            let offset = (specifier.span().hi - file.start_pos).0 as usize;
+            let next = file.src[offset..]
+                .find(|c: char| c != ',' && !c.is_whitespace())
+                .map_or(offset, |i| offset + i);
+            SourceEdit {
+                start: specifier.span().lo,
+                end: file.start_pos + BytePos(next as u32),
+                text: String::new(),
+            }
+        }
+        Some(next) => SourceEdit {
+            start: specifier.span().lo,
+            end: next.span().lo,
+            text: String::new(),
+        },
+        None => SourceEdit {
+            start: specifiers[index - 1].span().hi,
+            end: specifier.span().hi,
+            text: String::new(),
+        },
+    }
+}
+
+/// Text of the import declaration keeping the specifiers at the indexes
+pub fn import_text(cm: &SourceMap, decl: &ImportDecl, keep: &[usize]) -> String {
+    let mut parts = vec![];
+    let mut named = vec![];
+    for (index, specifier) in decl.specifiers.iter().enumerate() {
+        if !keep.contains(&index) {
+            continue;
+        }
+        match specifier {
+            ImportSpecifier::Named(..) => named.push(snippet(cm, specifier.span())),
+            _ => parts.push(snippet(cm, specifier.span())),
+        }
+    }
+    if !named.is_empty() {
+        parts.push(format!("{{ {} }}", named.join(", ")));
+    }
+    format!(
+        "import {}{} from {};",
+        if decl.type_only { "type " } else { "" },
+        parts.join(", "),
+        snippet(cm, decl.src.span)
+    )
+}
+
+/// Names the `react` package is imported with
+#[derive(Debug, Default)]
+pub struct ReactImports {
+    /// Local name of `import React from "react"` or `import * as React from "react"`
+    pub namespace: Option<String>,
+    /// Local names of the named imports, keyed by the imported name
+    pub named: HashMap<String, String>,
+    /// The import declarations of `react`
+    pub decls: Vec<ImportDecl>,
+    /// Local names bound by the imports of other modules
+    pub other_imports: Vec<String>,
+}
+
+impl ReactImports {
+    /// Local name of the export of `react`, `React.forwardRef` when it is only reachable through the namespace
+    pub fn local_name(&self, export: &str) -> Option<String> {
+        self.named.get(export).cloned().or_else(|| {
+            self.namespace
+                .as_ref()
+                .map(|namespace| format!("{}.{}", namespace, export))
+        })
+    }
+
+    /// Whether the callee is the export of `react`, either imported by name or through the namespace
+    pub fn is_react_export(&self, expr: &Expr, export: &str) -> bool {
+        match expr {
+            Expr::Ident(id) => match self.named.get(export) {
+                Some(local) => local.as_str() == &*id.sym,
+                // Only the namespace import is checked by type checkers, trust the name unless
+                // another module provides it
+                None => &*id.sym == export && !self.other_imports.iter().any(|n| *n == *id.sym),
+            },
+            Expr::Member(member) => match (&*member.obj, &member.prop) {
+                (Expr::Ident(obj), MemberProp::Ident(prop)) => {
+                    &*prop.sym == export
+                        && self
+                            .namespace
+                            .as_ref()
+                            .map_or(&*obj.sym == "React", |n| n.as_str() == &*obj.sym)
+                }
+                _ => false,
+            },
+            _ => false,
+        }
+    }
+
+    /// Adds named imports of `react`, to an existing import declaration when possible
+    pub fn add_named(&self, module: &Module, names: &[String]) -> Option<SourceEdit> {
+        if names.is_empty() {
+            return None;
+        }
+        let names = names.join(", ");
+        let with_named = self.decls.iter().find_map(|decl| {
+            decl.specifiers
+                .iter()
+                .rev()
+                .find(|s| matches!(s, ImportSpecifier::Named(..)))
+        });
+        if let Some(last) = with_named {
+            return Some(SourceEdit::insert(last.span().hi, format!(", {}", names)));
+        }
+        let default = self
+            .decls
+            .iter()
+            .find_map(|decl| match decl.specifiers.as_slice() {
+                [ImportSpecifier::Default(default)] => Some(default),
+                _ => None,
+            });
+        if let Some(default) = default {
+            return Some(SourceEdit::insert(
+                default.span.hi,
+                format!(", {{ {} }}", names),
+            ));
+        }
+        match self.decls.first() {
+            Some(decl) => Some(SourceEdit::insert(
+                decl.span.hi,
+                format!("\nimport {{ {} }} from \"react\";", names),
+            )),
+            None => Some(SourceEdit::insert(
+                module.span.lo,
+                format!("import {{ {} }} from \"react\";\n", names),
+            )),
+        }
+    }
+}
+
+impl Visit for ReactImports {
+    fn visit_import_decl(&mut self, n: &ImportDecl) {
+        if &*n.src.value != "react" {
+            for specifier in n.specifiers.iter() {
+                let local = match specifier {
+                    ImportSpecifier::Default(default) => &default.local,
+                    ImportSpecifier::Namespace(namespace) => &namespace.local,
+                    ImportSpecifier::Named(named) => &named.local,
+                };
+                self.other_imports.push(local.sym.to_string());
+            }
+            return;
+        }
+        for specifier in n.specifiers.iter() {
+            match specifier {
+                ImportSpecifier::Default(default) => {
+                    self.namespace = Some(default.local.sym.to_string())
+                }
+                ImportSpecifier::Namespace(namespace) => {
+                    self.namespace = Some(namespace.local.sym.to_string())
+                }
+                ImportSpecifier::Named(named) => {
+                    if let Some(imported) = imported_name(specifier) {
+                        self.named.insert(imported, named.local.sym.to_string());
+                    }
+                }
+            }
+        }
+        self.decls.push(n.to_owned());
+    }
+}
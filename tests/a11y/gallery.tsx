@@ -0,0 +1,46 @@
+import { useState } from "react";
+import { UnstyledButton } from "./UnstyledButton";
+
+export const Gallery = ({ images }: { images: string[] }) => {
+  const [selected, setSelected] = useState(0);
+  return (
+    <div className="gallery">
+      <img src={images[selected]} />
+      <img src="/spacer.gif" alt="" />
+      <ul>
+        {images.map((image, index) => (
+          <li key={image} onClick={() => setSelected(index)}>
+            <img src={image} alt={`Image ${index + 1}`} />
+          </li>
+        ))}
+      </ul>
+      <span role="button" tabIndex={0} onClick={() => setSelected(0)}>
+        First
+      </span>
+      <UnstyledButton aria-hidden onClick={() => setSelected(selected + 1)} />
+      <UnstyledButton aria-hidden tabIndex={-1} onClick={() => setSelected(0)} />
+      <button aria-hidden="true" onClick={() => setSelected(0)}>
+        Reset
+      </button>
+      <button onClick={() => setSelected(images.length - 1)} />
+    </div>
+  );
+};
+
+export const Search = () => (
+  <form>
+    <input name="query" />
+    <label>
+      Name
+      <input name="name" />
+    </label>
+    <label htmlFor="email">Email</label>
+    <input id="email" />
+    <input type="hidden" name="token" />
+    <input type="submit" value="Search" />
+    <select aria-label="Sort">
+      <option>Newest</option>
+    </select>
+    <textarea id="notes" />
+  </form>
+);
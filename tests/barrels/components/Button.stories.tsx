@@ -0,0 +1,5 @@
+import { Button } from "./Button";
+
+export default { component: Button };
+
+export const Primary = () => <Button label="Save" />;
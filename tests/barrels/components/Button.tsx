@@ -0,0 +1,7 @@
+export interface ButtonProps {
+  label: string;
+}
+
+export const Button = ({ label }: ButtonProps) => <button>{label}</button>;
+
+export const IconButton = ({ label }: ButtonProps) => <button aria-label={label} />;
@@ -0,0 +1,5 @@
+export type CardProps = { title: string };
+
+export default function Card({ title }: CardProps) {
+  return <section>{title}</section>;
+}
@@ -0,0 +1 @@
+export const formatLabel = (label: string) => label.trim();
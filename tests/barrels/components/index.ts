@@ -0,0 +1,3 @@
+export * from "./hooks";
+export { Button } from "./Button";
+export { Avatar } from "./Avatar";
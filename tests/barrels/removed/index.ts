@@ -0,0 +1 @@
+export { Avatar } from "./Avatar";
@@ -0,0 +1,58 @@
+import React, { Component } from "react";
+
+interface CounterProps {
+  label: string;
+}
+
+interface CounterState {
+  count: number;
+  active: boolean;
+}
+
+export class Counter extends Component<CounterProps, CounterState> {
+  static displayName = "@remod/Counter";
+
+  constructor(props: CounterProps) {
+    super(props);
+    this.state = { count: 0, active: false };
+    this.toggle = this.toggle.bind(this);
+  }
+
+  componentDidMount() {
+    document.title = this.props.label;
+  }
+
+  componentDidUpdate() {
+    document.title = `${this.props.label} ${this.state.count}`;
+  }
+
+  componentWillUnmount() {
+    document.title = "";
+  }
+
+  toggle() {
+    this.setState({ active: !this.state.active });
+  }
+
+  increment = () => {
+    this.setState({ count: this.state.count + 1 });
+    this.toggle();
+  };
+
+  render() {
+    const { count, active } = this.state;
+    return (
+      <button className={active ? "active" : ""} onClick={this.increment}>
+        {this.props.label} {count}
+      </button>
+    );
+  }
+}
+
+export class ErrorBoundary extends React.Component<CounterProps> {
+  componentDidCatch() {}
+
+  render() {
+    return this.props.children;
+  }
+}
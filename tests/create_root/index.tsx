@@ -0,0 +1,18 @@
+import React from "react";
+import ReactDOM from "react-dom";
+import { App } from "./App";
+import { Widget } from "./Widget";
+
+ReactDOM.render(
+  <React.StrictMode>
+    <App />
+  </React.StrictMode>,
+  document.getElementById("root")
+);
+
+const widget = document.getElementById("widget") as HTMLElement;
+ReactDOM.hydrate(<Widget />, widget);
+
+export function teardown() {
+  ReactDOM.unmountComponentAtNode(widget);
+}
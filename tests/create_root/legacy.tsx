@@ -0,0 +1,22 @@
+import { render, unmountComponentAtNode, createPortal } from "react-dom";
+import { Modal } from "./Modal";
+
+export function mount(container: HTMLElement) {
+  render(<Modal />, container, () => console.log("mounted"));
+}
+
+export function show(container: HTMLElement) {
+  render(<Modal />, container);
+}
+
+export function preview(node: HTMLElement) {
+  render(<Modal />, node);
+}
+
+export function unmount(container: HTMLElement) {
+  return unmountComponentAtNode(container);
+}
+
+render(<Modal />, document.getElementById("modal"));
+
+export const portal = (node: HTMLElement) => createPortal(<Modal />, node);
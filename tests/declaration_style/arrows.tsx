@@ -0,0 +1,19 @@
+import React, { memo } from "react";
+
+interface ListProps<T> {
+  items: T[];
+}
+
+export const Title: React.FC<{ text: string }> = ({ text }) => <h1>{text}</h1>;
+
+export const List = <T,>({ items }: ListProps<T>): JSX.Element => {
+  return <ul>{items.map((item) => <li>{String(item)}</li>)}</ul>;
+};
+
+export const Badge = memo((props: { label: string }) => {
+  return <span>{props.label}</span>;
+});
+
+Badge.displayName = "Badge";
+
+const format = (value: string) => value.trim();
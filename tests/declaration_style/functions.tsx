@@ -0,0 +1,25 @@
+import React, { memo } from "react";
+
+interface ListProps<T> {
+  items: T[];
+}
+
+export function List<T>({ items }: ListProps<T>): JSX.Element {
+  return <ul>{items.map((item) => <li>{String(item)}</li>)}</ul>;
+}
+
+export const Badge = memo(function Badge(props: { label: string }) {
+  return <span>{props.label}</span>;
+});
+
+export const Tag = memo(function (props: { text: string }) {
+  return <em>{props.text}</em>;
+});
+
+export default function Page() {
+  return <List items={[1, 2]} />;
+}
+
+function Legacy() {
+  return <div>{arguments.length}</div>;
+}
@@ -0,0 +1,27 @@
+import * as React from "react";
+
+export const Button = ({ label, size, disabled: isDisabled, ...others }: ButtonProps) => {
+  return <button disabled={isDisabled} className={size} {...others}>{label}</button>;
+};
+
+Button.defaultProps = {
+  size: "md",
+  disabled: false,
+  type: "button",
+};
+
+const defaultProps = {
+  color: "blue",
+  variant: "filled",
+};
+
+export function Badge({ color, ...others }: BadgeProps) {
+  const props = useProps("Badge", defaultProps, others);
+  return <span className={color}>{props.children}</span>;
+}
+
+export const Link = ({ href }: LinkProps) => <a href={href} />;
+
+Link.defaultProps = {
+  target: "_blank",
+};
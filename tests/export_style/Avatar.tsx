@@ -0,0 +1,5 @@
+export type AvatarSize = "small" | "large";
+
+export default function Avatar({ size }: { size: AvatarSize }) {
+  return <img className={size} />;
+}
@@ -0,0 +1,3 @@
+export const BADGE_SIZES = ["small", "large"];
+
+export const Badge = ({ label }: { label: string }) => <span>{label}</span>;
@@ -0,0 +1,7 @@
+import type { ReactNode } from "react";
+
+const Card = ({ children }: { children: ReactNode }) => <div>{children}</div>;
+
+Card.displayName = "Card";
+
+export default Card;
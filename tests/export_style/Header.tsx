@@ -0,0 +1 @@
+export default () => <header>Remod</header>;
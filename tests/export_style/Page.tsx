@@ -0,0 +1,16 @@
+import { lazy } from "react";
+import Card from "./Card";
+import Picture, { type AvatarSize } from "./Avatar";
+import Header from "./Header";
+import { Badge, BADGE_SIZES } from "./Badge";
+
+const LazyCard = lazy(() => import("./Card"));
+
+export const Page = ({ size }: { size: AvatarSize }) => (
+  <Card>
+    <Header />
+    <Picture size={size} />
+    <Badge label={BADGE_SIZES[0]} />
+    <LazyCard>Lazy</LazyCard>
+  </Card>
+);
@@ -0,0 +1,3 @@
+export { default as Card } from "./Card";
+export { default } from "./Avatar";
+export * from "./Badge";
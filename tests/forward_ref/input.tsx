@@ -0,0 +1,19 @@
+import React, { forwardRef, useState } from "react";
+
+interface InputProps {
+  label: string;
+}
+
+export const Input = forwardRef<HTMLInputElement, InputProps>(({ label, ...others }, ref) => {
+  const [value, setValue] = useState("");
+  return <input ref={ref} aria-label={label} value={value} {...others} />;
+});
+
+Input.displayName = "@remod/Input";
+
+export const TextArea = React.forwardRef(function TextArea(
+  props: InputProps,
+  forwardedRef: React.ForwardedRef<HTMLTextAreaElement>
+) {
+  return <textarea ref={forwardedRef} aria-label={props.label} />;
+});
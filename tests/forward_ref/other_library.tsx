@@ -0,0 +1,9 @@
+import { forwardRef } from "@acme/ui-kit";
+
+interface FieldProps {
+  label: string;
+}
+
+export const Field = forwardRef<HTMLInputElement, FieldProps>(({ label }, ref) => (
+  <input ref={ref} aria-label={label} />
+));
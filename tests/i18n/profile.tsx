@@ -0,0 +1,27 @@
+import { useState } from "react";
+
+export const ProfileCard = ({ name }: { name: string }) => (
+  <div className="profile" title="User profile">
+    <h2>Welcome back, {name}!</h2>
+    <p>
+      Update your details below.
+    </p>
+    <code>npm install</code>
+  </div>
+);
+
+export function SearchBox() {
+  const [query, setQuery] = useState("");
+  return (
+    <label>
+      Search
+      <input
+        value={query}
+        placeholder="Search users"
+        aria-label="Search users"
+        onChange={(e) => setQuery(e.target.value)}
+      />
+      <span>{query.length} / 100</span>
+    </label>
+  );
+}
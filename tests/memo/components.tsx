@@ -0,0 +1,18 @@
+import { forwardRef, useState } from "react";
+
+export const Label = ({ text }: { text: string }) => <label>{text}</label>;
+
+Label.displayName = "@remod/Label";
+
+export function ListItem({ children }: { children: React.ReactNode }) {
+  return <li>{children}</li>;
+}
+
+export const Toggle = () => {
+  const [on, setOn] = useState(false);
+  return <button onClick={() => setOn(!on)}>{on ? "On" : "Off"}</button>;
+};
+
+export const Field = forwardRef<HTMLInputElement>((props, ref) => <input ref={ref} {...props} />);
+
+const Internal = () => <span />;
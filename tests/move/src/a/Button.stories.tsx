@@ -0,0 +1,6 @@
+import type { Meta } from "@storybook/react";
+import { Button } from "./Button";
+
+const meta: Meta<typeof Button> = { component: Button };
+
+export default meta;
@@ -0,0 +1,12 @@
+import { cx } from "@lib";
+import { formatLabel } from "../lib/format";
+import { Icon } from "./Icon";
+import styles from "./Button.module.css";
+import { Missing } from "./Missing";
+
+export const Button = ({ label }: { label: string }) => (
+  <button className={cx(styles.button)}>
+    <Icon />
+    {formatLabel(label)}
+  </button>
+);
@@ -0,0 +1,11 @@
+import { Button } from "@/a/Button";
+import { Icon } from "../a/Icon";
+
+const Lazy = import("../a/Button.tsx");
+
+export const Home = () => (
+  <main>
+    <Icon />
+    <Button label="Go" />
+  </main>
+);
@@ -0,0 +1,27 @@
+import React from "react";
+import PropTypes from "prop-types";
+
+export const Button = ({ label, size }) => {
+  return <button className={size}>{label}</button>;
+};
+
+Button.propTypes = {
+  /** Text of the button */
+  label: PropTypes.string.isRequired,
+  size: PropTypes.oneOf(["small", "large"]),
+  onClick: PropTypes.func,
+  items: PropTypes.arrayOf(
+    PropTypes.shape({ id: PropTypes.number.isRequired, name: PropTypes.string })
+  ),
+  value: PropTypes.oneOfType([PropTypes.string, PropTypes.number]),
+};
+
+class Card extends React.Component {
+  static propTypes = {
+    title: PropTypes.node,
+  };
+
+  render() {
+    return <div>{this.props.title}</div>;
+  }
+}
@@ -0,0 +1,54 @@
+import { forwardRef } from "react";
+
+interface BaseSliderProps {
+  /** Disables the slider */
+  disabled?: boolean;
+}
+
+export interface SliderProps extends BaseSliderProps {
+  /** Current value */
+  value: number;
+  /** Step between the values, `1` by default */
+  step?: number;
+  size?: "sm" | "md" | "lg";
+}
+
+const Thumb = () => <span className="thumb" />;
+
+/** Picks a number in a range */
+export const Slider = ({
+  value,
+  step = 1,
+  size = "md",
+  disabled = false,
+}: SliderProps) => (
+  <div data-size={size} aria-disabled={disabled} aria-valuenow={value} data-step={step}>
+    <Thumb />
+  </div>
+);
+
+type RangeSliderProps = {
+  min?: number;
+  max?: number;
+  label: string;
+} & BaseSliderProps;
+
+const sliderDefaults = {
+  disabled: true,
+};
+
+export const RangeSlider = forwardRef<HTMLDivElement, RangeSliderProps>(
+  ({ min = 5, max, label, disabled, ...rest }, ref) => {
+    const state = useSliderState(sliderDefaults);
+    return (
+      <div ref={ref} aria-label={label} aria-disabled={disabled ?? state.disabled} {...rest}>
+        {min} - {max}
+      </div>
+    );
+  }
+);
+
+RangeSlider.defaultProps = {
+  min: 0,
+  max: 100,
+};
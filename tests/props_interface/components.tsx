@@ -0,0 +1,35 @@
+import type { FC, HTMLAttributes, ReactNode } from "react";
+
+/** Shows a user */
+export const UserCard = ({ name, age }: { name: string; age?: number }) => (
+  <div>
+    {name} {age}
+  </div>
+);
+
+export function Panel({
+  title,
+  children,
+  ...rest
+}: {
+  /** Heading of the panel */
+  title: string;
+  children: ReactNode;
+} & HTMLAttributes<HTMLDivElement>) {
+  return (
+    <section {...rest}>
+      <h2>{title}</h2>
+      {children}
+    </section>
+  );
+}
+
+export const List = <T,>({ items }: { items: T[] }) => <ul>{items.length}</ul>;
+
+export const Badge: FC<{ label: string }> = ({ label }) => <span>{label}</span>;
+
+interface ButtonProps {
+  label: string;
+}
+
+export const Button = ({ label }: ButtonProps) => <button>{label}</button>;
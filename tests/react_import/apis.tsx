@@ -0,0 +1,12 @@
+import * as React from "react";
+
+export const Context = React.createContext<string>("");
+
+export const Provider: React.FC<{ value: string }> = ({ value, children }) => {
+  const [state] = React.useState(value);
+  return (
+    <React.Fragment>
+      <Context.Provider value={state}>{children}</Context.Provider>
+    </React.Fragment>
+  );
+};
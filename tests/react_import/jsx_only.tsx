@@ -0,0 +1,6 @@
+import React, { useState } from "react";
+
+export function Toggle() {
+  const [on, setOn] = useState(false);
+  return <button onClick={() => setOn(!on)}>{on ? "On" : "Off"}</button>;
+}
@@ -0,0 +1,15 @@
+import type { ReactNode } from "react";
+
+export interface ButtonProps {
+  children: ReactNode;
+  onClick?: () => void;
+}
+
+/** Primary action of a form */
+export const Button = ({ children, onClick }: ButtonProps) => (
+  <button onClick={onClick}>{children}</button>
+);
+
+Button.displayName = "Button";
+
+export const IconButton = (props: ButtonProps) => <Button {...props}>★</Button>;
@@ -0,0 +1,10 @@
+import { Button as Action, Toolbar } from ".";
+
+export default function Page() {
+  return (
+    <main>
+      <Toolbar />
+      <Action>Save</Action>
+    </main>
+  );
+}
@@ -0,0 +1,10 @@
+import { Button, IconButton, type ButtonProps } from "./Button";
+import { Button as LegacyButton } from "../legacy";
+
+export const Toolbar = (props: ButtonProps) => (
+  <div>
+    <Button {...props} />
+    <IconButton {...props} />
+    <LegacyButton>Back</LegacyButton>
+  </div>
+);
@@ -0,0 +1,2 @@
+export * from "./Button";
+export { Toolbar } from "./Toolbar";
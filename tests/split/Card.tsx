@@ -0,0 +1,44 @@
+"use client";
+
+import { useState, type ReactNode } from "react";
+import { Icon } from "./Icon";
+
+export interface CardProps {
+  title: string;
+  children: ReactNode;
+}
+
+export const Card = ({ title, children }: CardProps) => {
+  const [open, setOpen] = useState(true);
+  return (
+    <section>
+      <CardHeader title={title} onToggle={() => setOpen(!open)} />
+      {open && children}
+    </section>
+  );
+};
+
+Card.displayName = "Card";
+
+const toggleLabel = (open: boolean) => (open ? "Collapse" : "Expand");
+
+export interface CardHeaderProps {
+  title: string;
+  onToggle: () => void;
+}
+
+/** Heading of a card */
+export const CardHeader = ({ title, onToggle }: CardHeaderProps) => (
+  <header>
+    <h2>{title}</h2>
+    <button aria-label={toggleLabel(true)} onClick={onToggle}>
+      <Icon name="chevron" />
+    </button>
+  </header>
+);
+
+CardHeader.displayName = "CardHeader";
+
+export function CardFooter({ children }: { children: ReactNode }) {
+  return <footer>{children}</footer>;
+}
@@ -0,0 +1,7 @@
+import { Card, CardFooter as Footer, type CardHeaderProps } from "./Card";
+
+export const Page = ({ header }: { header: CardHeaderProps }) => (
+  <Card title={header.title}>
+    <Footer>Done</Footer>
+  </Card>
+);
@@ -0,0 +1,12 @@
+import { type ReactNode } from "react";
+
+function Tabs({ children }: { children: ReactNode }) {
+  return <div role="tablist">{children}</div>;
+}
+
+/** A tab of the list */
+const Tab = ({ label }: { label: string }) => <button role="tab">{label}</button>;
+
+Tab.displayName = "Tab";
+
+export { Tabs, Tab };
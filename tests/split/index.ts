@@ -0,0 +1,2 @@
+export * from "./Card";
+export { Card as default } from "./Card";
@@ -0,0 +1,17 @@
+// @ts-nocheck
+import * as React from "react";
+
+interface ButtonProps {
+  /** Text rendered inside the button */
+  label: string;
+  /** Visual style of the button */
+  variant?: "primary" | "secondary";
+  onClick?: () => void;
+}
+
+/**
+ * Primary UI component for user interaction
+ */
+export const Button = ({ label, variant = "primary" }: ButtonProps) => {
+  return <button className={variant}>{label}</button>;
+};
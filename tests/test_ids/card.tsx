@@ -0,0 +1,23 @@
+import { Fragment } from "react";
+
+export const UserCard = ({ name }: { name: string }) => (
+  <div className="card">
+    <span data-testid="user-card-name">{name}</span>
+  </div>
+);
+
+export function HTMLPreview({ html, loading }: { html: string; loading: boolean }) {
+  const items = [html].map((item) => <p>{item}</p>);
+  if (loading) {
+    return <span className="spinner" />;
+  }
+  return <section dangerouslySetInnerHTML={{ __html: html }} data-testid="preview" />;
+}
+
+export const List = () => (
+  <>
+    <li />
+  </>
+);
+
+export const Wrapper = () => <UserCard name="wrapped" />;
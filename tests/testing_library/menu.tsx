@@ -0,0 +1,13 @@
+const MenuItem = ({ label }: { label: string }) => <li>{label}</li>;
+
+export const Menu = () => (
+  <ul>
+    <MenuItem label="Home" />
+  </ul>
+);
+
+function MenuPage() {
+  return <Menu />;
+}
+
+export default MenuPage;
@@ -0,0 +1,11 @@
+import { lazy } from "react";
+import { Button, Card } from "@/components";
+
+const Settings = lazy(() => import("./pages/Settings"));
+
+export const App = () => (
+  <Card>
+    <Button label="Open" />
+    <Settings />
+  </Card>
+);
@@ -0,0 +1,12 @@
+import type { ReactNode } from "react";
+
+export const CardHeader = () => <header />;
+
+export const CardFooter = () => <footer />;
+
+export const Card = ({ children }: { children: ReactNode }) => (
+  <div>
+    <CardHeader />
+    {children}
+  </div>
+);
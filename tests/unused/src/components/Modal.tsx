@@ -0,0 +1,3 @@
+const Modal = () => <dialog open />;
+
+export default Modal;
@@ -0,0 +1,8 @@
+import type { Meta } from "@storybook/react";
+import { Tooltip } from "./Tooltip";
+
+const meta: Meta<typeof Tooltip> = {
+  component: Tooltip,
+};
+
+export default meta;
@@ -0,0 +1 @@
+export const Tooltip = () => <span role="tooltip" />;
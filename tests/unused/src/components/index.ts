@@ -0,0 +1,3 @@
+export * from "./Button";
+export { Card, CardHeader, CardFooter } from "./Card";
+export { Tooltip } from "./Tooltip";
@@ -0,0 +1,5 @@
+import Modal from "../components/Modal";
+
+export default function Settings() {
+  return <Modal />;
+}
@@ -0,0 +1,8 @@
+import { useState } from "react";
+
+"use client";
+
+export function Banner() {
+  const [open, setOpen] = useState(true);
+  return <p hidden={!open}>Welcome</p>;
+}
@@ -0,0 +1,11 @@
+// Counter shown in the header
+import { useState } from "react";
+
+export function Counter() {
+  const [count, setCount] = useState(0);
+  return <button onClick={() => setCount(count + 1)}>{count}</button>;
+}
+
+export function Width() {
+  return <span>{window.innerWidth}</span>;
+}
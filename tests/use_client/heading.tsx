@@ -0,0 +1,7 @@
+"use client";
+
+import { Title } from "./title";
+
+export function Heading({ text }: { text: string }) {
+  return <Title document={text} />;
+}